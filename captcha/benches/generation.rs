@@ -1,13 +1,23 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use raidprotect_captcha::{generate_captcha, generate_captcha_png};
+use raidprotect_captcha::{generate_captcha_png_with_rng, generate_captcha_with_rng};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 
+// A seeded generator is used so that the same image is generated on each
+// iteration.
 pub fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("captcha with 6 letters", |b| {
-        b.iter(|| generate_captcha(black_box("ABCDEF")))
+        b.iter(|| {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            generate_captcha_with_rng(black_box("ABCDEF"), &mut rng)
+        })
     });
 
     c.bench_function("captcha with 6 letters as png", |b| {
-        b.iter(|| generate_captcha_png(black_box("ABCDEF")))
+        b.iter(|| {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            generate_captcha_png_with_rng(black_box("ABCDEF"), &mut rng)
+        })
     });
 }
 
@@ -8,9 +8,11 @@
 use argh::FromArgs;
 use imageproc::window::display_image;
 use raidprotect_captcha::{
-    code::{random_code, random_human_code},
-    generate_captcha,
+    code::{random_code_with_rng, random_human_code_with_rng},
+    generate_captcha_with, CaptchaOptions, Difficulty,
 };
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 /// Generate a captcha.
 #[derive(FromArgs, Debug)]
@@ -27,19 +29,45 @@ pub struct CaptchaArgs {
     /// whether the generated code should be easy to read for a human
     #[argh(switch, short = 'h')]
     human: bool,
+    /// seed of the random number generator, to reproduce a captcha
+    #[argh(option, short = 's')]
+    seed: Option<u64>,
+    /// difficulty of the captcha (easy, medium or hard)
+    #[argh(
+        option,
+        default = "Difficulty::Medium",
+        from_str_fn(parse_difficulty),
+        short = 'd'
+    )]
+    difficulty: Difficulty,
+}
+
+fn parse_difficulty(value: &str) -> Result<Difficulty, String> {
+    match value {
+        "easy" => Ok(Difficulty::Easy),
+        "medium" => Ok(Difficulty::Medium),
+        "hard" => Ok(Difficulty::Hard),
+        _ => Err(format!("unknown difficulty: {value}")),
+    }
 }
 
 fn main() {
     let args: CaptchaArgs = argh::from_env();
+    let mut rng: Box<dyn RngCore> = match args.seed {
+        Some(seed) => Box::new(ChaCha8Rng::seed_from_u64(seed)),
+        None => Box::new(rand::thread_rng()),
+    };
+
     let code = args.code.unwrap_or_else(|| {
         if args.human {
-            random_human_code(args.length)
+            random_human_code_with_rng(args.length, &mut rng)
         } else {
-            random_code(args.length)
+            random_code_with_rng(args.length, &mut rng)
         }
     });
 
-    let image = generate_captcha(&code);
+    let options = CaptchaOptions::new(args.difficulty);
+    let image = generate_captcha_with(&code, &options, &mut rng).into_luma8();
     let (width, height) = image.dimensions();
 
     if let Some(output) = args.output {
@@ -3,20 +3,29 @@
 //! - [`random_code`] generates a random code using alphabetic ascii characters.
 //! - [`random_human_code`] generates a random human-readable code using
 //!   alphabetic ascii character.
+//!
+//! The `_with_rng` variants of each function take a caller-supplied random
+//! number generator.
 
-use rand::{rngs::ThreadRng, Rng};
+use rand::Rng;
 
 /// Generates a random code.
 ///
 /// The generated code is a [`String`] of `len` random a-z ascii characters.
 pub fn random_code(len: usize) -> String {
+    random_code_with_rng(len, &mut rand::thread_rng())
+}
+
+/// Generates a random code with the provided random number generator.
+///
+/// See [`random_code`] for more information.
+pub fn random_code_with_rng<R: Rng + ?Sized>(len: usize, rng: &mut R) -> String {
     const CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
 
-    let mut rng = rand::thread_rng();
     let mut code = String::with_capacity(len);
 
     for _ in 0..len {
-        code.push(random_char(&mut rng, CHARSET));
+        code.push(random_char(rng, CHARSET));
     }
 
     code
@@ -28,24 +37,31 @@ pub fn random_code(len: usize) -> String {
 ///
 /// Adapted from [Proquints](https://arxiv.org/html/0901.4016).
 pub fn random_human_code(len: usize) -> String {
+    random_human_code_with_rng(len, &mut rand::thread_rng())
+}
+
+/// Generates a random human-readable code with the provided random number
+/// generator.
+///
+/// See [`random_human_code`] for more information.
+pub fn random_human_code_with_rng<R: Rng + ?Sized>(len: usize, rng: &mut R) -> String {
     const CONSONANTS: &[u8] = b"bdfghjklmnprstvz";
     const VOWELS: &[u8] = b"aiou";
 
-    let mut rng = rand::thread_rng();
-    let mut code = String::with_capacity(5);
+    let mut code = String::with_capacity(len);
 
     for idx in 0..len {
         if idx % 2 == 0 {
-            code.push(random_char(&mut rng, CONSONANTS));
+            code.push(random_char(rng, CONSONANTS));
         } else {
-            code.push(random_char(&mut rng, VOWELS));
+            code.push(random_char(rng, VOWELS));
         }
     }
 
     code
 }
 
-fn random_char(rng: &mut ThreadRng, charset: &[u8]) -> char {
+fn random_char<R: Rng + ?Sized>(rng: &mut R, charset: &[u8]) -> char {
     let index = rng.gen_range(0..charset.len());
 
     charset[index] as char
@@ -53,7 +69,10 @@ fn random_char(rng: &mut ThreadRng, charset: &[u8]) -> char {
 
 #[cfg(test)]
 mod tests {
-    use super::{random_code, random_human_code};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::{random_code, random_code_with_rng, random_human_code, random_human_code_with_rng};
 
     #[test]
     fn test_random_code() {
@@ -72,4 +91,17 @@ mod tests {
         assert_eq!(code_1.len(), 6);
         assert_ne!(code_1, code_2);
     }
+
+    #[test]
+    fn test_seeded_code() {
+        let code_1 = random_code_with_rng(6, &mut ChaCha8Rng::seed_from_u64(42));
+        let code_2 = random_code_with_rng(6, &mut ChaCha8Rng::seed_from_u64(42));
+
+        assert_eq!(code_1, code_2);
+
+        let code_1 = random_human_code_with_rng(6, &mut ChaCha8Rng::seed_from_u64(42));
+        let code_2 = random_human_code_with_rng(6, &mut ChaCha8Rng::seed_from_u64(42));
+
+        assert_eq!(code_1, code_2);
+    }
 }
@@ -3,23 +3,33 @@
 //! This library contains the captcha image generator used by RaidProtect. The
 //! generated [`GrayImage`] can be converted to any relevant image format. A
 //! [`generate_captcha_png`] function is provided for convenience.
+//!
+//! The generation is randomized with [`rand::thread_rng`]. The `_with_rng`
+//! variants of each function take a caller-supplied random number generator,
+//! so that a seeded generator can be used to reproduce an image.
+//!
+//! The difficulty of the generated captcha can be configured with
+//! [`CaptchaOptions`] and the [`generate_captcha_with`] function.
 
 pub mod code;
+pub mod options;
 
 use std::io::Cursor;
 
 use image::{
     imageops::overlay, DynamicImage, GrayAlphaImage, GrayImage, ImageError, ImageOutputFormat,
-    LumaA, Pixel,
+    LumaA, Pixel, Rgb, RgbImage,
 };
 use imageproc::{
     drawing,
     geometric_transformations::{self, Interpolation, Projection},
 };
 use once_cell::sync::Lazy;
-use rand::{rngs::ThreadRng, seq::SliceRandom, Rng};
+use rand::{seq::SliceRandom, Rng};
 use rusttype::{Font, Scale};
 
+pub use crate::options::{CaptchaOptions, Difficulty};
+
 /// Font used for the captcha generation.
 ///
 /// The font is part of the GNU FreeFont family and licensed under GNU GPL v3.
@@ -33,26 +43,71 @@ const LETTER_WIDTH: u32 = 80;
 
 /// Generate a new captcha image with the provided code.
 pub fn generate_captcha(code: &str) -> GrayImage {
+    generate_captcha_with_rng(code, &mut rand::thread_rng())
+}
+
+/// Generate a new captcha image with the provided code and random number
+/// generator.
+///
+/// The same image is generated for a given code and generator state.
+pub fn generate_captcha_with_rng<R: Rng + ?Sized>(code: &str, rng: &mut R) -> GrayImage {
+    generate_captcha_with(code, &CaptchaOptions::default(), rng).into_luma8()
+}
+
+/// Generate a new captcha image with the provided code, options and random
+/// number generator.
+///
+/// The image is a [`DynamicImage::ImageLuma8`], or a
+/// [`DynamicImage::ImageRgb8`] if colors are set in the options.
+pub fn generate_captcha_with<R: Rng + ?Sized>(
+    code: &str,
+    options: &CaptchaOptions,
+    rng: &mut R,
+) -> DynamicImage {
     let image_width = (code.len() as u32 * LETTER_WIDTH) + 40;
     let mut image = GrayAlphaImage::from_pixel(image_width, IMAGE_HEIGHT, LumaA([255, 255]));
-    let mut rng = rand::thread_rng();
 
     for (index, letter) in code.char_indices() {
         let x = (index as u32 * LETTER_WIDTH) + 20;
         let y = rng.gen_range(0..70);
 
-        let letter_image = generate_letter(letter, &mut rng);
+        let letter_image = generate_letter(letter, options, rng);
         overlay(&mut image, &letter_image, x as i64, y);
     }
 
-    image_noise(&mut image, &mut rng);
+    interference_lines(&mut image, options.lines, rng);
+    image_noise(&mut image, options.noise, rng);
 
-    DynamicImage::ImageLumaA8(image).to_luma8()
+    let image = DynamicImage::ImageLumaA8(image).into_luma8();
+
+    match options.color {
+        Some((text, background)) => DynamicImage::ImageRgb8(colorize(&image, text, background)),
+        None => DynamicImage::ImageLuma8(image),
+    }
 }
 
 /// Generate a new captcha with the provided code and encode it as png.
 pub fn generate_captcha_png(code: &str) -> Result<Vec<u8>, ImageError> {
-    let image = generate_captcha(code);
+    generate_captcha_png_with_rng(code, &mut rand::thread_rng())
+}
+
+/// Generate a new captcha with the provided code and random number generator
+/// and encode it as png.
+pub fn generate_captcha_png_with_rng<R: Rng + ?Sized>(
+    code: &str,
+    rng: &mut R,
+) -> Result<Vec<u8>, ImageError> {
+    generate_captcha_png_with(code, &CaptchaOptions::default(), rng)
+}
+
+/// Generate a new captcha with the provided code, options and random number
+/// generator and encode it as png.
+pub fn generate_captcha_png_with<R: Rng + ?Sized>(
+    code: &str,
+    options: &CaptchaOptions,
+    rng: &mut R,
+) -> Result<Vec<u8>, ImageError> {
+    let image = generate_captcha_with(code, options, rng);
     let mut buffer = Cursor::new(Vec::new());
 
     image.write_to(&mut buffer, ImageOutputFormat::Png)?;
@@ -61,7 +116,11 @@ pub fn generate_captcha_png(code: &str) -> Result<Vec<u8>, ImageError> {
 }
 
 /// Generate a captcha letter.
-fn generate_letter(letter: char, rng: &mut ThreadRng) -> GrayAlphaImage {
+fn generate_letter<R: Rng + ?Sized>(
+    letter: char,
+    options: &CaptchaOptions,
+    rng: &mut R,
+) -> GrayAlphaImage {
     let mut image = GrayAlphaImage::new(LETTER_WIDTH, LETTER_HEIGHT);
 
     drawing::draw_text_mut(
@@ -74,14 +133,19 @@ fn generate_letter(letter: char, rng: &mut ThreadRng) -> GrayAlphaImage {
         &letter.to_uppercase().to_string(),
     );
 
-    letter_transform(image, rng)
+    let image = letter_transform(image, options.distortion, rng);
+    letter_rotate(image, options.rotation, rng)
 }
 
 /// Applies a random transformation on the letter.
 ///
 /// A projection is calculated with a randomization of the found image corners
-/// coordinates.
-fn letter_transform(image: GrayAlphaImage, rng: &mut ThreadRng) -> GrayAlphaImage {
+/// coordinates, moved by an offset in the `distortion` range.
+fn letter_transform<R: Rng + ?Sized>(
+    image: GrayAlphaImage,
+    distortion: (f32, f32),
+    rng: &mut R,
+) -> GrayAlphaImage {
     let (width, height) = (image.dimensions().0 as f32, image.dimensions().1 as f32);
 
     // Choose which corners to transform.
@@ -94,7 +158,14 @@ fn letter_transform(image: GrayAlphaImage, rng: &mut ThreadRng) -> GrayAlphaImag
     // Calculate new corners coordinates
     //
     // This code is ugly, but it works -- refactor it if you want.
-    let mut gen_range = || rng.gen_range(15.0..35.0);
+    let (min, max) = distortion;
+    let mut gen_range = || {
+        if min < max {
+            rng.gen_range(min..max)
+        } else {
+            min
+        }
+    };
 
     let top_left_init = (0.0, 0.0);
     let top_right_init = (width, 0.0);
@@ -141,11 +212,72 @@ fn letter_transform(image: GrayAlphaImage, rng: &mut ThreadRng) -> GrayAlphaImag
     }
 }
 
+/// Rotate the letter by a random angle of at most `max` degrees.
+fn letter_rotate<R: Rng + ?Sized>(image: GrayAlphaImage, max: f32, rng: &mut R) -> GrayAlphaImage {
+    if max <= 0.0 {
+        return image;
+    }
+
+    let angle = rng.gen_range(-max..max).to_radians();
+
+    geometric_transformations::rotate_about_center(
+        &image,
+        angle,
+        Interpolation::Bicubic,
+        LumaA([0, 0]),
+    )
+}
+
+/// Draw interference lines across the image.
+///
+/// Each line goes from the left to the right border of the image, and is
+/// three pixels thick to not be removed by the noise.
+fn interference_lines<R: Rng + ?Sized>(image: &mut GrayAlphaImage, count: u32, rng: &mut R) {
+    let (width, height) = (image.width() as f32, image.height() as f32);
+
+    for _ in 0..count {
+        let start = rng.gen_range(0.0..height);
+        let end = rng.gen_range(0.0..height);
+
+        for offset in [-1.0, 0.0, 1.0] {
+            drawing::draw_line_segment_mut(
+                image,
+                (0.0, start + offset),
+                (width, end + offset),
+                LumaA([0, 255]),
+            );
+        }
+    }
+}
+
 /// Add noise to the image.
-fn image_noise(image: &mut GrayAlphaImage, rng: &mut ThreadRng) {
+///
+/// The noise is blended with an opacity of `alpha`.
+fn image_noise<R: Rng + ?Sized>(image: &mut GrayAlphaImage, alpha: u8, rng: &mut R) {
+    if alpha == 0 {
+        return;
+    }
+
     for pixel in image.pixels_mut() {
         let noise = rng.gen_range(0..255);
 
-        pixel.blend(&LumaA([noise, 160]));
+        pixel.blend(&LumaA([noise, alpha]));
     }
 }
+
+/// Convert a grayscale image to a colored image.
+///
+/// Black pixels are given the `text` color and white pixels the `background`
+/// color, intermediate values are interpolated.
+fn colorize(image: &GrayImage, text: Rgb<u8>, background: Rgb<u8>) -> RgbImage {
+    RgbImage::from_fn(image.width(), image.height(), |x, y| {
+        let luma = image.get_pixel(x, y).0[0] as u32;
+
+        Rgb(std::array::from_fn(|channel| {
+            let text = text.0[channel] as u32;
+            let background = background.0[channel] as u32;
+
+            ((text * (255 - luma) + background * luma) / 255) as u8
+        }))
+    })
+}
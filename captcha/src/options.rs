@@ -0,0 +1,144 @@
+//! Captcha generation options.
+//!
+//! The [`CaptchaOptions`] builder controls how hard a captcha is to read. It is
+//! created from a [`Difficulty`] preset, whose parameters can then be adjusted
+//! individually:
+//!
+//! ```
+//! use raidprotect_captcha::options::{CaptchaOptions, Difficulty};
+//!
+//! let options = CaptchaOptions::new(Difficulty::Hard).noise(200).lines(2);
+//! ```
+
+use image::Rgb;
+
+/// Captcha difficulty preset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    /// Slightly distorted letters with light noise.
+    Easy,
+    /// Distorted letters with noise.
+    #[default]
+    Medium,
+    /// Heavily distorted and rotated letters, with noise and interference
+    /// lines.
+    Hard,
+}
+
+/// Options of the captcha generation.
+///
+/// The default options correspond to the [`Difficulty::Medium`] preset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaptchaOptions {
+    pub(crate) distortion: (f32, f32),
+    pub(crate) noise: u8,
+    pub(crate) rotation: f32,
+    pub(crate) lines: u32,
+    pub(crate) color: Option<(Rgb<u8>, Rgb<u8>)>,
+}
+
+impl CaptchaOptions {
+    /// Initialize options from a difficulty preset.
+    pub fn new(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => Self {
+                distortion: (5.0, 15.0),
+                noise: 100,
+                rotation: 0.0,
+                lines: 0,
+                color: None,
+            },
+            Difficulty::Medium => Self {
+                distortion: (15.0, 35.0),
+                noise: 160,
+                rotation: 0.0,
+                lines: 0,
+                color: None,
+            },
+            Difficulty::Hard => Self {
+                distortion: (15.0, 35.0),
+                noise: 175,
+                rotation: 15.0,
+                lines: 3,
+                color: None,
+            },
+        }
+    }
+
+    /// Set the range of the letters corners offset, in pixels.
+    ///
+    /// Two corners of each letter are moved towards its center by a random
+    /// offset in this range. Negative values are treated as zero.
+    pub fn distortion(mut self, min: f32, max: f32) -> Self {
+        let (min, max) = (min.max(0.0), max.max(0.0));
+        self.distortion = if min <= max { (min, max) } else { (max, min) };
+
+        self
+    }
+
+    /// Set the opacity of the noise added to the image.
+    ///
+    /// A value of `0` disables the noise.
+    pub fn noise(mut self, alpha: u8) -> Self {
+        self.noise = alpha;
+
+        self
+    }
+
+    /// Set the maximum rotation of the letters, in degrees.
+    ///
+    /// Each letter is rotated by a random angle between `-max` and `max`. A
+    /// value of `0` disables the rotation.
+    pub fn rotation(mut self, max: f32) -> Self {
+        self.rotation = max.abs();
+
+        self
+    }
+
+    /// Set the number of interference lines drawn across the image.
+    pub fn lines(mut self, count: u32) -> Self {
+        self.lines = count;
+
+        self
+    }
+
+    /// Set the colors of the text and the background.
+    ///
+    /// The image is generated in grayscale if no colors are set.
+    pub fn color(mut self, text: Rgb<u8>, background: Rgb<u8>) -> Self {
+        self.color = Some((text, background));
+
+        self
+    }
+}
+
+impl Default for CaptchaOptions {
+    fn default() -> Self {
+        Self::new(Difficulty::default())
+    }
+}
+
+impl From<Difficulty> for CaptchaOptions {
+    fn from(difficulty: Difficulty) -> Self {
+        Self::new(difficulty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let options = CaptchaOptions::new(Difficulty::Easy)
+            .distortion(30.0, 10.0)
+            .rotation(-20.0)
+            .lines(2);
+
+        assert_eq!(options.distortion, (10.0, 30.0));
+        assert_eq!(options.rotation, 20.0);
+        assert_eq!(options.lines, 2);
+        assert_eq!(options.noise, CaptchaOptions::new(Difficulty::Easy).noise);
+        assert_eq!(CaptchaOptions::default(), Difficulty::Medium.into());
+    }
+}
@@ -0,0 +1,99 @@
+//! Golden-image regression tests.
+//!
+//! Captchas are generated with a seeded random number generator and compared
+//! with the reference images of the `tests/golden` directory. Run the tests
+//! with the `UPDATE_GOLDEN` environment variable set to regenerate the
+//! reference images after an intended change of the generation.
+
+use std::{env, path::PathBuf};
+
+use image::{GrayImage, Rgb};
+use raidprotect_captcha::{
+    code::random_human_code_with_rng, generate_captcha_png_with_rng, generate_captcha_with,
+    generate_captcha_with_rng, CaptchaOptions, Difficulty,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+fn assert_golden(name: &str, image: &GrayImage) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name);
+
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        image.save(&path).unwrap();
+        return;
+    }
+
+    let expected = image::open(&path)
+        .unwrap_or_else(|error| panic!("failed to open {}: {error}", path.display()))
+        .to_luma8();
+
+    assert_eq!(image.dimensions(), expected.dimensions());
+    assert!(
+        image.as_raw() == expected.as_raw(),
+        "generated image differs from {name}"
+    );
+}
+
+#[test]
+fn test_golden_captcha() {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    let image = generate_captcha_with_rng("ABCDEF", &mut rng);
+
+    assert_golden("abcdef.png", &image);
+}
+
+#[test]
+fn test_golden_human_captcha() {
+    let mut rng = ChaCha8Rng::seed_from_u64(2);
+    let code = random_human_code_with_rng(5, &mut rng);
+    let image = generate_captcha_with_rng(&code, &mut rng);
+
+    assert_eq!(code, "vobub");
+    assert_golden("human.png", &image);
+}
+
+#[test]
+fn test_golden_presets() {
+    for (difficulty, name) in [
+        (Difficulty::Easy, "easy.png"),
+        (Difficulty::Hard, "hard.png"),
+    ] {
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        let options = CaptchaOptions::new(difficulty);
+        let image = generate_captcha_with("ABCDEF", &options, &mut rng);
+
+        assert_golden(name, &image.into_luma8());
+    }
+}
+
+#[test]
+fn test_medium_preset() {
+    let default = generate_captcha_with_rng("ABCDEF", &mut ChaCha8Rng::seed_from_u64(1));
+    let medium = generate_captcha_with(
+        "ABCDEF",
+        &CaptchaOptions::new(Difficulty::Medium),
+        &mut ChaCha8Rng::seed_from_u64(1),
+    );
+
+    assert_eq!(medium.into_luma8(), default);
+}
+
+#[test]
+fn test_color() {
+    let options = CaptchaOptions::default().color(Rgb([0, 0, 0]), Rgb([255, 255, 255]));
+    let image = generate_captcha_with("abc", &options, &mut ChaCha8Rng::seed_from_u64(5));
+    let gray = generate_captcha_with_rng("abc", &mut ChaCha8Rng::seed_from_u64(5));
+
+    assert!(image.as_rgb8().is_some());
+    assert_eq!(image.into_luma8(), gray);
+}
+
+#[test]
+fn test_seeded_png() {
+    let png_1 = generate_captcha_png_with_rng("abc", &mut ChaCha8Rng::seed_from_u64(3)).unwrap();
+    let png_2 = generate_captcha_png_with_rng("abc", &mut ChaCha8Rng::seed_from_u64(3)).unwrap();
+
+    assert_eq!(png_1, png_2);
+}
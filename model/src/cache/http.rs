@@ -5,18 +5,30 @@
 
 use anyhow::anyhow;
 use twilight_http::{
-    request::{channel::message::CreateMessage, guild::CreateGuildChannel},
+    request::{
+        channel::{
+            message::{CreateMessage, DeleteMessage, DeleteMessages},
+            DeleteChannelPermission, UpdateChannelPermission,
+        },
+        guild::{
+            ban::{CreateBan, DeleteBan},
+            member::{AddRoleToMember, RemoveMember, RemoveRoleFromMember, UpdateGuildMember},
+            role::CreateRole,
+            CreateGuildChannel,
+        },
+    },
     Client as HttpClient,
 };
 use twilight_model::{
     guild::Permissions,
+    http::permission_overwrite::PermissionOverwrite,
     id::{
-        marker::{ChannelMarker, GuildMarker},
+        marker::{ChannelMarker, GuildMarker, MessageMarker, RoleMarker, UserMarker},
         Id,
     },
 };
 
-use super::redis::RedisClient;
+use super::{model::CachedRole, permission::RoleOrdering, redis::RedisClient};
 
 /// HTTP client with permission checks.
 #[derive(Debug)]
@@ -95,4 +107,286 @@ impl<'a> CacheHttp<'a> {
 
         Ok(self.http.create_guild_channel(self.guild_id, name)?)
     }
+
+    /// Create a new guild role.
+    ///
+    /// This method ensure that the bot has the [`MANAGE_ROLES`] permission.
+    ///
+    /// [`MANAGE_ROLES`]: Permissions::MANAGE_ROLES
+    pub async fn create_role(&self) -> Result<CreateRole<'a>, anyhow::Error> {
+        let permissions = self
+            .redis
+            .permissions(self.guild_id)
+            .await?
+            .current_member()
+            .await?
+            .guild();
+
+        if !permissions.contains(Permissions::MANAGE_ROLES) {
+            return Err(anyhow!("missing permissions to create role"));
+        }
+
+        Ok(self.http.create_role(self.guild_id))
+    }
+
+    /// Kick a member from the guild.
+    ///
+    /// This method ensure that the bot has the [`KICK_MEMBERS`] permission.
+    ///
+    /// [`KICK_MEMBERS`]: Permissions::KICK_MEMBERS
+    pub async fn remove_guild_member(
+        &self,
+        user_id: Id<UserMarker>,
+    ) -> Result<RemoveMember<'a>, anyhow::Error> {
+        let permissions = self
+            .redis
+            .permissions(self.guild_id)
+            .await?
+            .current_member()
+            .await?
+            .guild();
+
+        if !permissions.contains(Permissions::KICK_MEMBERS) {
+            return Err(anyhow!("missing permissions to kick member"));
+        }
+
+        Ok(self.http.remove_guild_member(self.guild_id, user_id))
+    }
+
+    /// Add a role to a guild member.
+    ///
+    /// This method ensure that the bot has the [`MANAGE_ROLES`] permission and
+    /// that the role is below the bot's highest role.
+    ///
+    /// [`MANAGE_ROLES`]: Permissions::MANAGE_ROLES
+    pub async fn add_guild_member_role(
+        &self,
+        user_id: Id<UserMarker>,
+        role_id: Id<RoleMarker>,
+    ) -> Result<AddRoleToMember<'a>, anyhow::Error> {
+        self.check_manage_role(role_id).await?;
+
+        Ok(self
+            .http
+            .add_guild_member_role(self.guild_id, user_id, role_id))
+    }
+
+    /// Remove a role from a guild member.
+    ///
+    /// This method ensure that the bot has the [`MANAGE_ROLES`] permission and
+    /// that the role is below the bot's highest role.
+    ///
+    /// [`MANAGE_ROLES`]: Permissions::MANAGE_ROLES
+    pub async fn remove_guild_member_role(
+        &self,
+        user_id: Id<UserMarker>,
+        role_id: Id<RoleMarker>,
+    ) -> Result<RemoveRoleFromMember<'a>, anyhow::Error> {
+        self.check_manage_role(role_id).await?;
+
+        Ok(self
+            .http
+            .remove_guild_member_role(self.guild_id, user_id, role_id))
+    }
+
+    /// Ban a user from the guild.
+    ///
+    /// This method ensure that the bot has the [`BAN_MEMBERS`] permission.
+    ///
+    /// [`BAN_MEMBERS`]: Permissions::BAN_MEMBERS
+    pub async fn create_ban(
+        &self,
+        user_id: Id<UserMarker>,
+    ) -> Result<CreateBan<'a>, anyhow::Error> {
+        let permissions = self
+            .redis
+            .permissions(self.guild_id)
+            .await?
+            .current_member()
+            .await?
+            .guild();
+
+        if !permissions.contains(Permissions::BAN_MEMBERS) {
+            return Err(anyhow!("missing permissions to ban member"));
+        }
+
+        Ok(self.http.create_ban(self.guild_id, user_id))
+    }
+
+    /// Update a guild member.
+    ///
+    /// This method is used to timeout members and ensure that the bot has the
+    /// [`MODERATE_MEMBERS`] permission.
+    ///
+    /// [`MODERATE_MEMBERS`]: Permissions::MODERATE_MEMBERS
+    pub async fn update_guild_member(
+        &self,
+        user_id: Id<UserMarker>,
+    ) -> Result<UpdateGuildMember<'a>, anyhow::Error> {
+        let permissions = self
+            .redis
+            .permissions(self.guild_id)
+            .await?
+            .current_member()
+            .await?
+            .guild();
+
+        if !permissions.contains(Permissions::MODERATE_MEMBERS) {
+            return Err(anyhow!("missing permissions to moderate member"));
+        }
+
+        Ok(self.http.update_guild_member(self.guild_id, user_id))
+    }
+
+    /// Unban a user from the guild.
+    ///
+    /// This method ensure that the bot has the [`BAN_MEMBERS`] permission.
+    ///
+    /// [`BAN_MEMBERS`]: Permissions::BAN_MEMBERS
+    pub async fn delete_ban(
+        &self,
+        user_id: Id<UserMarker>,
+    ) -> Result<DeleteBan<'a>, anyhow::Error> {
+        let permissions = self
+            .redis
+            .permissions(self.guild_id)
+            .await?
+            .current_member()
+            .await?
+            .guild();
+
+        if !permissions.contains(Permissions::BAN_MEMBERS) {
+            return Err(anyhow!("missing permissions to unban member"));
+        }
+
+        Ok(self.http.delete_ban(self.guild_id, user_id))
+    }
+
+    /// Delete a message from a channel.
+    ///
+    /// This method ensure that the bot has the [`MANAGE_MESSAGES`] permission
+    /// in the channel.
+    ///
+    /// [`MANAGE_MESSAGES`]: Permissions::MANAGE_MESSAGES
+    pub async fn delete_message(
+        &self,
+        channel: Id<ChannelMarker>,
+        message: Id<MessageMarker>,
+    ) -> Result<DeleteMessage<'a>, anyhow::Error> {
+        self.check_manage_messages(channel).await?;
+
+        Ok(self.http.delete_message(channel, message))
+    }
+
+    /// Bulk delete messages from a channel.
+    ///
+    /// This method ensure that the bot has the [`MANAGE_MESSAGES`] permission
+    /// in the channel. Discord only allows to bulk delete between 2 and 100
+    /// messages that are not older than 14 days.
+    ///
+    /// [`MANAGE_MESSAGES`]: Permissions::MANAGE_MESSAGES
+    pub async fn delete_messages(
+        &self,
+        channel: Id<ChannelMarker>,
+        messages: &'a [Id<MessageMarker>],
+    ) -> Result<DeleteMessages<'a>, anyhow::Error> {
+        self.check_manage_messages(channel).await?;
+
+        Ok(self.http.delete_messages(channel, messages))
+    }
+
+    /// Create or update a permission overwrite of a channel.
+    ///
+    /// This method ensure that the bot has the [`MANAGE_CHANNELS`] and
+    /// [`MANAGE_ROLES`] permissions in the channel.
+    ///
+    /// [`MANAGE_CHANNELS`]: Permissions::MANAGE_CHANNELS
+    /// [`MANAGE_ROLES`]: Permissions::MANAGE_ROLES
+    pub async fn update_channel_permission(
+        &self,
+        channel: Id<ChannelMarker>,
+        overwrite: &PermissionOverwrite,
+    ) -> Result<UpdateChannelPermission<'a>, anyhow::Error> {
+        self.check_manage_permissions(channel).await?;
+
+        Ok(self.http.update_channel_permission(channel, overwrite))
+    }
+
+    /// Delete a permission overwrite of a channel.
+    ///
+    /// This method ensure that the bot has the [`MANAGE_CHANNELS`] and
+    /// [`MANAGE_ROLES`] permissions in the channel.
+    ///
+    /// [`MANAGE_CHANNELS`]: Permissions::MANAGE_CHANNELS
+    /// [`MANAGE_ROLES`]: Permissions::MANAGE_ROLES
+    pub async fn delete_channel_permission(
+        &self,
+        channel: Id<ChannelMarker>,
+    ) -> Result<DeleteChannelPermission<'a>, anyhow::Error> {
+        self.check_manage_permissions(channel).await?;
+
+        Ok(self.http.delete_channel_permission(channel))
+    }
+
+    /// Ensure the bot has the [`MANAGE_ROLES`] permission and that a role is
+    /// below the bot's highest role.
+    ///
+    /// [`MANAGE_ROLES`]: Permissions::MANAGE_ROLES
+    async fn check_manage_role(&self, role_id: Id<RoleMarker>) -> Result<(), anyhow::Error> {
+        let permissions = self
+            .redis
+            .permissions(self.guild_id)
+            .await?
+            .current_member()
+            .await?;
+
+        if !permissions.guild().contains(Permissions::MANAGE_ROLES) {
+            return Err(anyhow!("missing permissions to manage roles"));
+        }
+
+        let role = self
+            .redis
+            .get::<CachedRole>(&role_id)
+            .await?
+            .ok_or_else(|| anyhow!("role {role_id} not found in cache"))?;
+
+        if RoleOrdering::from_cached(&role) >= permissions.highest_role() {
+            return Err(anyhow!("role {role_id} is above the bot's highest role"));
+        }
+
+        Ok(())
+    }
+
+    /// Ensure the bot has the [`MANAGE_MESSAGES`] permission in a channel.
+    ///
+    /// [`MANAGE_MESSAGES`]: Permissions::MANAGE_MESSAGES
+    async fn check_manage_messages(&self, channel: Id<ChannelMarker>) -> Result<(), anyhow::Error> {
+        let permissions = self.redis.permissions(self.guild_id).await?;
+        let (permissions, _) = permissions.current_member().await?.channel(channel).await?;
+
+        if !permissions.contains(Permissions::MANAGE_MESSAGES) {
+            return Err(anyhow!("missing permissions to delete messages"));
+        }
+
+        Ok(())
+    }
+
+    /// Ensure the bot has the [`MANAGE_CHANNELS`] and [`MANAGE_ROLES`]
+    /// permissions in a channel.
+    ///
+    /// [`MANAGE_CHANNELS`]: Permissions::MANAGE_CHANNELS
+    /// [`MANAGE_ROLES`]: Permissions::MANAGE_ROLES
+    async fn check_manage_permissions(
+        &self,
+        channel: Id<ChannelMarker>,
+    ) -> Result<(), anyhow::Error> {
+        let permissions = self.redis.permissions(self.guild_id).await?;
+        let (permissions, _) = permissions.current_member().await?.channel(channel).await?;
+
+        if !permissions.contains(Permissions::MANAGE_CHANNELS | Permissions::MANAGE_ROLES) {
+            return Err(anyhow!("missing permissions to manage channel permissions"));
+        }
+
+        Ok(())
+    }
 }
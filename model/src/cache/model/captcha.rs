@@ -0,0 +1,157 @@
+//! State of the captcha verification.
+//!
+//! Members that joined a guild with the captcha enabled have a
+//! [`PendingCaptcha`] until they complete the verification. The deadline of
+//! each verification is also stored in a sorted set shared by all guilds, so
+//! that members that did not complete the captcha in time can be kicked.
+//!
+//! The [`CaptchaMessage`] of each guild is cached once checked, so that it is
+//! not fetched again for every joining member.
+
+use std::{fmt, str::FromStr};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use twilight_model::id::{
+    marker::{GuildMarker, MessageMarker, UserMarker},
+    Id,
+};
+
+use crate::{cache::RedisModel, serde::IdAsU64};
+
+/// Key of the sorted set of captcha deadlines.
+///
+/// Members of the set are [`PendingCaptchaId`] scored by the unix timestamp
+/// (in seconds) of the verification deadline.
+pub const TIMEOUTS_KEY: &str = "pending:captcha:timeouts";
+
+/// Captcha verification of a guild member.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingCaptcha {
+    /// Guild of the member.
+    #[serde_as(as = "IdAsU64")]
+    pub guild_id: Id<GuildMarker>,
+    /// Id of the member.
+    #[serde_as(as = "IdAsU64")]
+    pub user_id: Id<UserMarker>,
+    /// Code of the last captcha image sent to the member.
+    ///
+    /// This field is [`None`] until the member starts the verification.
+    pub code: Option<String>,
+    /// Number of failed attempts.
+    pub attempts: u8,
+}
+
+impl PendingCaptcha {
+    /// Initialize a new [`PendingCaptcha`].
+    pub fn new(guild_id: Id<GuildMarker>, user_id: Id<UserMarker>) -> Self {
+        Self {
+            guild_id,
+            user_id,
+            code: None,
+            attempts: 0,
+        }
+    }
+
+    /// Get the unique identifier of the verification.
+    pub fn id(&self) -> PendingCaptchaId {
+        PendingCaptchaId {
+            guild_id: self.guild_id,
+            user_id: self.user_id,
+        }
+    }
+}
+
+impl RedisModel for PendingCaptcha {
+    type Id = PendingCaptchaId;
+
+    // Verifications are removed once completed or timed out, the expiration
+    // only ensures that keys are not leaked.
+    const EXPIRES_AFTER: Option<usize> = Some(24 * 60 * 60);
+
+    fn key(&self) -> String {
+        Self::key_from(&self.id())
+    }
+
+    fn key_from(id: &Self::Id) -> String {
+        format!("pending:captcha:{id}")
+    }
+}
+
+/// Verification message of a guild known to exist.
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaptchaMessage {
+    /// Guild of the message.
+    #[serde_as(as = "IdAsU64")]
+    pub guild_id: Id<GuildMarker>,
+    /// Id of the message.
+    #[serde_as(as = "IdAsU64")]
+    pub message_id: Id<MessageMarker>,
+}
+
+impl RedisModel for CaptchaMessage {
+    type Id = Id<GuildMarker>;
+
+    // The message may be deleted while the bot is offline, it is checked
+    // again once the key expired.
+    const EXPIRES_AFTER: Option<usize> = Some(10 * 60);
+
+    fn key(&self) -> String {
+        Self::key_from(&self.guild_id)
+    }
+
+    fn key_from(id: &Self::Id) -> String {
+        format!("c:captcha:message:{id}")
+    }
+}
+
+/// Unique identifier of a [`PendingCaptcha`].
+///
+/// The identifier is formatted as `{guild_id}:{user_id}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingCaptchaId {
+    /// Guild of the member.
+    pub guild_id: Id<GuildMarker>,
+    /// Id of the member.
+    pub user_id: Id<UserMarker>,
+}
+
+impl fmt::Display for PendingCaptchaId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.guild_id, self.user_id)
+    }
+}
+
+impl FromStr for PendingCaptchaId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (guild_id, user_id) = s.split_once(':').context("missing separator")?;
+
+        Ok(Self {
+            guild_id: guild_id.parse()?,
+            user_id: user_id.parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_captcha_id() {
+        let id = PendingCaptchaId {
+            guild_id: Id::new(1),
+            user_id: Id::new(2),
+        };
+
+        assert_eq!(id.to_string(), "1:2");
+        assert_eq!("1:2".parse::<PendingCaptchaId>().unwrap(), id);
+        assert!("1".parse::<PendingCaptchaId>().is_err());
+        assert!("1:0".parse::<PendingCaptchaId>().is_err());
+    }
+}
@@ -12,8 +12,8 @@ use crate::{cache::RedisModel, serde::IdAsU64};
 
 /// Cached model of a [`Channel`].
 ///
-/// Only text channels and threads are cached as the bot
-/// does not interact with voice channels.
+/// Voice channels are only cached with their permission overwrites, to allow
+/// the bot to update them.
 ///
 /// [`Channel`]: twilight_model::channel::Channel
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -24,6 +24,8 @@ pub enum CachedChannel {
     Category(CachedCategoryChannel),
     /// Public or private thread.
     Thread(CachedThread),
+    /// Voice or stage channel.
+    Voice(CachedVoiceChannel),
 }
 
 impl CachedChannel {
@@ -33,6 +35,7 @@ impl CachedChannel {
             CachedChannel::Text(channel) => channel.id,
             CachedChannel::Category(channel) => channel.id,
             CachedChannel::Thread(channel) => channel.id,
+            CachedChannel::Voice(channel) => channel.id,
         }
     }
 
@@ -42,6 +45,7 @@ impl CachedChannel {
             CachedChannel::Text(channel) => channel.guild_id,
             CachedChannel::Category(channel) => channel.guild_id,
             CachedChannel::Thread(channel) => channel.guild_id,
+            CachedChannel::Voice(channel) => channel.guild_id,
         }
     }
 
@@ -56,6 +60,8 @@ impl CachedChannel {
             CachedChannel::Text(_) => ChannelType::GuildText,
             CachedChannel::Category(_) => ChannelType::GuildCategory,
             CachedChannel::Thread(_) => ChannelType::GuildPublicThread,
+            CachedChannel::Voice(channel) if channel.stage => ChannelType::GuildStageVoice,
+            CachedChannel::Voice(_) => ChannelType::GuildVoice,
         }
     }
 
@@ -67,6 +73,7 @@ impl CachedChannel {
             CachedChannel::Text(channel) => &channel.permission_overwrites,
             CachedChannel::Category(channel) => &channel.permission_overwrites,
             CachedChannel::Thread(_) => &[],
+            CachedChannel::Voice(channel) => &channel.permission_overwrites,
         }
     }
 
@@ -80,6 +87,8 @@ impl CachedChannel {
                 | ChannelType::GuildPublicThread
                 | ChannelType::GuildPrivateThread
                 | ChannelType::GuildNewsThread
+                | ChannelType::GuildVoice
+                | ChannelType::GuildStageVoice
         )
     }
 }
@@ -181,3 +190,34 @@ impl From<CachedThread> for CachedChannel {
         CachedChannel::Thread(thread)
     }
 }
+
+/// Cached model of a voice or stage [`Channel`].
+///
+/// [`Channel`]: twilight_model::channel::Channel
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CachedVoiceChannel {
+    /// Id of the channel.
+    #[serde_as(as = "IdAsU64")]
+    pub id: Id<ChannelMarker>,
+    /// Id of the guild to which the channel belongs.
+    #[serde_as(as = "IdAsU64")]
+    pub guild_id: Id<GuildMarker>,
+    /// Name of the channel.
+    pub name: String,
+    /// If the channel is in a category, the category id.
+    #[serde_as(as = "Option<IdAsU64>")]
+    pub parent_id: Option<Id<ChannelMarker>>,
+    /// Sorting position of the channel.
+    pub position: i16,
+    /// Permission overwrites of the channel.
+    pub permission_overwrites: Vec<PermissionOverwrite>,
+    /// Whether the channel is a stage channel.
+    pub stage: bool,
+}
+
+impl From<CachedVoiceChannel> for CachedChannel {
+    fn from(channel: CachedVoiceChannel) -> Self {
+        CachedChannel::Voice(channel)
+    }
+}
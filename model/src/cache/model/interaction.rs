@@ -4,16 +4,20 @@ use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use twilight_model::{
     http::interaction::InteractionResponseData,
-    id::{marker::UserMarker, Id},
+    id::{
+        marker::{GuildMarker, UserMarker},
+        Id,
+    },
     user::User,
 };
 
-use crate::{cache::RedisModel, mongodb::modlog::ModlogType, serde::IdAsU64};
+use crate::{cache::RedisModel, mongodb::modlog::SanctionKind, serde::IdAsU64};
 
 /// State of a component (button, select modal) waiting for user interaction.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PendingComponent {
     PostInChat(PostInChatButton),
+    ModlogsPage(ModlogsPageButton),
 }
 
 impl PendingComponent {
@@ -21,6 +25,7 @@ impl PendingComponent {
     pub fn id(&self) -> &str {
         match self {
             Self::PostInChat(component) => &component.id,
+            Self::ModlogsPage(component) => &component.id,
         }
     }
 }
@@ -53,10 +58,26 @@ pub struct PostInChatButton {
     pub author_id: Id<UserMarker>,
 }
 
+/// State for the modlogs pagination buttons.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModlogsPageButton {
+    /// Component unique identifier.
+    pub id: String,
+    /// Guild of the modlogs.
+    #[serde_as(as = "IdAsU64")]
+    pub guild_id: Id<GuildMarker>,
+    /// User whose modlogs are shown.
+    pub user: User,
+    /// Page shown when the button is clicked.
+    pub page: u64,
+}
+
 // State of a modal waiting for user interaction.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PendingModal {
     Sanction(PendingSanction),
+    CaseEdit(PendingCaseEdit),
 }
 
 impl PendingModal {
@@ -64,6 +85,7 @@ impl PendingModal {
     pub fn id(&self) -> &str {
         match self {
             Self::Sanction(component) => &component.id,
+            Self::CaseEdit(component) => &component.id,
         }
     }
 }
@@ -88,8 +110,21 @@ impl RedisModel for PendingModal {
 pub struct PendingSanction {
     /// Component unique identifier.
     pub id: String,
-    /// Type of the pending modlog.
-    pub kind: ModlogType,
+    /// Type of the pending sanction.
+    pub kind: SanctionKind,
     /// User targeted by the sanction.
     pub user: User,
+    /// Duration of the sanction in seconds, if the sanction is temporary.
+    pub duration: Option<i64>,
+    /// Number of days of messages to delete (only used with bans).
+    pub delete_message_days: Option<u16>,
+}
+
+/// State for a pending case edit modal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingCaseEdit {
+    /// Component unique identifier.
+    pub id: String,
+    /// Case number of the edited modlog.
+    pub case: u64,
 }
@@ -0,0 +1,58 @@
+//! Resolved invites.
+//!
+//! Invite links sent in messages are resolved to the guild they belong to
+//! using the Discord API. The result is cached as a [`CachedInvite`] so that
+//! invites spammed during a raid are only resolved once.
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use twilight_model::id::{marker::GuildMarker, Id};
+
+use crate::{cache::RedisModel, serde::IdAsU64};
+
+/// Guild of a resolved invite code.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedInvite {
+    /// Invite code.
+    pub code: String,
+    /// Guild of the invite.
+    ///
+    /// This field is [`None`] if the invite is invalid or does not belong to
+    /// a guild.
+    #[serde_as(as = "Option<IdAsU64>")]
+    pub guild_id: Option<Id<GuildMarker>>,
+}
+
+impl RedisModel for CachedInvite {
+    type Id = str;
+
+    // Invite expiration duration (1 hour)
+    const EXPIRES_AFTER: Option<usize> = Some(60 * 60);
+
+    fn key(&self) -> String {
+        Self::key_from(&self.code)
+    }
+
+    fn key_from(id: &Self::Id) -> String {
+        format!("c:invite:{id}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_invite() {
+        let invite = CachedInvite {
+            code: "raidprotect".to_string(),
+            guild_id: Some(Id::new(1)),
+        };
+
+        assert_eq!(invite.key(), "c:invite:raidprotect");
+
+        let serialized = invite.serialize_model().unwrap();
+        assert_eq!(CachedInvite::deserialize_model(serialized).unwrap(), invite);
+    }
+}
@@ -5,7 +5,7 @@ use serde_with::serde_as;
 use twilight_model::{
     channel::Attachment,
     id::{
-        marker::{ChannelMarker, MessageMarker, RoleMarker, UserMarker},
+        marker::{ChannelMarker, GuildMarker, MessageMarker, RoleMarker, UserMarker},
         Id,
     },
     util::Timestamp,
@@ -71,6 +71,19 @@ impl RedisModel for CachedMessage {
     }
 }
 
+impl CachedMessage {
+    /// Maximum number of recent messages stored for each guild member.
+    pub const RECENT_MESSAGES_LIMIT: isize = 50;
+
+    /// Get the key of the recent messages list of a guild member.
+    ///
+    /// This list contains the ids of the last messages sent by the member, and
+    /// is used by the anti-spam module.
+    pub fn recent_key(guild_id: Id<GuildMarker>, user_id: Id<UserMarker>) -> String {
+        format!("c:msg:recent:{guild_id}:{user_id}")
+    }
+}
+
 /// Kind of message link.
 ///
 /// This type is used in [`CachedMessage`].
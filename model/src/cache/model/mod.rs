@@ -9,11 +9,16 @@
 //! [`Serialize`]: serde::Serialize
 //! [`Deserialize`]: serde::Deserialize
 
+pub mod captcha;
 pub mod interaction;
+pub mod invite;
 pub mod message;
+pub mod raid;
 
 mod channel;
 mod guild;
 
-pub use channel::{CachedCategoryChannel, CachedChannel, CachedTextChannel, CachedThread};
+pub use channel::{
+    CachedCategoryChannel, CachedChannel, CachedTextChannel, CachedThread, CachedVoiceChannel,
+};
 pub use guild::{CachedGuild, CachedRole, CurrentMember};
@@ -0,0 +1,21 @@
+//! Keys used by the anti-raid module.
+//!
+//! The anti-raid state of each guild is stored in the cache with the
+//! following keys:
+//!
+//! - a sorted set of the members that recently joined the guild, scored by
+//!   their join timestamp in milliseconds.
+//! - a key whose presence indicates that the guild is in raid mode, which
+//!   expires when the raid mode ends.
+
+use twilight_model::id::{marker::GuildMarker, Id};
+
+/// Get the key of the recent joins set of a guild.
+pub fn joins_key(guild_id: Id<GuildMarker>) -> String {
+    format!("c:raid:joins:{guild_id}")
+}
+
+/// Get the key of the raid mode of a guild.
+pub fn raid_mode_key(guild_id: Id<GuildMarker>) -> String {
+    format!("c:raid:mode:{guild_id}")
+}
@@ -19,7 +19,7 @@ use twilight_model::{
 use crate::cache::{
     model::{
         CachedCategoryChannel, CachedChannel, CachedGuild, CachedRole, CachedTextChannel,
-        CachedThread, CurrentMember,
+        CachedThread, CachedVoiceChannel, CurrentMember,
     },
     RedisModel,
 };
@@ -109,6 +109,9 @@ pub fn cache_guild_channel(pipe: &mut Pipeline, channel: &Channel) -> Result<(),
         ChannelType::GuildNewsThread
         | ChannelType::GuildPublicThread
         | ChannelType::GuildPrivateThread => cache_thread(pipe, channel),
+        ChannelType::GuildVoice | ChannelType::GuildStageVoice => {
+            cache_voice_channel(pipe, channel)
+        }
         _ => Ok(()),
     }
 }
@@ -151,6 +154,26 @@ pub fn cache_category_channel(pipe: &mut Pipeline, channel: &Channel) -> Result<
     Ok(())
 }
 
+pub fn cache_voice_channel(pipe: &mut Pipeline, channel: &Channel) -> Result<(), anyhow::Error> {
+    let cached = CachedChannel::from(CachedVoiceChannel {
+        id: channel.id,
+        guild_id: channel.guild_id.ok_or(CacheError::GuildId)?,
+        name: channel.name.as_ref().ok_or(CacheError::Name)?.clone(),
+        parent_id: channel.parent_id,
+        position: channel.position.ok_or(CacheError::Position)?,
+        permission_overwrites: channel
+            .permission_overwrites
+            .as_ref()
+            .ok_or(CacheError::PermissionOverwrites)?
+            .clone(),
+        stage: channel.kind == ChannelType::GuildStageVoice,
+    });
+
+    pipe.set(cached.key(), cached.serialize_model()?);
+
+    Ok(())
+}
+
 pub fn cache_thread(pipe: &mut Pipeline, thread: &Channel) -> Result<(), anyhow::Error> {
     let cached = CachedChannel::from(CachedThread {
         id: thread.id,
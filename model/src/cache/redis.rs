@@ -15,11 +15,18 @@ use redis::AsyncCommands;
 use serde::{de::DeserializeOwned, Serialize};
 use tracing::{instrument, trace};
 use twilight_http::Client as HttpClient;
-use twilight_model::id::{marker::GuildMarker, Id};
+use twilight_model::id::{
+    marker::{GuildMarker, UserMarker},
+    Id,
+};
 
 use super::{
     http::CacheHttp,
-    model::{CachedChannel, CachedGuild, CachedRole},
+    model::{
+        captcha::{self, PendingCaptcha, PendingCaptchaId},
+        message::CachedMessage,
+        raid, CachedChannel, CachedGuild, CachedRole,
+    },
     permission::GuildPermissions,
 };
 
@@ -84,6 +91,18 @@ impl RedisClient {
         Ok(())
     }
 
+    /// Delete a value from Redis.
+    #[instrument(skip(self))]
+    pub async fn delete<T: RedisModel>(&self, id: &T::Id) -> Result<(), anyhow::Error> {
+        let mut conn = self.conn().await?;
+        let key = T::key_from(id);
+
+        trace!("deleting key {}", key);
+        conn.del::<_, ()>(key).await?;
+
+        Ok(())
+    }
+
     /// Run a `PING` command to check if Redis is connected.
     pub async fn ping(&self) -> Result<(), anyhow::Error> {
         let mut conn = self.conn().await?;
@@ -153,6 +172,200 @@ impl RedisClient {
         }
     }
 
+    /// Store a message in the recent messages of its author.
+    ///
+    /// The message is stored with [`set`] and its id is appended to the list
+    /// of recent messages of the guild member. The list expires at the same
+    /// time as the cached message.
+    ///
+    /// [`set`]: Self::set
+    #[instrument(skip(self))]
+    pub async fn add_recent_message(
+        &self,
+        guild_id: Id<GuildMarker>,
+        message: &CachedMessage,
+    ) -> Result<(), anyhow::Error> {
+        self.set(message).await?;
+
+        let mut conn = self.conn().await?;
+        let key = CachedMessage::recent_key(guild_id, message.author_id);
+        let expires_after = CachedMessage::EXPIRES_AFTER.unwrap_or_default();
+
+        redis::pipe()
+            .rpush(&key, message.id.get())
+            .ltrim(&key, -CachedMessage::RECENT_MESSAGES_LIMIT, -1)
+            .expire(&key, expires_after)
+            .query_async::<_, ()>(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get the recent messages of a guild member.
+    ///
+    /// Messages are returned from the oldest to the most recent. Expired
+    /// messages are not included.
+    #[instrument(skip(self))]
+    pub async fn recent_messages(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<Vec<CachedMessage>, anyhow::Error> {
+        let mut conn = self.conn().await?;
+        let key = CachedMessage::recent_key(guild_id, user_id);
+
+        let ids: Vec<u64> = conn.lrange(&key, 0, -1).await?;
+        let mut pipe = redis::pipe();
+
+        for id in ids.into_iter().filter_map(Id::new_checked) {
+            pipe.get(CachedMessage::key_from(&id));
+        }
+
+        let values: Vec<Option<Vec<u8>>> = pipe.query_async(&mut *conn).await?;
+
+        values
+            .into_iter()
+            .flatten()
+            .map(RedisModel::deserialize_model)
+            .collect()
+    }
+
+    /// Clear the recent messages of a guild member.
+    #[instrument(skip(self))]
+    pub async fn clear_recent_messages(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<(), anyhow::Error> {
+        let mut conn = self.conn().await?;
+        conn.del::<_, ()>(CachedMessage::recent_key(guild_id, user_id))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Register a member join for the anti-raid module.
+    ///
+    /// The `timestamp` is the join date of the member in milliseconds. Joins
+    /// older than `window` seconds are removed. Returns the number of members
+    /// that joined during the window.
+    #[instrument(skip(self))]
+    pub async fn add_member_join(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        timestamp: i64,
+        window: u32,
+    ) -> Result<u64, anyhow::Error> {
+        let mut conn = self.conn().await?;
+        let key = raid::joins_key(guild_id);
+        let window_start = timestamp - i64::from(window) * 1000;
+
+        let (count,): (u64,) = redis::pipe()
+            .zadd(&key, user_id.get(), timestamp)
+            .ignore()
+            .zrembyscore(&key, "-inf", format!("({window_start}"))
+            .ignore()
+            .zcard(&key)
+            .expire(&key, window as usize)
+            .ignore()
+            .query_async(&mut *conn)
+            .await?;
+
+        Ok(count)
+    }
+
+    /// Enable the raid mode of a guild for `duration` seconds.
+    ///
+    /// If the raid mode is already enabled, its duration is extended. Returns
+    /// whether the raid mode has been enabled by this call.
+    #[instrument(skip(self))]
+    pub async fn enable_raid_mode(
+        &self,
+        guild_id: Id<GuildMarker>,
+        duration: u32,
+    ) -> Result<bool, anyhow::Error> {
+        let mut conn = self.conn().await?;
+        let key = raid::raid_mode_key(guild_id);
+
+        let enabled: Option<String> = redis::cmd("SET")
+            .arg(&key)
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(duration)
+            .query_async(&mut *conn)
+            .await?;
+
+        if enabled.is_none() {
+            conn.expire::<_, ()>(&key, duration as usize).await?;
+        }
+
+        Ok(enabled.is_some())
+    }
+
+    /// Check whether a guild is in raid mode.
+    #[instrument(skip(self))]
+    pub async fn is_raid_mode(&self, guild_id: Id<GuildMarker>) -> Result<bool, anyhow::Error> {
+        let mut conn = self.conn().await?;
+
+        Ok(conn.exists(raid::raid_mode_key(guild_id)).await?)
+    }
+
+    /// Start the captcha verification of a guild member.
+    ///
+    /// The verification is stored with [`set`] and must be completed before
+    /// the `expires_at` unix timestamp (in seconds).
+    ///
+    /// [`set`]: Self::set
+    #[instrument(skip(self))]
+    pub async fn add_pending_captcha(
+        &self,
+        captcha: &PendingCaptcha,
+        expires_at: i64,
+    ) -> Result<(), anyhow::Error> {
+        self.set(captcha).await?;
+
+        let mut conn = self.conn().await?;
+        conn.zadd::<_, _, _, ()>(captcha::TIMEOUTS_KEY, captcha.id().to_string(), expires_at)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Remove the captcha verification of a guild member.
+    #[instrument(skip(self))]
+    pub async fn remove_pending_captcha(&self, id: PendingCaptchaId) -> Result<(), anyhow::Error> {
+        let mut conn = self.conn().await?;
+
+        redis::pipe()
+            .del(PendingCaptcha::key_from(&id))
+            .zrem(captcha::TIMEOUTS_KEY, id.to_string())
+            .query_async::<_, ()>(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get the captcha verifications that expired before `now`.
+    ///
+    /// The expired verifications are removed from the deadlines set, but not
+    /// from the pending verifications.
+    #[instrument(skip(self))]
+    pub async fn expired_captchas(&self, now: i64) -> Result<Vec<PendingCaptchaId>, anyhow::Error> {
+        let mut conn = self.conn().await?;
+
+        let (expired,): (Vec<String>,) = redis::pipe()
+            .atomic()
+            .zrangebyscore(captcha::TIMEOUTS_KEY, "-inf", now)
+            .zrembyscore(captcha::TIMEOUTS_KEY, "-inf", now)
+            .ignore()
+            .query_async(&mut *conn)
+            .await?;
+
+        expired.iter().map(|id| id.parse()).collect()
+    }
+
     /// Get a [`GuildPermissions`] for a given guild.
     ///
     /// If the guild is not found in the cache, [`None`] is returned.
@@ -6,7 +6,7 @@
 //! `RAIDPROTECT_`. If variables are defined in a `.env` file, they will take
 //! precedence over other variables.
 
-use std::net::SocketAddr;
+use std::{net::SocketAddr, path::PathBuf};
 
 use serde::{de, Deserialize};
 use tracing::Level;
@@ -26,6 +26,12 @@ where
 pub struct BotConfig {
     /// Discord bot token.
     pub token: String,
+    /// Path of the file containing known phishing domains.
+    ///
+    /// The file contains one domain per line and is reloaded periodically. If
+    /// not set, only domains imitating popular websites are detected.
+    #[serde(default)]
+    pub phishing_domains: Option<PathBuf>,
     /// Databases configuration.
     #[serde(flatten, default)]
     pub database: DatabaseConfig,
@@ -3,17 +3,23 @@ use std::time::Duration;
 use anyhow::{anyhow, Context};
 use mongodb::{
     bson::{doc, oid::ObjectId, to_bson, to_document, Bson},
-    options, Client, Cursor, Database,
+    options, Client, Cursor, Database, IndexModel,
 };
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
+use serde_with::{serde_as, skip_serializing_none};
+use time::OffsetDateTime;
 use twilight_model::id::{
-    marker::{GuildMarker, UserMarker},
+    marker::{ChannelMarker, GuildMarker, MessageMarker, UserMarker},
     Id,
 };
 
-use super::{guild::Guild, modlog::Modlog};
-use crate::serde::IdAsI64;
+use super::{
+    counter::GuildCounters,
+    guild::{CaptchaUpdate, Guild},
+    lockdown::Lockdown,
+    modlog::{Modlog, ModlogEdit, ModlogMessage, ModlogRevocation, ModlogType},
+};
+use crate::serde::{DateTimeAsBson, IdAsI64};
 
 /// Error type returned by [`MongoDbClient`].
 ///
@@ -109,24 +115,130 @@ impl MongoDbClient {
 
         self.db()
             .collection::<Guild>(Guild::COLLECTION)
-            .replace_one(doc! { "_id": to_document(&query)? }, guild, options)
+            .replace_one(to_document(&query)?, guild, options)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Update the captcha configuration of a guild.
+    ///
+    /// Only the fields set in the [`CaptchaUpdate`] are modified, the rest of
+    /// the guild configuration is left unchanged.
+    pub async fn update_captcha(
+        &self,
+        guild_id: Id<GuildMarker>,
+        update: &CaptchaUpdate,
+    ) -> Result<(), MongoDbError> {
+        if update.is_empty() {
+            return Ok(());
+        }
+
+        let query = GuildQuery { id: guild_id };
+
+        self.db()
+            .collection::<Guild>(Guild::COLLECTION)
+            .update_one(to_document(&query)?, update.to_document()?, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Replace the captcha verification message of a guild.
+    ///
+    /// The message is only replaced if the current message is still `current`,
+    /// so that concurrent updates do not overwrite each other. Returns whether
+    /// the message has been replaced.
+    pub async fn replace_captcha_message(
+        &self,
+        guild_id: Id<GuildMarker>,
+        current: Option<Id<MessageMarker>>,
+        message: Option<Id<MessageMarker>>,
+    ) -> Result<bool, MongoDbError> {
+        let mut query = to_document(&GuildQuery { id: guild_id })?;
+        query.insert(
+            "captcha_message",
+            current.map_or(Bson::Null, |id| Bson::Int64(id.get() as i64)),
+        );
+
+        let update = CaptchaUpdate {
+            message: Some(message),
+            ..Default::default()
+        };
+
+        let result = self
+            .db()
+            .collection::<Guild>(Guild::COLLECTION)
+            .update_one(query, update.to_document()?, None)
+            .await?;
+
+        Ok(result.matched_count > 0)
+    }
+
+    /// Create the indexes of the collections.
+    ///
+    /// Existing indexes are not modified.
+    pub async fn create_indexes(&self) -> Result<(), MongoDbError> {
+        let case_index = IndexModel::builder()
+            .keys(doc! { "guild_id": 1, "case": 1 })
+            .options(
+                options::IndexOptions::builder()
+                    .unique(true)
+                    .partial_filter_expression(doc! { "case": { "$exists": true } })
+                    .build(),
+            )
+            .build();
+
+        self.db()
+            .collection::<Modlog>(Modlog::COLLECTION)
+            .create_index(case_index, None)
             .await?;
 
         Ok(())
     }
 
     /// Insert a new [`Modlog`] in the database.
-    pub async fn create_modlog(&self, modlog: &Modlog) -> Result<ObjectId, anyhow::Error> {
+    ///
+    /// A case number is atomically assigned to the modlog before it is
+    /// inserted. The `id` and `case` fields of the modlog are updated with the
+    /// inserted values.
+    pub async fn create_modlog(&self, modlog: &mut Modlog) -> Result<(), anyhow::Error> {
+        modlog.case = Some(self.next_modlog_case(modlog.guild_id).await?);
+
         let result = self
             .db()
             .collection::<Modlog>(Modlog::COLLECTION)
-            .insert_one(modlog, None)
+            .insert_one(&*modlog, None)
             .await?;
 
         match result.inserted_id {
-            Bson::ObjectId(id) => Ok(id),
-            other => Err(anyhow!("expected object id, got {:?}", other)),
+            Bson::ObjectId(id) => modlog.id = Some(id),
+            other => return Err(anyhow!("expected object id, got {:?}", other)),
         }
+
+        Ok(())
+    }
+
+    /// Increment the modlog case counter of a guild and return its value.
+    async fn next_modlog_case(&self, guild_id: Id<GuildMarker>) -> Result<u64, anyhow::Error> {
+        let query = GuildQuery { id: guild_id };
+        let options = options::FindOneAndUpdateOptions::builder()
+            .upsert(true)
+            .return_document(options::ReturnDocument::After)
+            .build();
+
+        let counters = self
+            .db()
+            .collection::<GuildCounters>(GuildCounters::COLLECTION)
+            .find_one_and_update(
+                to_document(&query)?,
+                doc! { "$inc": { "modlog_case": 1_i64 } },
+                options,
+            )
+            .await?
+            .context("no counters sent by the database")?;
+
+        Ok(counters.modlog_case)
     }
 
     /// Get a [`Modlog`] from the database with its id.
@@ -139,20 +251,251 @@ impl MongoDbClient {
             .await
     }
 
+    /// Get a [`Modlog`] from the database with its guild id and case number.
+    pub async fn get_modlog_by_case(
+        &self,
+        guild_id: Id<GuildMarker>,
+        case: u64,
+    ) -> Result<Option<Modlog>, MongoDbError> {
+        let query = CaseQuery { guild_id, case };
+
+        self.db()
+            .collection::<Modlog>(Modlog::COLLECTION)
+            .find_one(to_document(&query)?, None)
+            .await
+    }
+
     /// Find multiple [`Modlog`]s from the database that match a given guild id
     /// and optional user id.
+    ///
+    /// The modlogs are sorted from the most recent to the oldest. The `skip`
+    /// and `limit` parameters can be used to paginate results.
     pub async fn find_modlogs(
         &self,
         guild_id: Id<GuildMarker>,
         user_id: Option<Id<UserMarker>>,
+        skip: u64,
+        limit: i64,
     ) -> Result<Cursor<Modlog>, MongoDbError> {
         let query = ModlogQuery { guild_id, user_id };
+        let options = options::FindOptions::builder()
+            .sort(doc! { "date": -1 })
+            .skip(skip)
+            .limit(limit)
+            .build();
+
+        self.db()
+            .collection::<Modlog>(Modlog::COLLECTION)
+            .find(to_document(&query)?, options)
+            .await
+    }
+
+    /// Count the [`Modlog`]s that match a given guild id and optional user id.
+    pub async fn count_modlogs(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Option<Id<UserMarker>>,
+    ) -> Result<u64, MongoDbError> {
+        let query = ModlogQuery { guild_id, user_id };
 
         self.db()
             .collection::<Modlog>(Modlog::COLLECTION)
+            .count_documents(to_document(&query)?, None)
+            .await
+    }
+
+    /// Find the warnings of a guild member, sorted by date.
+    pub async fn find_warns(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<Cursor<Modlog>, MongoDbError> {
+        let query = MemberModlogQuery {
+            guild_id,
+            user_id,
+            kind: ModlogType::Warn,
+        };
+        let options = options::FindOptions::builder()
+            .sort(doc! { "date": 1 })
+            .build();
+
+        self.db()
+            .collection::<Modlog>(Modlog::COLLECTION)
+            .find(to_document(&query)?, options)
+            .await
+    }
+
+    /// Find the latest active sanction of a given type for a guild member.
+    ///
+    /// Sanctions that have expired or have been revoked are ignored.
+    pub async fn find_active_sanction(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        kind: ModlogType,
+    ) -> Result<Option<Modlog>, MongoDbError> {
+        let mut query = to_document(&MemberModlogQuery {
+            guild_id,
+            user_id,
+            kind,
+        })?;
+        query.insert("expired", doc! { "$ne": true });
+        query.insert("revoked", Bson::Null);
+
+        let options = options::FindOneOptions::builder()
+            .sort(doc! { "date": -1 })
+            .build();
+
+        self.db()
+            .collection::<Modlog>(Modlog::COLLECTION)
+            .find_one(query, options)
+            .await
+    }
+
+    /// Mark a [`Modlog`] as revoked.
+    pub async fn revoke_modlog(
+        &self,
+        id: ObjectId,
+        revocation: &ModlogRevocation,
+    ) -> Result<(), MongoDbError> {
+        self.db()
+            .collection::<Modlog>(Modlog::COLLECTION)
+            .update_one(
+                doc! { "_id": id },
+                doc! { "$set": { "revoked": to_bson(revocation)? } },
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Set the logs channel message of a [`Modlog`].
+    pub async fn set_modlog_logs_message(
+        &self,
+        id: ObjectId,
+        message: ModlogMessage,
+    ) -> Result<(), MongoDbError> {
+        self.db()
+            .collection::<Modlog>(Modlog::COLLECTION)
+            .update_one(
+                doc! { "_id": id },
+                doc! { "$set": { "logs_message": to_bson(&message)? } },
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Update the reason and notes of a [`Modlog`].
+    ///
+    /// The [`ModlogEdit`] containing the previous values is appended to the
+    /// edit history of the modlog.
+    pub async fn edit_modlog(
+        &self,
+        id: ObjectId,
+        reason: Option<&str>,
+        notes: Option<&str>,
+        edit: &ModlogEdit,
+    ) -> Result<(), MongoDbError> {
+        self.db()
+            .collection::<Modlog>(Modlog::COLLECTION)
+            .update_one(
+                doc! { "_id": id },
+                doc! {
+                    "$set": { "reason": reason, "notes": notes },
+                    "$push": { "edits": to_bson(edit)? },
+                },
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Find temporary [`Modlog`]s that have expired at a given date and have
+    /// not yet been lifted.
+    ///
+    /// Sanctions that have been revoked are ignored since they have already
+    /// been lifted.
+    pub async fn find_expired_modlogs(
+        &self,
+        date: OffsetDateTime,
+    ) -> Result<Cursor<Modlog>, MongoDbError> {
+        let query = doc! {
+            "expires_at": to_bson(&ExpiresQuery { lte: date })?,
+            "expired": { "$ne": true },
+            "revoked": null,
+        };
+
+        self.db()
+            .collection::<Modlog>(Modlog::COLLECTION)
+            .find(query, None)
+            .await
+    }
+
+    /// Mark a temporary [`Modlog`] as expired.
+    pub async fn expire_modlog(&self, id: ObjectId) -> Result<(), MongoDbError> {
+        self.db()
+            .collection::<Modlog>(Modlog::COLLECTION)
+            .update_one(
+                doc! { "_id": id },
+                doc! { "$set": { "expired": true } },
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get the [`Lockdown`] of a channel, if it is locked.
+    pub async fn get_lockdown(
+        &self,
+        channel_id: Id<ChannelMarker>,
+    ) -> Result<Option<Lockdown>, MongoDbError> {
+        let query = LockdownQuery { channel_id };
+
+        self.db()
+            .collection::<Lockdown>(Lockdown::COLLECTION)
+            .find_one(to_document(&query)?, None)
+            .await
+    }
+
+    /// Find the [`Lockdown`]s of a guild.
+    pub async fn find_lockdowns(
+        &self,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<Cursor<Lockdown>, MongoDbError> {
+        let query = GuildLockdownQuery { guild_id };
+
+        self.db()
+            .collection::<Lockdown>(Lockdown::COLLECTION)
             .find(to_document(&query)?, None)
             .await
     }
+
+    /// Insert a new [`Lockdown`] in the database.
+    pub async fn create_lockdown(&self, lockdown: &Lockdown) -> Result<(), MongoDbError> {
+        self.db()
+            .collection::<Lockdown>(Lockdown::COLLECTION)
+            .insert_one(lockdown, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Delete the [`Lockdown`] of a channel.
+    pub async fn delete_lockdown(&self, channel_id: Id<ChannelMarker>) -> Result<(), MongoDbError> {
+        let query = LockdownQuery { channel_id };
+
+        self.db()
+            .collection::<Lockdown>(Lockdown::COLLECTION)
+            .delete_one(to_document(&query)?, None)
+            .await?;
+
+        Ok(())
+    }
 }
 
 /// Query a guild with its guild_id
@@ -166,10 +509,59 @@ struct GuildQuery {
 
 /// Query modlogs with guild_id and optional user_id
 #[serde_as]
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 struct ModlogQuery {
     #[serde_as(as = "IdAsI64")]
     pub guild_id: Id<GuildMarker>,
     #[serde_as(as = "Option<IdAsI64>")]
+    #[serde(rename = "user.id")]
     pub user_id: Option<Id<UserMarker>>,
 }
+
+/// Query a modlog with its guild_id and case number
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+struct CaseQuery {
+    #[serde_as(as = "IdAsI64")]
+    pub guild_id: Id<GuildMarker>,
+    pub case: u64,
+}
+
+/// Query modlogs of a given type for a guild member
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+struct MemberModlogQuery {
+    #[serde_as(as = "IdAsI64")]
+    pub guild_id: Id<GuildMarker>,
+    #[serde_as(as = "IdAsI64")]
+    #[serde(rename = "user.id")]
+    pub user_id: Id<UserMarker>,
+    pub kind: ModlogType,
+}
+
+/// Query dates lower than or equal to a given date
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+struct ExpiresQuery {
+    #[serde_as(as = "DateTimeAsBson")]
+    #[serde(rename = "$lte")]
+    pub lte: OffsetDateTime,
+}
+
+/// Query a lockdown with its channel_id
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+struct LockdownQuery {
+    #[serde_as(as = "IdAsI64")]
+    #[serde(rename = "_id")]
+    pub channel_id: Id<ChannelMarker>,
+}
+
+/// Query lockdowns with their guild_id
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+struct GuildLockdownQuery {
+    #[serde_as(as = "IdAsI64")]
+    pub guild_id: Id<GuildMarker>,
+}
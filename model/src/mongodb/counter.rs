@@ -0,0 +1,28 @@
+//! Models for the `counters` collection.
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use twilight_model::id::{marker::GuildMarker, Id};
+
+use crate::serde::IdAsI64;
+
+/// Per-guild counters.
+///
+/// This struct is used to generate monotonically increasing numbers for each
+/// guild, such as modlogs case numbers. Counters are incremented atomically.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct GuildCounters {
+    /// Discord guild id.
+    #[serde_as(as = "IdAsI64")]
+    #[serde(rename = "_id")]
+    pub id: Id<GuildMarker>,
+    /// Last case number assigned to a modlog.
+    #[serde(default)]
+    pub modlog_case: u64,
+}
+
+impl GuildCounters {
+    /// Name of the MongoDB collection.
+    pub const COLLECTION: &'static str = "counters";
+}
@@ -1,5 +1,6 @@
 //! Models for the `guilds` collection.
 
+use mongodb::bson::{self, doc, Bson, Document};
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, skip_serializing_none, with_prefix};
 use twilight_model::id::{
@@ -36,6 +37,39 @@ pub struct Guild {
     /// The captcha module configuration.
     #[serde(default, flatten, with = "prefix_captcha")]
     pub captcha: Captcha,
+    /// The anti-raid module configuration.
+    #[serde(default, flatten, with = "prefix_raid")]
+    pub raid: AntiRaid,
+    /// The account age gate configuration.
+    #[serde(default, flatten, with = "prefix_account_age")]
+    pub account_age: AccountAge,
+    /// Exemptions of the auto-moderation modules.
+    #[serde(default, flatten, with = "prefix_exemptions")]
+    pub exemptions: Exemptions,
+    /// The anti-spam module configuration.
+    #[serde(default, flatten, with = "prefix_antispam")]
+    pub antispam: AntiSpam,
+    /// The invite filter configuration.
+    #[serde(default, flatten, with = "prefix_invites")]
+    pub invites: InviteFilter,
+    /// The mention spam filter configuration.
+    #[serde(default, flatten, with = "prefix_mentions")]
+    pub mentions: MentionFilter,
+    /// The blocklist filter configuration.
+    #[serde(default, flatten, with = "prefix_blocklist")]
+    pub blocklist: Blocklist,
+    /// The phishing filter configuration.
+    #[serde(default, flatten, with = "prefix_phishing")]
+    pub phishing: PhishingFilter,
+    /// The excessive caps filter configuration.
+    #[serde(default, flatten, with = "prefix_caps")]
+    pub caps: CapsFilter,
+    /// The excessive emojis filter configuration.
+    #[serde(default, flatten, with = "prefix_emojis")]
+    pub emojis: EmojiFilter,
+    /// The zalgo text filter configuration.
+    #[serde(default, flatten, with = "prefix_zalgo")]
+    pub zalgo: ZalgoFilter,
 }
 
 impl Guild {
@@ -50,6 +84,17 @@ impl Guild {
             lang: Self::default_lang(),
             moderation: Moderation::default(),
             captcha: Captcha::default(),
+            raid: AntiRaid::default(),
+            account_age: AccountAge::default(),
+            exemptions: Exemptions::default(),
+            antispam: AntiSpam::default(),
+            invites: InviteFilter::default(),
+            mentions: MentionFilter::default(),
+            blocklist: Blocklist::default(),
+            phishing: PhishingFilter::default(),
+            caps: CapsFilter::default(),
+            emojis: EmojiFilter::default(),
+            zalgo: ZalgoFilter::default(),
         }
     }
 
@@ -75,6 +120,15 @@ pub struct Moderation {
     ///
     /// This is enabled by default.
     pub anonymize: bool,
+    /// Link sent to sanctioned users to appeal their sanction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub appeal_url: Option<String>,
+    /// Escalation rules applied when a member receives warnings.
+    ///
+    /// When the number of warnings of a member reaches the threshold of a
+    /// rule, the corresponding sanction is automatically applied.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub escalation: Vec<EscalationRule>,
 }
 
 impl Default for Moderation {
@@ -83,10 +137,44 @@ impl Default for Moderation {
             roles: Vec::new(),
             enforce_reason: false,
             anonymize: true,
+            appeal_url: None,
+            escalation: Vec::new(),
         }
     }
 }
 
+/// Warnings escalation rule.
+///
+/// For example, a rule can be configured to mute a member for one hour when
+/// they receive three warnings in seven days.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct EscalationRule {
+    /// Number of warnings that triggers the rule.
+    pub warns: u32,
+    /// Period in seconds during which the warnings are counted.
+    ///
+    /// If [`None`], all the warnings of the member are counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period: Option<i64>,
+    /// Sanction applied when the rule is triggered.
+    pub sanction: EscalationSanction,
+}
+
+/// Sanction applied by an [`EscalationRule`].
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum EscalationSanction {
+    /// Mute the member for a given duration in seconds.
+    Mute { duration: i64 },
+    /// Kick the member.
+    Kick,
+    /// Ban the member, with an optional duration in seconds.
+    Ban {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        duration: Option<i64>,
+    },
+}
+
 with_prefix!(prefix_moderation "moderation_");
 
 /// Configuration for the captcha module.
@@ -108,6 +196,8 @@ pub struct Captcha {
     #[serde_as(as = "Option<IdAsI64>")]
     pub message: Option<Id<MessageMarker>>,
     /// Role given to users that haven't completed the captcha.
+    ///
+    /// The captcha is disabled if the role is deleted.
     #[serde_as(as = "Option<IdAsI64>")]
     pub role: Option<Id<RoleMarker>>,
     /// Roles given to users after completing the captcha.
@@ -119,6 +209,544 @@ pub struct Captcha {
     /// If set, the captcha will send detailed logs to this channel.
     #[serde_as(as = "Option<IdAsI64>")]
     pub logs: Option<Id<ChannelMarker>>,
+    /// Difficulty of the captcha images.
+    pub difficulty: CaptchaDifficulty,
+}
+
+/// Difficulty of the captcha images.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptchaDifficulty {
+    /// Slightly distorted letters with light noise.
+    Easy,
+    /// Distorted letters with noise.
+    #[default]
+    Medium,
+    /// Rotated letters with noise and interference lines.
+    Hard,
+}
+
+/// Partial update of the [`Captcha`] configuration.
+///
+/// Only the fields that are set are updated in the database, so that
+/// concurrent changes to the rest of the guild configuration are not
+/// overwritten. Fields of type `Option<Option<_>>` are removed when set to
+/// `Some(None)`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CaptchaUpdate {
+    /// New value of [`Captcha::enabled`].
+    pub enabled: Option<bool>,
+    /// New value of [`Captcha::channel`].
+    pub channel: Option<Option<Id<ChannelMarker>>>,
+    /// New value of [`Captcha::message`].
+    pub message: Option<Option<Id<MessageMarker>>>,
+    /// New value of [`Captcha::role`].
+    pub role: Option<Option<Id<RoleMarker>>>,
+    /// Role removed from [`Captcha::verified_roles`].
+    pub remove_verified_role: Option<Id<RoleMarker>>,
+    /// New value of [`Captcha::logs`].
+    pub logs: Option<Option<Id<ChannelMarker>>>,
+    /// New value of [`Captcha::difficulty`].
+    pub difficulty: Option<CaptchaDifficulty>,
+}
+
+impl CaptchaUpdate {
+    /// Whether the update does not change any field.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Convert the update into a MongoDB update document.
+    pub fn to_document(&self) -> Result<Document, bson::ser::Error> {
+        let mut set = Document::new();
+        let mut unset = Document::new();
+
+        let mut optional = |key: &str, value: Option<Option<u64>>| match value {
+            Some(Some(id)) => {
+                set.insert(key, id_to_bson(id));
+            }
+            Some(None) => {
+                unset.insert(key, "");
+            }
+            None => {}
+        };
+
+        optional("captcha_channel", self.channel.map(|id| id.map(Id::get)));
+        optional("captcha_message", self.message.map(|id| id.map(Id::get)));
+        optional("captcha_role", self.role.map(|id| id.map(Id::get)));
+        optional("captcha_logs", self.logs.map(|id| id.map(Id::get)));
+
+        if let Some(enabled) = self.enabled {
+            set.insert("captcha_enabled", enabled);
+        }
+
+        if let Some(difficulty) = self.difficulty {
+            set.insert("captcha_difficulty", bson::to_bson(&difficulty)?);
+        }
+
+        let mut update = Document::new();
+
+        if !set.is_empty() {
+            update.insert("$set", set);
+        }
+
+        if !unset.is_empty() {
+            update.insert("$unset", unset);
+        }
+
+        if let Some(role) = self.remove_verified_role {
+            update.insert(
+                "$pull",
+                doc! { "captcha_verified_roles": id_to_bson(role.get()) },
+            );
+        }
+
+        Ok(update)
+    }
+}
+
+/// Convert an id to a [`Bson`] value, as serialized by [`IdAsI64`].
+fn id_to_bson(id: u64) -> Bson {
+    Bson::Int64(id as i64)
 }
 
 with_prefix!(prefix_captcha "captcha_");
+
+/// Configuration for the anti-raid module.
+///
+/// The anti-raid counts the members that join the guild during a sliding
+/// window of `window` seconds. When more than `max_joins` members join during
+/// the window, the guild enters raid mode. The raid mode ends when no raid
+/// has been detected for `cooldown` seconds.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct AntiRaid {
+    /// Whether the anti-raid is enabled.
+    pub enabled: bool,
+    /// Duration in seconds of the window during which joins are counted.
+    pub window: u32,
+    /// Maximum number of members joining during the window.
+    pub max_joins: u32,
+    /// Duration in seconds of the raid mode.
+    pub cooldown: u32,
+    /// Whether members joining during raid mode are kicked.
+    pub kick: bool,
+}
+
+impl Default for AntiRaid {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window: 10,
+            max_joins: 10,
+            cooldown: 300,
+            kick: false,
+        }
+    }
+}
+
+with_prefix!(prefix_raid "raid_");
+
+/// Configuration for the account age gate.
+///
+/// Members whose account has been created less than `min_age` seconds before
+/// joining the guild are subject to the configured action. Each attempt is
+/// recorded in the modlogs.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct AccountAge {
+    /// Whether the account age gate is enabled.
+    pub enabled: bool,
+    /// Minimum age in seconds of the account of joining members.
+    pub min_age: u32,
+    /// Action applied to members with a younger account.
+    pub action: AccountAgeAction,
+}
+
+impl Default for AccountAge {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_age: 604_800, // 7 days
+            action: AccountAgeAction::Kick,
+        }
+    }
+}
+
+/// Action applied by the [`AccountAge`] gate.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum AccountAgeAction {
+    /// Kick the member.
+    ///
+    /// The member is notified of the minimum account age by private message.
+    Kick,
+    /// Give a quarantine role to the member.
+    Quarantine {
+        #[serde_as(as = "IdAsI64")]
+        role: Id<RoleMarker>,
+    },
+    /// Only send a message in the logs channel.
+    Log,
+}
+
+with_prefix!(prefix_account_age "account_age_");
+
+/// Exemptions of the auto-moderation modules.
+///
+/// Messages sent by exempted members or in exempted channels are not checked
+/// by any auto-moderation module. Modules may also have their own exemptions.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct Exemptions {
+    /// Exempted channels.
+    ///
+    /// Threads of exempted channels are also exempted.
+    #[serde_as(as = "Vec<IdAsI64>")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub channels: Vec<Id<ChannelMarker>>,
+    /// Exempted categories.
+    #[serde_as(as = "Vec<IdAsI64>")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<Id<ChannelMarker>>,
+    /// Exempted roles.
+    #[serde_as(as = "Vec<IdAsI64>")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<Id<RoleMarker>>,
+    /// Whether members with the `MANAGE_MESSAGES` permission in the channel
+    /// are exempted.
+    ///
+    /// This is enabled by default.
+    pub manage_messages: bool,
+}
+
+impl Default for Exemptions {
+    fn default() -> Self {
+        Self {
+            channels: Vec::new(),
+            categories: Vec::new(),
+            roles: Vec::new(),
+            manage_messages: true,
+        }
+    }
+}
+
+with_prefix!(prefix_exemptions "exemptions_");
+
+/// Configuration for the anti-spam module.
+///
+/// The anti-spam counts the messages sent by each member during a sliding
+/// window of `window` seconds.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct AntiSpam {
+    /// Whether the anti-spam is enabled.
+    pub enabled: bool,
+    /// Duration in seconds of the window during which messages are counted.
+    pub window: u32,
+    /// Maximum number of messages sent by a member during the window.
+    pub max_messages: u32,
+    /// Maximum number of messages with the same content during the window.
+    pub max_duplicates: u32,
+    /// Maximum number of occurrences of the same word during the window.
+    pub max_repeated_words: u32,
+    /// Action applied when spam is detected.
+    pub action: AutoModAction,
+}
+
+impl Default for AntiSpam {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window: 10,
+            max_messages: 7,
+            max_duplicates: 3,
+            max_repeated_words: 15,
+            action: AutoModAction::default(),
+        }
+    }
+}
+
+with_prefix!(prefix_antispam "antispam_");
+
+/// Configuration for the invite filter.
+///
+/// The filter detects messages containing invites to other guilds. Invites to
+/// the guild itself and to partner guilds are allowed.
+///
+/// By default, members sending invites are warned, so that repeat offenders
+/// are sanctioned according to the escalation rules of the guild.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct InviteFilter {
+    /// Whether the invite filter is enabled.
+    pub enabled: bool,
+    /// Partner guilds whose invites are allowed.
+    #[serde_as(as = "Vec<IdAsI64>")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_guilds: Vec<Id<GuildMarker>>,
+    /// Action applied when an invite is detected.
+    pub action: AutoModAction,
+}
+
+impl Default for InviteFilter {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allowed_guilds: Vec::new(),
+            action: AutoModAction {
+                delete: true,
+                sanction: Some(AutoModSanction::Warn),
+            },
+        }
+    }
+}
+
+with_prefix!(prefix_invites "invites_");
+
+/// Configuration for the mention spam filter.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct MentionFilter {
+    /// Whether the mention spam filter is enabled.
+    pub enabled: bool,
+    /// Maximum number of unique users and roles mentioned in a message.
+    pub max_mentions: u32,
+    /// Maximum number of unique users mentioned during the window.
+    pub max_window_mentions: u32,
+    /// Duration in seconds of the window during which mentions are counted.
+    pub window: u32,
+    /// Whether attempts to mention `@everyone`, `@here` or roles by members
+    /// without the `MENTION_EVERYONE` permission are blocked.
+    pub block_everyone: bool,
+    /// Action applied when mention spam is detected.
+    pub action: AutoModAction,
+}
+
+impl Default for MentionFilter {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_mentions: 5,
+            max_window_mentions: 10,
+            window: 60,
+            block_everyone: true,
+            action: AutoModAction {
+                delete: true,
+                sanction: Some(AutoModSanction::Warn),
+            },
+        }
+    }
+}
+
+with_prefix!(prefix_mentions "mentions_");
+
+/// Configuration for the blocklist filter.
+///
+/// Blocked words and wildcard patterns are matched against the normalized
+/// words of messages, which prevents bypassing the filter with lookalike
+/// characters. Regular expressions are matched against the raw content.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct Blocklist {
+    /// Whether the blocklist filter is enabled.
+    pub enabled: bool,
+    /// Blocked words.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<String>,
+    /// Blocked wildcard patterns.
+    ///
+    /// Patterns may contain `*` to match any sequence of characters and `?`
+    /// to match a single character.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub wildcards: Vec<String>,
+    /// Blocked regular expressions.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub regexes: Vec<String>,
+    /// Roles whose members are not filtered.
+    #[serde_as(as = "Vec<IdAsI64>")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exempt_roles: Vec<Id<RoleMarker>>,
+    /// Channels where messages are not filtered.
+    #[serde_as(as = "Vec<IdAsI64>")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exempt_channels: Vec<Id<ChannelMarker>>,
+    /// Action applied when a blocked term is detected.
+    pub action: AutoModAction,
+}
+
+impl Default for Blocklist {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            words: Vec::new(),
+            wildcards: Vec::new(),
+            regexes: Vec::new(),
+            exempt_roles: Vec::new(),
+            exempt_channels: Vec::new(),
+            action: AutoModAction {
+                delete: true,
+                sanction: Some(AutoModSanction::Warn),
+            },
+        }
+    }
+}
+
+with_prefix!(prefix_blocklist "blocklist_");
+
+/// Configuration for the phishing filter.
+///
+/// The filter detects links to known malicious domains and to domains that
+/// imitate popular websites such as Discord or Steam.
+///
+/// By default, members sending such links are muted for one hour, since they
+/// are usually sent by compromised accounts.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct PhishingFilter {
+    /// Whether the phishing filter is enabled.
+    pub enabled: bool,
+    /// Action applied when a malicious link is detected.
+    pub action: AutoModAction,
+}
+
+impl Default for PhishingFilter {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            action: AutoModAction {
+                delete: true,
+                sanction: Some(AutoModSanction::Mute { duration: 3600 }),
+            },
+        }
+    }
+}
+
+with_prefix!(prefix_phishing "phishing_");
+
+/// Configuration for the excessive caps filter.
+///
+/// Mentions, custom emojis and links are ignored when counting letters.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct CapsFilter {
+    /// Whether the caps filter is enabled.
+    pub enabled: bool,
+    /// Minimum number of letters of checked messages.
+    pub min_length: u32,
+    /// Maximum percentage of uppercase letters.
+    pub max_ratio: u32,
+    /// Action applied when excessive caps are detected.
+    pub action: AutoModAction,
+}
+
+impl Default for CapsFilter {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_length: 10,
+            max_ratio: 70,
+            action: AutoModAction::default(),
+        }
+    }
+}
+
+with_prefix!(prefix_caps "caps_");
+
+/// Configuration for the excessive emojis filter.
+///
+/// Both unicode and custom emojis are counted. Each emoji counts as a single
+/// character of the message length.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct EmojiFilter {
+    /// Whether the emojis filter is enabled.
+    pub enabled: bool,
+    /// Minimum number of characters of checked messages, whitespace
+    /// excluded.
+    pub min_length: u32,
+    /// Maximum number of emojis in a message.
+    pub max_emojis: u32,
+    /// Action applied when excessive emojis are detected.
+    pub action: AutoModAction,
+}
+
+impl Default for EmojiFilter {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_length: 10,
+            max_emojis: 10,
+            action: AutoModAction::default(),
+        }
+    }
+}
+
+with_prefix!(prefix_emojis "emojis_");
+
+/// Configuration for the zalgo text filter.
+///
+/// Zalgo text uses many combining characters stacked on top of each other.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct ZalgoFilter {
+    /// Whether the zalgo filter is enabled.
+    pub enabled: bool,
+    /// Minimum number of characters of checked messages, combining
+    /// characters excluded.
+    pub min_length: u32,
+    /// Maximum number of combining characters, as a percentage of the other
+    /// characters.
+    pub max_ratio: u32,
+    /// Action applied when zalgo text is detected.
+    pub action: AutoModAction,
+}
+
+impl Default for ZalgoFilter {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_length: 5,
+            max_ratio: 50,
+            action: AutoModAction::default(),
+        }
+    }
+}
+
+with_prefix!(prefix_zalgo "zalgo_");
+
+/// Action applied by auto-moderation modules.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct AutoModAction {
+    /// Whether the offending messages are deleted.
+    pub delete: bool,
+    /// Sanction applied to the member.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sanction: Option<AutoModSanction>,
+}
+
+impl Default for AutoModAction {
+    fn default() -> Self {
+        Self {
+            delete: true,
+            sanction: None,
+        }
+    }
+}
+
+/// Sanction applied by an [`AutoModAction`].
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum AutoModSanction {
+    /// Warn the member.
+    ///
+    /// The warning may trigger the escalation rules of the guild.
+    Warn,
+    /// Mute the member for a given duration in seconds.
+    Mute { duration: i64 },
+}
@@ -0,0 +1,59 @@
+//! Models for the `lockdowns` collection.
+
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, skip_serializing_none};
+use time::OffsetDateTime;
+use twilight_model::{
+    guild::Permissions,
+    id::{
+        marker::{ChannelMarker, GuildMarker, UserMarker},
+        Id,
+    },
+};
+
+use crate::serde::{DateTimeAsBson, IdAsI64};
+
+/// Channel locked with the lockdown command.
+///
+/// The permission overwrite of the `@everyone` role is saved before the
+/// channel is locked, so that it can be restored exactly when the channel is
+/// unlocked.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Lockdown {
+    /// Id of the locked channel.
+    #[serde_as(as = "IdAsI64")]
+    #[serde(rename = "_id")]
+    pub channel_id: Id<ChannelMarker>,
+    /// Guild of the locked channel.
+    #[serde_as(as = "IdAsI64")]
+    pub guild_id: Id<GuildMarker>,
+    /// Permission overwrite of the `@everyone` role before the lockdown.
+    ///
+    /// This field is [`None`] if the channel had no overwrite for the
+    /// `@everyone` role, in which case the overwrite is deleted when the
+    /// channel is unlocked.
+    #[serde(default)]
+    pub overwrite: Option<LockdownOverwrite>,
+    /// Moderator that locked the channel.
+    #[serde_as(as = "IdAsI64")]
+    pub moderator: Id<UserMarker>,
+    /// Date of the lockdown.
+    #[serde_as(as = "DateTimeAsBson")]
+    pub date: OffsetDateTime,
+}
+
+impl Lockdown {
+    /// Name of the MongoDB collection.
+    pub const COLLECTION: &'static str = "lockdowns";
+}
+
+/// Saved permission overwrite of a [`Lockdown`].
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct LockdownOverwrite {
+    /// Allowed permissions.
+    pub allow: Permissions,
+    /// Denied permissions.
+    pub deny: Permissions,
+}
@@ -6,14 +6,21 @@
 //! ## MongoDB collections
 //! - `guilds` ([Guild]): configuration for guilds that uses the bot
 //! - `modlogs` ([Modlog]): moderation logs
+//! - `counters` ([GuildCounters]): per-guild counters such as case numbers
+//! - `lockdowns` ([Lockdown]): channels locked with the lockdown command
 //!
 //! Each collection name is exported as an associated constant.
 //!
 //! [Guild]: guild::Guild
+//! [GuildCounters]: counter::GuildCounters
+//! [Lockdown]: lockdown::Lockdown
 //! [Modlog]: modlog::Modlog
 
 mod client;
+pub mod counter;
 pub mod guild;
+pub mod lockdown;
 pub mod modlog;
 
 pub use client::{MongoDbClient, MongoDbError};
+pub use mongodb::bson::oid::ObjectId;
@@ -6,9 +6,10 @@ use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
 use time::OffsetDateTime;
 use twilight_model::{
     id::{
-        marker::{GuildMarker, UserMarker},
+        marker::{ChannelMarker, GuildMarker, MessageMarker, UserMarker},
         Id,
     },
+    user::{CurrentUser, User},
     util::ImageHash,
 };
 
@@ -24,6 +25,12 @@ pub struct Modlog {
     /// Unique ID of the moderation log.
     #[serde(rename = "_id")]
     pub id: Option<ObjectId>,
+    /// Case number of the moderation log.
+    ///
+    /// Case numbers are unique per guild and assigned when the moderation log
+    /// is stored in the database.
+    #[serde(default)]
+    pub case: Option<u64>,
     /// Type of moderation log.
     pub kind: ModlogType,
     /// Guild where the moderation log was issued.
@@ -40,6 +47,31 @@ pub struct Modlog {
     pub reason: Option<String>,
     /// Optional notes attached to the moderation log.
     pub notes: Option<String>,
+    /// Expiration date of temporary sanctions.
+    #[serde_as(as = "Option<DateTimeAsBson>")]
+    #[serde(default)]
+    pub expires_at: Option<OffsetDateTime>,
+    /// Whether the temporary sanction has expired and has been lifted.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub expired: bool,
+    /// Warnings that triggered an automatic sanction.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warns: Vec<ObjectId>,
+    /// Revocation of the sanction, if it has been manually lifted.
+    #[serde(default)]
+    pub revoked: Option<ModlogRevocation>,
+    /// Whether the user has been notified of the sanction by private message.
+    ///
+    /// The notification may fail if the user has closed its private messages
+    /// or blocked the bot. This is [`None`] if no notification has been sent.
+    #[serde(default)]
+    pub notified: Option<bool>,
+    /// Message sent in the logs channel when the modlog was issued.
+    #[serde(default)]
+    pub logs_message: Option<ModlogMessage>,
+    /// Edits of the reason and notes, from the oldest to the latest.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub edits: Vec<ModlogEdit>,
 }
 
 impl Modlog {
@@ -52,6 +84,83 @@ impl Modlog {
 #[serde(rename_all = "lowercase")]
 pub enum ModlogType {
     Kick,
+    Ban,
+    Mute,
+    Warn,
+    /// Member flagged by an automatic check, such as the account age gate.
+    Flag,
+}
+
+/// Type of sanction applied to a user.
+///
+/// This is the subset of [`ModlogType`] that can be applied by moderators or
+/// by the auto-moderation. Flags are only recorded in the modlogs.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SanctionKind {
+    Kick,
+    Ban,
+    Mute,
+    Warn,
+}
+
+impl From<SanctionKind> for ModlogType {
+    fn from(kind: SanctionKind) -> Self {
+        match kind {
+            SanctionKind::Kick => ModlogType::Kick,
+            SanctionKind::Ban => ModlogType::Ban,
+            SanctionKind::Mute => ModlogType::Mute,
+            SanctionKind::Warn => ModlogType::Warn,
+        }
+    }
+}
+
+/// Revocation of a sanction.
+///
+/// This is stored in the [`Modlog`] of a sanction that has been manually
+/// lifted by a moderator (e.g. with an unban).
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ModlogRevocation {
+    /// Moderator that revoked the sanction.
+    pub moderator: ModlogUser,
+    /// Date of the revocation.
+    #[serde_as(as = "DateTimeAsBson")]
+    pub date: OffsetDateTime,
+    /// Optional reason provided by the moderator.
+    pub reason: Option<String>,
+}
+
+/// Reference to a message sent in the logs channel.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ModlogMessage {
+    /// Channel the message was sent in.
+    #[serde_as(as = "IdAsI64")]
+    pub channel_id: Id<ChannelMarker>,
+    /// Id of the message.
+    #[serde_as(as = "IdAsI64")]
+    pub message_id: Id<MessageMarker>,
+}
+
+/// Edit of a modlog reason and notes.
+///
+/// The previous values are stored to keep an history of the modifications
+/// made by moderators.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ModlogEdit {
+    /// Moderator that edited the modlog.
+    pub editor: ModlogUser,
+    /// Date of the edit.
+    #[serde_as(as = "DateTimeAsBson")]
+    pub date: OffsetDateTime,
+    /// Reason before the edit.
+    pub reason: Option<String>,
+    /// Notes before the edit.
+    pub notes: Option<String>,
 }
 
 /// User model stored with modlog information.
@@ -65,7 +174,29 @@ pub struct ModlogUser {
     pub id: Id<UserMarker>,
     pub name: String,
     pub discriminator: u16,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub avatar: Option<ImageHash>,
 }
+
+impl From<&CurrentUser> for ModlogUser {
+    fn from(user: &CurrentUser) -> Self {
+        Self {
+            id: user.id,
+            name: user.name.clone(),
+            discriminator: user.discriminator,
+            avatar: user.avatar,
+        }
+    }
+}
+
+impl From<&User> for ModlogUser {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.id,
+            name: user.name.clone(),
+            discriminator: user.discriminator,
+            avatar: user.avatar,
+        }
+    }
+}
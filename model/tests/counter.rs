@@ -0,0 +1,31 @@
+use mongodb::bson;
+use pretty_assertions::assert_eq;
+use raidprotect_model::mongodb::counter::GuildCounters;
+use twilight_model::id::Id;
+
+#[test]
+fn test_counters_bson() {
+    let counters = GuildCounters {
+        id: Id::new(1),
+        modlog_case: 142,
+    };
+
+    let expected = bson::doc! {
+        "_id": 1_i64,
+        "modlog_case": 142_i64,
+    };
+
+    assert_eq!(bson::to_document(&counters).unwrap(), expected);
+    assert_eq!(
+        bson::from_document::<GuildCounters>(expected).unwrap(),
+        counters
+    );
+}
+
+#[test]
+fn test_counters_default() {
+    let document = bson::doc! { "_id": 1_i64 };
+    let counters = bson::from_document::<GuildCounters>(document).unwrap();
+
+    assert_eq!(counters.modlog_case, 0);
+}
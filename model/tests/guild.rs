@@ -1,6 +1,13 @@
+#![recursion_limit = "256"]
+
 use mongodb::bson;
 use pretty_assertions::assert_eq;
-use raidprotect_model::mongodb::guild::{Captcha, Guild, Moderation};
+use raidprotect_model::mongodb::guild::{
+    AccountAge, AccountAgeAction, AntiRaid, AntiSpam, AutoModAction, AutoModSanction, Blocklist,
+    CapsFilter, Captcha, CaptchaDifficulty, CaptchaUpdate, EmojiFilter, EscalationRule,
+    EscalationSanction, Exemptions, Guild, InviteFilter, MentionFilter, Moderation, PhishingFilter,
+    ZalgoFilter,
+};
 use serde_test::{assert_tokens, Token};
 use twilight_model::id::Id;
 
@@ -24,6 +31,181 @@ fn test_guild_default() {
             Token::Bool(true),
             Token::Str("captcha_enabled"),
             Token::Bool(false),
+            Token::Str("captcha_difficulty"),
+            Token::UnitVariant {
+                name: "CaptchaDifficulty",
+                variant: "medium",
+            },
+            Token::Str("raid_enabled"),
+            Token::Bool(false),
+            Token::Str("raid_window"),
+            Token::U32(10),
+            Token::Str("raid_max_joins"),
+            Token::U32(10),
+            Token::Str("raid_cooldown"),
+            Token::U32(300),
+            Token::Str("raid_kick"),
+            Token::Bool(false),
+            Token::Str("account_age_enabled"),
+            Token::Bool(false),
+            Token::Str("account_age_min_age"),
+            Token::U32(604_800),
+            Token::Str("account_age_action"),
+            Token::Struct {
+                name: "AccountAgeAction",
+                len: 1,
+            },
+            Token::Str("kind"),
+            Token::Str("kick"),
+            Token::StructEnd,
+            Token::Str("exemptions_manage_messages"),
+            Token::Bool(true),
+            Token::Str("antispam_enabled"),
+            Token::Bool(false),
+            Token::Str("antispam_window"),
+            Token::U32(10),
+            Token::Str("antispam_max_messages"),
+            Token::U32(7),
+            Token::Str("antispam_max_duplicates"),
+            Token::U32(3),
+            Token::Str("antispam_max_repeated_words"),
+            Token::U32(15),
+            Token::Str("antispam_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 1,
+            },
+            Token::Str("delete"),
+            Token::Bool(true),
+            Token::StructEnd,
+            Token::Str("invites_enabled"),
+            Token::Bool(false),
+            Token::Str("invites_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 2,
+            },
+            Token::Str("delete"),
+            Token::Bool(true),
+            Token::Str("sanction"),
+            Token::Some,
+            Token::Struct {
+                name: "AutoModSanction",
+                len: 1,
+            },
+            Token::Str("kind"),
+            Token::Str("warn"),
+            Token::StructEnd,
+            Token::StructEnd,
+            Token::Str("mentions_enabled"),
+            Token::Bool(false),
+            Token::Str("mentions_max_mentions"),
+            Token::U32(5),
+            Token::Str("mentions_max_window_mentions"),
+            Token::U32(10),
+            Token::Str("mentions_window"),
+            Token::U32(60),
+            Token::Str("mentions_block_everyone"),
+            Token::Bool(true),
+            Token::Str("mentions_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 2,
+            },
+            Token::Str("delete"),
+            Token::Bool(true),
+            Token::Str("sanction"),
+            Token::Some,
+            Token::Struct {
+                name: "AutoModSanction",
+                len: 1,
+            },
+            Token::Str("kind"),
+            Token::Str("warn"),
+            Token::StructEnd,
+            Token::StructEnd,
+            Token::Str("blocklist_enabled"),
+            Token::Bool(false),
+            Token::Str("blocklist_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 2,
+            },
+            Token::Str("delete"),
+            Token::Bool(true),
+            Token::Str("sanction"),
+            Token::Some,
+            Token::Struct {
+                name: "AutoModSanction",
+                len: 1,
+            },
+            Token::Str("kind"),
+            Token::Str("warn"),
+            Token::StructEnd,
+            Token::StructEnd,
+            Token::Str("phishing_enabled"),
+            Token::Bool(false),
+            Token::Str("phishing_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 2,
+            },
+            Token::Str("delete"),
+            Token::Bool(true),
+            Token::Str("sanction"),
+            Token::Some,
+            Token::Struct {
+                name: "AutoModSanction",
+                len: 2,
+            },
+            Token::Str("kind"),
+            Token::Str("mute"),
+            Token::Str("duration"),
+            Token::I64(3600),
+            Token::StructEnd,
+            Token::StructEnd,
+            Token::Str("caps_enabled"),
+            Token::Bool(false),
+            Token::Str("caps_min_length"),
+            Token::U32(10),
+            Token::Str("caps_max_ratio"),
+            Token::U32(70),
+            Token::Str("caps_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 1,
+            },
+            Token::Str("delete"),
+            Token::Bool(true),
+            Token::StructEnd,
+            Token::Str("emojis_enabled"),
+            Token::Bool(false),
+            Token::Str("emojis_min_length"),
+            Token::U32(10),
+            Token::Str("emojis_max_emojis"),
+            Token::U32(10),
+            Token::Str("emojis_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 1,
+            },
+            Token::Str("delete"),
+            Token::Bool(true),
+            Token::StructEnd,
+            Token::Str("zalgo_enabled"),
+            Token::Bool(false),
+            Token::Str("zalgo_min_length"),
+            Token::U32(5),
+            Token::Str("zalgo_max_ratio"),
+            Token::U32(50),
+            Token::Str("zalgo_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 1,
+            },
+            Token::Str("delete"),
+            Token::Bool(true),
+            Token::StructEnd,
             Token::MapEnd,
         ],
     );
@@ -39,6 +221,19 @@ fn test_guild_full() {
             roles: vec![Id::new(3), Id::new(4)],
             enforce_reason: true,
             anonymize: false,
+            appeal_url: Some("https://example.com/appeal".to_string()),
+            escalation: vec![
+                EscalationRule {
+                    warns: 3,
+                    period: Some(604_800),
+                    sanction: EscalationSanction::Mute { duration: 3600 },
+                },
+                EscalationRule {
+                    warns: 5,
+                    period: None,
+                    sanction: EscalationSanction::Kick,
+                },
+            ],
         },
         captcha: Captcha {
             enabled: true,
@@ -47,6 +242,101 @@ fn test_guild_full() {
             role: Some(Id::new(7)),
             verified_roles: vec![Id::new(8), Id::new(9)],
             logs: Some(Id::new(10)),
+            difficulty: CaptchaDifficulty::Hard,
+        },
+        raid: AntiRaid {
+            enabled: true,
+            window: 30,
+            max_joins: 5,
+            cooldown: 600,
+            kick: true,
+        },
+        account_age: AccountAge {
+            enabled: true,
+            min_age: 86_400,
+            action: AccountAgeAction::Quarantine { role: Id::new(17) },
+        },
+        exemptions: Exemptions {
+            channels: vec![Id::new(14)],
+            categories: vec![Id::new(15)],
+            roles: vec![Id::new(16)],
+            manage_messages: false,
+        },
+        antispam: AntiSpam {
+            enabled: true,
+            window: 5,
+            max_messages: 5,
+            max_duplicates: 2,
+            max_repeated_words: 10,
+            action: AutoModAction {
+                delete: false,
+                sanction: Some(AutoModSanction::Mute { duration: 600 }),
+            },
+        },
+        invites: InviteFilter {
+            enabled: true,
+            allowed_guilds: vec![Id::new(11)],
+            action: AutoModAction {
+                delete: true,
+                sanction: None,
+            },
+        },
+        mentions: MentionFilter {
+            enabled: true,
+            max_mentions: 3,
+            max_window_mentions: 6,
+            window: 30,
+            block_everyone: false,
+            action: AutoModAction {
+                delete: false,
+                sanction: Some(AutoModSanction::Warn),
+            },
+        },
+        blocklist: Blocklist {
+            enabled: true,
+            words: vec!["spam".to_string()],
+            wildcards: vec!["free*nitro".to_string()],
+            regexes: vec![r"\d{4}-\d{4}".to_string()],
+            exempt_roles: vec![Id::new(12)],
+            exempt_channels: vec![Id::new(13)],
+            action: AutoModAction {
+                delete: true,
+                sanction: None,
+            },
+        },
+        phishing: PhishingFilter {
+            enabled: true,
+            action: AutoModAction {
+                delete: false,
+                sanction: None,
+            },
+        },
+        caps: CapsFilter {
+            enabled: true,
+            min_length: 20,
+            max_ratio: 80,
+            action: AutoModAction {
+                delete: false,
+                sanction: None,
+            },
+        },
+        emojis: EmojiFilter {
+            enabled: true,
+            min_length: 15,
+            max_emojis: 5,
+            action: AutoModAction {
+                delete: false,
+                sanction: None,
+            },
+        },
+        zalgo: ZalgoFilter {
+            enabled: true,
+            min_length: 10,
+            max_ratio: 100,
+            action: AutoModAction {
+                delete: false,
+                sanction: None,
+            },
         },
     };
 
@@ -70,6 +360,47 @@ fn test_guild_full() {
             Token::Bool(true),
             Token::Str("moderation_anonymize"),
             Token::Bool(false),
+            Token::Str("moderation_appeal_url"),
+            Token::Some,
+            Token::Str("https://example.com/appeal"),
+            Token::Str("moderation_escalation"),
+            Token::Seq { len: Some(2) },
+            Token::Struct {
+                name: "EscalationRule",
+                len: 3,
+            },
+            Token::Str("warns"),
+            Token::U32(3),
+            Token::Str("period"),
+            Token::Some,
+            Token::I64(604_800),
+            Token::Str("sanction"),
+            Token::Struct {
+                name: "EscalationSanction",
+                len: 2,
+            },
+            Token::Str("kind"),
+            Token::Str("mute"),
+            Token::Str("duration"),
+            Token::I64(3600),
+            Token::StructEnd,
+            Token::StructEnd,
+            Token::Struct {
+                name: "EscalationRule",
+                len: 2,
+            },
+            Token::Str("warns"),
+            Token::U32(5),
+            Token::Str("sanction"),
+            Token::Struct {
+                name: "EscalationSanction",
+                len: 1,
+            },
+            Token::Str("kind"),
+            Token::Str("kick"),
+            Token::StructEnd,
+            Token::StructEnd,
+            Token::SeqEnd,
             Token::Str("captcha_enabled"),
             Token::Bool(true),
             Token::Str("captcha_channel"),
@@ -89,6 +420,201 @@ fn test_guild_full() {
             Token::Str("captcha_logs"),
             Token::Some,
             Token::I64(10),
+            Token::Str("captcha_difficulty"),
+            Token::UnitVariant {
+                name: "CaptchaDifficulty",
+                variant: "hard",
+            },
+            Token::Str("raid_enabled"),
+            Token::Bool(true),
+            Token::Str("raid_window"),
+            Token::U32(30),
+            Token::Str("raid_max_joins"),
+            Token::U32(5),
+            Token::Str("raid_cooldown"),
+            Token::U32(600),
+            Token::Str("raid_kick"),
+            Token::Bool(true),
+            Token::Str("account_age_enabled"),
+            Token::Bool(true),
+            Token::Str("account_age_min_age"),
+            Token::U32(86_400),
+            Token::Str("account_age_action"),
+            Token::Struct {
+                name: "AccountAgeAction",
+                len: 2,
+            },
+            Token::Str("kind"),
+            Token::Str("quarantine"),
+            Token::Str("role"),
+            Token::I64(17),
+            Token::StructEnd,
+            Token::Str("exemptions_channels"),
+            Token::Seq { len: Some(1) },
+            Token::I64(14),
+            Token::SeqEnd,
+            Token::Str("exemptions_categories"),
+            Token::Seq { len: Some(1) },
+            Token::I64(15),
+            Token::SeqEnd,
+            Token::Str("exemptions_roles"),
+            Token::Seq { len: Some(1) },
+            Token::I64(16),
+            Token::SeqEnd,
+            Token::Str("exemptions_manage_messages"),
+            Token::Bool(false),
+            Token::Str("antispam_enabled"),
+            Token::Bool(true),
+            Token::Str("antispam_window"),
+            Token::U32(5),
+            Token::Str("antispam_max_messages"),
+            Token::U32(5),
+            Token::Str("antispam_max_duplicates"),
+            Token::U32(2),
+            Token::Str("antispam_max_repeated_words"),
+            Token::U32(10),
+            Token::Str("antispam_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 2,
+            },
+            Token::Str("delete"),
+            Token::Bool(false),
+            Token::Str("sanction"),
+            Token::Some,
+            Token::Struct {
+                name: "AutoModSanction",
+                len: 2,
+            },
+            Token::Str("kind"),
+            Token::Str("mute"),
+            Token::Str("duration"),
+            Token::I64(600),
+            Token::StructEnd,
+            Token::StructEnd,
+            Token::Str("invites_enabled"),
+            Token::Bool(true),
+            Token::Str("invites_allowed_guilds"),
+            Token::Seq { len: Some(1) },
+            Token::I64(11),
+            Token::SeqEnd,
+            Token::Str("invites_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 1,
+            },
+            Token::Str("delete"),
+            Token::Bool(true),
+            Token::StructEnd,
+            Token::Str("mentions_enabled"),
+            Token::Bool(true),
+            Token::Str("mentions_max_mentions"),
+            Token::U32(3),
+            Token::Str("mentions_max_window_mentions"),
+            Token::U32(6),
+            Token::Str("mentions_window"),
+            Token::U32(30),
+            Token::Str("mentions_block_everyone"),
+            Token::Bool(false),
+            Token::Str("mentions_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 2,
+            },
+            Token::Str("delete"),
+            Token::Bool(false),
+            Token::Str("sanction"),
+            Token::Some,
+            Token::Struct {
+                name: "AutoModSanction",
+                len: 1,
+            },
+            Token::Str("kind"),
+            Token::Str("warn"),
+            Token::StructEnd,
+            Token::StructEnd,
+            Token::Str("blocklist_enabled"),
+            Token::Bool(true),
+            Token::Str("blocklist_words"),
+            Token::Seq { len: Some(1) },
+            Token::Str("spam"),
+            Token::SeqEnd,
+            Token::Str("blocklist_wildcards"),
+            Token::Seq { len: Some(1) },
+            Token::Str("free*nitro"),
+            Token::SeqEnd,
+            Token::Str("blocklist_regexes"),
+            Token::Seq { len: Some(1) },
+            Token::Str(r"\d{4}-\d{4}"),
+            Token::SeqEnd,
+            Token::Str("blocklist_exempt_roles"),
+            Token::Seq { len: Some(1) },
+            Token::I64(12),
+            Token::SeqEnd,
+            Token::Str("blocklist_exempt_channels"),
+            Token::Seq { len: Some(1) },
+            Token::I64(13),
+            Token::SeqEnd,
+            Token::Str("blocklist_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 1,
+            },
+            Token::Str("delete"),
+            Token::Bool(true),
+            Token::StructEnd,
+            Token::Str("phishing_enabled"),
+            Token::Bool(true),
+            Token::Str("phishing_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 1,
+            },
+            Token::Str("delete"),
+            Token::Bool(false),
+            Token::StructEnd,
+            Token::Str("caps_enabled"),
+            Token::Bool(true),
+            Token::Str("caps_min_length"),
+            Token::U32(20),
+            Token::Str("caps_max_ratio"),
+            Token::U32(80),
+            Token::Str("caps_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 1,
+            },
+            Token::Str("delete"),
+            Token::Bool(false),
+            Token::StructEnd,
+            Token::Str("emojis_enabled"),
+            Token::Bool(true),
+            Token::Str("emojis_min_length"),
+            Token::U32(15),
+            Token::Str("emojis_max_emojis"),
+            Token::U32(5),
+            Token::Str("emojis_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 1,
+            },
+            Token::Str("delete"),
+            Token::Bool(false),
+            Token::StructEnd,
+            Token::Str("zalgo_enabled"),
+            Token::Bool(true),
+            Token::Str("zalgo_min_length"),
+            Token::U32(10),
+            Token::Str("zalgo_max_ratio"),
+            Token::U32(100),
+            Token::Str("zalgo_action"),
+            Token::Struct {
+                name: "AutoModAction",
+                len: 1,
+            },
+            Token::Str("delete"),
+            Token::Bool(false),
+            Token::StructEnd,
             Token::MapEnd,
         ],
     );
@@ -104,6 +630,19 @@ fn test_guild_bson() {
             roles: vec![Id::new(3), Id::new(4)],
             enforce_reason: true,
             anonymize: false,
+            appeal_url: Some("https://example.com/appeal".to_string()),
+            escalation: vec![
+                EscalationRule {
+                    warns: 3,
+                    period: Some(604_800),
+                    sanction: EscalationSanction::Mute { duration: 3600 },
+                },
+                EscalationRule {
+                    warns: 5,
+                    period: None,
+                    sanction: EscalationSanction::Kick,
+                },
+            ],
         },
         captcha: Captcha {
             enabled: true,
@@ -112,6 +651,101 @@ fn test_guild_bson() {
             role: Some(Id::new(7)),
             verified_roles: vec![Id::new(8), Id::new(9)],
             logs: Some(Id::new(10)),
+            difficulty: CaptchaDifficulty::Hard,
+        },
+        raid: AntiRaid {
+            enabled: true,
+            window: 30,
+            max_joins: 5,
+            cooldown: 600,
+            kick: true,
+        },
+        account_age: AccountAge {
+            enabled: true,
+            min_age: 86_400,
+            action: AccountAgeAction::Quarantine { role: Id::new(17) },
+        },
+        exemptions: Exemptions {
+            channels: vec![Id::new(14)],
+            categories: vec![Id::new(15)],
+            roles: vec![Id::new(16)],
+            manage_messages: false,
+        },
+        antispam: AntiSpam {
+            enabled: true,
+            window: 5,
+            max_messages: 5,
+            max_duplicates: 2,
+            max_repeated_words: 10,
+            action: AutoModAction {
+                delete: false,
+                sanction: Some(AutoModSanction::Mute { duration: 600 }),
+            },
+        },
+        invites: InviteFilter {
+            enabled: true,
+            allowed_guilds: vec![Id::new(11)],
+            action: AutoModAction {
+                delete: true,
+                sanction: None,
+            },
+        },
+        mentions: MentionFilter {
+            enabled: true,
+            max_mentions: 3,
+            max_window_mentions: 6,
+            window: 30,
+            block_everyone: false,
+            action: AutoModAction {
+                delete: false,
+                sanction: Some(AutoModSanction::Warn),
+            },
+        },
+        blocklist: Blocklist {
+            enabled: true,
+            words: vec!["spam".to_string()],
+            wildcards: vec!["free*nitro".to_string()],
+            regexes: vec![r"\d{4}-\d{4}".to_string()],
+            exempt_roles: vec![Id::new(12)],
+            exempt_channels: vec![Id::new(13)],
+            action: AutoModAction {
+                delete: true,
+                sanction: None,
+            },
+        },
+        phishing: PhishingFilter {
+            enabled: true,
+            action: AutoModAction {
+                delete: false,
+                sanction: None,
+            },
+        },
+        caps: CapsFilter {
+            enabled: true,
+            min_length: 20,
+            max_ratio: 80,
+            action: AutoModAction {
+                delete: false,
+                sanction: None,
+            },
+        },
+        emojis: EmojiFilter {
+            enabled: true,
+            min_length: 15,
+            max_emojis: 5,
+            action: AutoModAction {
+                delete: false,
+                sanction: None,
+            },
+        },
+        zalgo: ZalgoFilter {
+            enabled: true,
+            min_length: 10,
+            max_ratio: 100,
+            action: AutoModAction {
+                delete: false,
+                sanction: None,
+            },
         },
     };
 
@@ -122,14 +756,98 @@ fn test_guild_bson() {
         "moderation_roles": [3_i64, 4_i64],
         "moderation_enforce_reason": true,
         "moderation_anonymize": false,
+        "moderation_appeal_url": "https://example.com/appeal",
+        "moderation_escalation": [
+            { "warns": 3_i64, "period": 604_800_i64, "sanction": { "kind": "mute", "duration": 3600_i64 } },
+            { "warns": 5_i64, "sanction": { "kind": "kick" } },
+        ],
         "captcha_enabled": true,
         "captcha_channel": 5_i64,
         "captcha_message": 6_i64,
         "captcha_role": 7_i64,
         "captcha_verified_roles": [8_i64, 9_i64],
         "captcha_logs": 10_i64,
+        "captcha_difficulty": "hard",
+        "raid_enabled": true,
+        "raid_window": 30_i64,
+        "raid_max_joins": 5_i64,
+        "raid_cooldown": 600_i64,
+        "raid_kick": true,
+        "account_age_enabled": true,
+        "account_age_min_age": 86_400_i64,
+        "account_age_action": {
+            "kind": "quarantine",
+            "role": 17_i64,
+        },
+        "exemptions_channels": [14_i64],
+        "exemptions_categories": [15_i64],
+        "exemptions_roles": [16_i64],
+        "exemptions_manage_messages": false,
+        "antispam_enabled": true,
+        "antispam_window": 5_i64,
+        "antispam_max_messages": 5_i64,
+        "antispam_max_duplicates": 2_i64,
+        "antispam_max_repeated_words": 10_i64,
+        "antispam_action": { "delete": false, "sanction": { "kind": "mute", "duration": 600_i64 } },
+        "invites_enabled": true,
+        "invites_allowed_guilds": [11_i64],
+        "invites_action": { "delete": true },
+        "mentions_enabled": true,
+        "mentions_max_mentions": 3_i64,
+        "mentions_max_window_mentions": 6_i64,
+        "mentions_window": 30_i64,
+        "mentions_block_everyone": false,
+        "mentions_action": { "delete": false, "sanction": { "kind": "warn" } },
+        "blocklist_enabled": true,
+        "blocklist_words": ["spam"],
+        "blocklist_wildcards": ["free*nitro"],
+        "blocklist_regexes": [r"\d{4}-\d{4}"],
+        "blocklist_exempt_roles": [12_i64],
+        "blocklist_exempt_channels": [13_i64],
+        "blocklist_action": { "delete": true },
+        "phishing_enabled": true,
+        "phishing_action": { "delete": false },
+        "caps_enabled": true,
+        "caps_min_length": 20_i64,
+        "caps_max_ratio": 80_i64,
+        "caps_action": { "delete": false },
+        "emojis_enabled": true,
+        "emojis_min_length": 15_i64,
+        "emojis_max_emojis": 5_i64,
+        "emojis_action": { "delete": false },
+        "zalgo_enabled": true,
+        "zalgo_min_length": 10_i64,
+        "zalgo_max_ratio": 100_i64,
+        "zalgo_action": { "delete": false },
     };
 
     assert_eq!(bson::to_document(&guild).unwrap(), expected);
     assert_eq!(bson::from_document::<Guild>(expected).unwrap(), guild);
 }
+
+#[test]
+fn test_captcha_update() {
+    assert!(CaptchaUpdate::default().is_empty());
+
+    let update = CaptchaUpdate {
+        enabled: Some(false),
+        channel: Some(Some(Id::new(1))),
+        message: Some(None),
+        remove_verified_role: Some(Id::new(2)),
+        difficulty: Some(CaptchaDifficulty::Easy),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        update.to_document().unwrap(),
+        bson::doc! {
+            "$set": {
+                "captcha_channel": 1_i64,
+                "captcha_enabled": false,
+                "captcha_difficulty": "easy",
+            },
+            "$unset": { "captcha_message": "" },
+            "$pull": { "captcha_verified_roles": 2_i64 },
+        }
+    );
+}
@@ -0,0 +1,47 @@
+use mongodb::bson::{self, DateTime};
+use pretty_assertions::assert_eq;
+use raidprotect_model::mongodb::lockdown::{Lockdown, LockdownOverwrite};
+use time::OffsetDateTime;
+use twilight_model::{guild::Permissions, id::Id};
+
+#[test]
+fn test_lockdown_bson() {
+    let lockdown = Lockdown {
+        channel_id: Id::new(1),
+        guild_id: Id::new(2),
+        overwrite: Some(LockdownOverwrite {
+            allow: Permissions::ADD_REACTIONS,
+            deny: Permissions::ATTACH_FILES,
+        }),
+        moderator: Id::new(3),
+        date: OffsetDateTime::from_unix_timestamp(1_628_594_197_123).unwrap(),
+    };
+
+    let expected = bson::doc! {
+        "_id": 1_i64,
+        "guild_id": 2_i64,
+        "overwrite": {
+            "allow": "64",
+            "deny": "32768",
+        },
+        "moderator": 3_i64,
+        "date": DateTime::from_millis(1_628_594_197_123),
+    };
+
+    assert_eq!(bson::to_document(&lockdown).unwrap(), expected);
+    assert_eq!(bson::from_document::<Lockdown>(expected).unwrap(), lockdown);
+}
+
+#[test]
+fn test_lockdown_without_overwrite() {
+    let document = bson::doc! {
+        "_id": 1_i64,
+        "guild_id": 2_i64,
+        "moderator": 3_i64,
+        "date": DateTime::from_millis(1_628_594_197_123),
+    };
+    let lockdown = bson::from_document::<Lockdown>(document.clone()).unwrap();
+
+    assert_eq!(lockdown.overwrite, None);
+    assert_eq!(bson::to_document(&lockdown).unwrap(), document);
+}
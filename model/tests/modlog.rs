@@ -1,6 +1,8 @@
 use mongodb::bson::{self, oid::ObjectId, DateTime};
 use pretty_assertions::assert_eq;
-use raidprotect_model::mongodb::modlog::{Modlog, ModlogType, ModlogUser};
+use raidprotect_model::mongodb::modlog::{
+    Modlog, ModlogEdit, ModlogMessage, ModlogRevocation, ModlogType, ModlogUser, SanctionKind,
+};
 use serde_test::{assert_tokens, Configure, Token};
 use time::OffsetDateTime;
 use twilight_model::{id::Id, util::ImageHash};
@@ -9,6 +11,7 @@ use twilight_model::{id::Id, util::ImageHash};
 fn test_modlog_full() {
     let modlog = Modlog {
         id: Some(ObjectId::parse_str("62aca55a551e9a0102351bda").unwrap()),
+        case: Some(142),
         kind: ModlogType::Kick,
         guild_id: Id::new(1),
         user: ModlogUser {
@@ -28,6 +31,13 @@ fn test_modlog_full() {
         date: OffsetDateTime::from_unix_timestamp(1_628_594_197_123).unwrap(),
         reason: Some("reason".to_string()),
         notes: Some("notes".to_string()),
+        expires_at: None,
+        expired: false,
+        warns: Vec::new(),
+        revoked: None,
+        notified: None,
+        logs_message: None,
+        edits: Vec::new(),
     };
 
     assert_tokens(
@@ -35,7 +45,7 @@ fn test_modlog_full() {
         &[
             Token::Struct {
                 name: "Modlog",
-                len: 8,
+                len: 9,
             },
             // id
             Token::Str("_id"),
@@ -47,6 +57,10 @@ fn test_modlog_full() {
             Token::Str("$oid"),
             Token::Str("62aca55a551e9a0102351bda"),
             Token::StructEnd,
+            // case
+            Token::Str("case"),
+            Token::Some,
+            Token::U64(142),
             // kind
             Token::Str("kind"),
             Token::Enum { name: "ModlogType" },
@@ -127,6 +141,7 @@ fn test_modlog_full() {
 fn test_modlog_bson() {
     let modlog = Modlog {
         id: Some(ObjectId::parse_str("62aca55a551e9a0102351bda").unwrap()),
+        case: Some(142),
         kind: ModlogType::Kick,
         guild_id: Id::new(1),
         user: ModlogUser {
@@ -146,10 +161,18 @@ fn test_modlog_bson() {
         date: OffsetDateTime::from_unix_timestamp(1_628_594_197_123).unwrap(),
         reason: Some("reason".to_string()),
         notes: Some("notes".to_string()),
+        expires_at: None,
+        expired: false,
+        warns: Vec::new(),
+        revoked: None,
+        notified: None,
+        logs_message: None,
+        edits: Vec::new(),
     };
 
     let expected = bson::doc! {
         "_id": ObjectId::parse_str("62aca55a551e9a0102351bda").unwrap(),
+        "case": 142_i64,
         "kind": "kick",
         "guild_id": 1_i64,
         "user": {
@@ -172,3 +195,215 @@ fn test_modlog_bson() {
     assert_eq!(bson::to_document(&modlog).unwrap(), expected);
     assert_eq!(bson::from_document::<Modlog>(expected).unwrap(), modlog);
 }
+
+#[test]
+fn test_modlog_temporary_bson() {
+    let modlog = Modlog {
+        id: Some(ObjectId::parse_str("62aca55a551e9a0102351bda").unwrap()),
+        case: None,
+        kind: ModlogType::Ban,
+        guild_id: Id::new(1),
+        user: ModlogUser {
+            id: Id::new(2),
+            name: "username".to_string(),
+            discriminator: 1234,
+            avatar: None,
+        },
+        moderator: ModlogUser {
+            id: Id::new(3),
+            name: "moderator".to_string(),
+            discriminator: 4567,
+            avatar: None,
+        },
+        date: OffsetDateTime::from_unix_timestamp(1_628_594_197_123).unwrap(),
+        reason: None,
+        notes: None,
+        expires_at: Some(OffsetDateTime::from_unix_timestamp(1_628_594_200_000).unwrap()),
+        expired: true,
+        warns: vec![ObjectId::parse_str("62aca55a551e9a0102351bdb").unwrap()],
+        revoked: None,
+        notified: None,
+        logs_message: None,
+        edits: Vec::new(),
+    };
+
+    let expected = bson::doc! {
+        "_id": ObjectId::parse_str("62aca55a551e9a0102351bda").unwrap(),
+        "kind": "ban",
+        "guild_id": 1_i64,
+        "user": {
+            "id": 2_i64,
+            "name": "username",
+            "discriminator": 1234_i32,
+        },
+        "moderator": {
+            "id": 3_i64,
+            "name": "moderator",
+            "discriminator": 4567_i32,
+        },
+        "date": DateTime::from_millis(1_628_594_197_123),
+        "expires_at": DateTime::from_millis(1_628_594_200_000),
+        "expired": true,
+        "warns": [ObjectId::parse_str("62aca55a551e9a0102351bdb").unwrap()],
+    };
+
+    assert_eq!(bson::to_document(&modlog).unwrap(), expected);
+    assert_eq!(bson::from_document::<Modlog>(expected).unwrap(), modlog);
+}
+
+#[test]
+fn test_modlog_revoked_bson() {
+    let moderator = ModlogUser {
+        id: Id::new(3),
+        name: "moderator".to_string(),
+        discriminator: 4567,
+        avatar: None,
+    };
+    let modlog = Modlog {
+        id: Some(ObjectId::parse_str("62aca55a551e9a0102351bda").unwrap()),
+        case: None,
+        kind: ModlogType::Mute,
+        guild_id: Id::new(1),
+        user: ModlogUser {
+            id: Id::new(2),
+            name: "username".to_string(),
+            discriminator: 1234,
+            avatar: None,
+        },
+        moderator: moderator.clone(),
+        date: OffsetDateTime::from_unix_timestamp(1_628_594_197_123).unwrap(),
+        reason: None,
+        notes: None,
+        expires_at: Some(OffsetDateTime::from_unix_timestamp(1_628_594_200_000).unwrap()),
+        expired: false,
+        warns: Vec::new(),
+        revoked: Some(ModlogRevocation {
+            moderator,
+            date: OffsetDateTime::from_unix_timestamp(1_628_594_198_000).unwrap(),
+            reason: Some("reason".to_string()),
+        }),
+        notified: None,
+        logs_message: None,
+        edits: Vec::new(),
+    };
+
+    let expected = bson::doc! {
+        "_id": ObjectId::parse_str("62aca55a551e9a0102351bda").unwrap(),
+        "kind": "mute",
+        "guild_id": 1_i64,
+        "user": {
+            "id": 2_i64,
+            "name": "username",
+            "discriminator": 1234_i32,
+        },
+        "moderator": {
+            "id": 3_i64,
+            "name": "moderator",
+            "discriminator": 4567_i32,
+        },
+        "date": DateTime::from_millis(1_628_594_197_123),
+        "expires_at": DateTime::from_millis(1_628_594_200_000),
+        "revoked": {
+            "moderator": {
+                "id": 3_i64,
+                "name": "moderator",
+                "discriminator": 4567_i32,
+            },
+            "date": DateTime::from_millis(1_628_594_198_000),
+            "reason": "reason",
+        },
+    };
+
+    assert_eq!(bson::to_document(&modlog).unwrap(), expected);
+    assert_eq!(bson::from_document::<Modlog>(expected).unwrap(), modlog);
+}
+
+#[test]
+fn test_modlog_edited_bson() {
+    let moderator = ModlogUser {
+        id: Id::new(3),
+        name: "moderator".to_string(),
+        discriminator: 4567,
+        avatar: None,
+    };
+    let modlog = Modlog {
+        id: Some(ObjectId::parse_str("62aca55a551e9a0102351bda").unwrap()),
+        case: Some(3),
+        kind: ModlogType::Warn,
+        guild_id: Id::new(1),
+        user: ModlogUser {
+            id: Id::new(2),
+            name: "username".to_string(),
+            discriminator: 1234,
+            avatar: None,
+        },
+        moderator: moderator.clone(),
+        date: OffsetDateTime::from_unix_timestamp(1_628_594_197_123).unwrap(),
+        reason: Some("new reason".to_string()),
+        notes: None,
+        expires_at: None,
+        expired: false,
+        warns: Vec::new(),
+        revoked: None,
+        notified: Some(false),
+        logs_message: Some(ModlogMessage {
+            channel_id: Id::new(4),
+            message_id: Id::new(5),
+        }),
+        edits: vec![ModlogEdit {
+            editor: moderator,
+            date: OffsetDateTime::from_unix_timestamp(1_628_594_198_000).unwrap(),
+            reason: Some("reason".to_string()),
+            notes: Some("notes".to_string()),
+        }],
+    };
+
+    let expected = bson::doc! {
+        "_id": ObjectId::parse_str("62aca55a551e9a0102351bda").unwrap(),
+        "case": 3_i64,
+        "kind": "warn",
+        "guild_id": 1_i64,
+        "user": {
+            "id": 2_i64,
+            "name": "username",
+            "discriminator": 1234_i32,
+        },
+        "moderator": {
+            "id": 3_i64,
+            "name": "moderator",
+            "discriminator": 4567_i32,
+        },
+        "date": DateTime::from_millis(1_628_594_197_123),
+        "reason": "new reason",
+        "notified": false,
+        "logs_message": {
+            "channel_id": 4_i64,
+            "message_id": 5_i64,
+        },
+        "edits": [{
+            "editor": {
+                "id": 3_i64,
+                "name": "moderator",
+                "discriminator": 4567_i32,
+            },
+            "date": DateTime::from_millis(1_628_594_198_000),
+            "reason": "reason",
+            "notes": "notes",
+        }],
+    };
+
+    assert_eq!(bson::to_document(&modlog).unwrap(), expected);
+    assert_eq!(bson::from_document::<Modlog>(expected).unwrap(), modlog);
+}
+
+#[test]
+fn test_sanction_kind() {
+    assert_tokens(
+        &SanctionKind::Mute,
+        &[Token::UnitVariant {
+            name: "SanctionKind",
+            variant: "mute",
+        }],
+    );
+    assert_eq!(ModlogType::from(SanctionKind::Mute), ModlogType::Mute);
+}
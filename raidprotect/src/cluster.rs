@@ -1,6 +1,6 @@
 //! Shards cluster implementation.
 
-use std::sync::Arc;
+use std::{future, sync::Arc};
 
 use anyhow::Context;
 use futures::StreamExt;
@@ -21,10 +21,17 @@ use twilight_model::{
         marker::{ApplicationMarker, GuildMarker},
         Id,
     },
+    user::CurrentUser,
 };
 
 use crate::{
-    event::ProcessEvent, interaction::register_commands, util::shutdown::ShutdownSubscriber,
+    event::{
+        message::{BlocklistCache, DomainList, FileDomainSource},
+        ProcessEvent,
+    },
+    interaction::register_commands,
+    task,
+    util::shutdown::ShutdownSubscriber,
 };
 
 /// Discord shards cluster.
@@ -39,6 +46,8 @@ pub struct ShardCluster {
     events: Events,
     /// Shared cluster state
     state: Arc<ClusterState>,
+    /// Source of the phishing domains
+    phishing_domains: Option<FileDomainSource>,
 }
 
 impl ShardCluster {
@@ -56,6 +65,7 @@ impl ShardCluster {
             .model()
             .await?;
         let current_user = application.id;
+        let bot_user = http.current_user().exec().await?.model().await?;
 
         info!("logged as {} with ID {}", application.name, current_user);
 
@@ -71,6 +81,10 @@ impl ShardCluster {
             .ping()
             .await
             .context("failed to connect to mongodb")?;
+        mongodb
+            .create_indexes()
+            .await
+            .context("failed to create mongodb indexes")?;
 
         let intents = Intents::GUILDS
             | Intents::GUILD_MEMBERS
@@ -85,7 +99,7 @@ impl ShardCluster {
 
         info!("started cluster with {} shards", cluster.shards().len());
 
-        let state = ClusterState::new(redis, mongodb, http, current_user);
+        let state = ClusterState::new(redis, mongodb, http, current_user, bot_user);
 
         register_commands(&state, application.id).await;
 
@@ -93,6 +107,7 @@ impl ShardCluster {
             cluster: Arc::new(cluster),
             events,
             state: Arc::new(state),
+            phishing_domains: config.phishing_domains.map(FileDomainSource),
         })
     }
 
@@ -107,9 +122,27 @@ impl ShardCluster {
             cluster.up().await;
         });
 
-        // Handle incoming events
+        // Handle incoming events and run background tasks
+        let state = self.state.clone();
+
+        // The phishing domains are only refreshed if a source is configured.
+        let phishing_source = self.phishing_domains.take();
+        let phishing_domains = {
+            let state = state.clone();
+
+            async move {
+                match phishing_source {
+                    Some(source) => task::phishing_domains(state, source).await,
+                    None => future::pending().await,
+                }
+            }
+        };
+
         tokio::select! {
             _ = self.handle_events() => {},
+            _ = task::expired_sanctions(state.clone()) => {},
+            _ = task::captcha_timeouts(state) => {},
+            _ = phishing_domains => {},
             _ = shutdown.wait_shutdown() => {},
         };
 
@@ -161,6 +194,12 @@ pub struct ClusterState {
     http: Arc<HttpClient>,
     /// Bot user id
     current_user: Id<ApplicationMarker>,
+    /// Bot user, fetched when the cluster starts
+    bot_user: CurrentUser,
+    /// Compiled guild blocklists
+    blocklists: BlocklistCache,
+    /// Known phishing domains
+    phishing_domains: DomainList,
 }
 
 impl ClusterState {
@@ -170,12 +209,16 @@ impl ClusterState {
         mongodb: MongoDbClient,
         http: Arc<HttpClient>,
         current_user: Id<ApplicationMarker>,
+        bot_user: CurrentUser,
     ) -> Self {
         Self {
             redis,
             mongodb,
             http,
             current_user,
+            bot_user,
+            blocklists: BlocklistCache::default(),
+            phishing_domains: DomainList::default(),
         }
     }
 
@@ -203,4 +246,19 @@ impl ClusterState {
     pub fn current_user(&self) -> Id<ApplicationMarker> {
         self.current_user
     }
+
+    /// Get the bot [`CurrentUser`]
+    pub fn bot_user(&self) -> &CurrentUser {
+        &self.bot_user
+    }
+
+    /// Get the cluster [`BlocklistCache`]
+    pub fn blocklists(&self) -> &BlocklistCache {
+        &self.blocklists
+    }
+
+    /// Get the cluster [`DomainList`] of phishing domains
+    pub fn phishing_domains(&self) -> &DomainList {
+        &self.phishing_domains
+    }
 }
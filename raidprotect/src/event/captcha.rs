@@ -0,0 +1,204 @@
+//! Captcha configuration repair.
+//!
+//! The captcha depends on a verification channel, a verification message and
+//! an unverified role that may be deleted by the guild administrators. This
+//! module keeps the configuration consistent when this happens:
+//!
+//! - the captcha is disabled if its channel or its unverified role is deleted.
+//! - deleted verified roles are removed from the configuration.
+//! - the verification message is sent again if it is deleted.
+//!
+//! Each change is reported in the guild's logs channel. The configuration is
+//! updated with targeted updates of the captcha fields, so that concurrent
+//! changes to the guild configuration are not overwritten.
+
+use std::sync::Arc;
+
+use raidprotect_model::mongodb::guild::{CaptchaUpdate, Guild};
+use tracing::error;
+use twilight_mention::Mention;
+use twilight_model::id::{
+    marker::{ChannelMarker, GuildMarker, MessageMarker, RoleMarker},
+    Id,
+};
+
+use crate::{
+    cluster::ClusterState,
+    interaction::{component::CaptchaVerify, embed},
+    translations::Lang,
+    util::guild_logs_channel,
+};
+
+/// Handle the deletion of a guild channel.
+pub async fn handle_channel_delete(
+    guild_id: Id<GuildMarker>,
+    channel_id: Id<ChannelMarker>,
+    state: Arc<ClusterState>,
+) {
+    if let Err(error) = channel_delete(guild_id, channel_id, &state).await {
+        error!(error = ?error, guild = ?guild_id, "error while processing captcha channel deletion");
+    }
+}
+
+/// Handle the deletion of a guild role.
+pub async fn handle_role_delete(
+    guild_id: Id<GuildMarker>,
+    role_id: Id<RoleMarker>,
+    state: Arc<ClusterState>,
+) {
+    if let Err(error) = role_delete(guild_id, role_id, &state).await {
+        error!(error = ?error, guild = ?guild_id, "error while processing captcha role deletion");
+    }
+}
+
+/// Handle the deletion of one or several messages.
+pub async fn handle_message_delete(
+    guild_id: Id<GuildMarker>,
+    message_ids: &[Id<MessageMarker>],
+    state: Arc<ClusterState>,
+) {
+    if let Err(error) = message_delete(guild_id, message_ids, &state).await {
+        error!(error = ?error, guild = ?guild_id, "error while processing captcha message deletion");
+    }
+}
+
+async fn channel_delete(
+    guild_id: Id<GuildMarker>,
+    channel_id: Id<ChannelMarker>,
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    let config = match state.mongodb().get_guild(guild_id).await? {
+        Some(config) => config,
+        None => return Ok(()),
+    };
+
+    let mut update = CaptchaUpdate::default();
+    let disabled = config.captcha.channel == Some(channel_id);
+
+    if config.captcha.logs == Some(channel_id) {
+        update.logs = Some(None);
+    }
+
+    if disabled {
+        update.enabled = Some(false);
+        update.channel = Some(None);
+        update.message = Some(None);
+    }
+
+    state.mongodb().update_captcha(guild_id, &update).await?;
+
+    if disabled && config.captcha.enabled {
+        let guild_lang = Lang::from(&*config.lang);
+        send_logs(&config, guild_lang.captcha_channel_deleted(), state).await?;
+    }
+
+    Ok(())
+}
+
+async fn role_delete(
+    guild_id: Id<GuildMarker>,
+    role_id: Id<RoleMarker>,
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    let config = match state.mongodb().get_guild(guild_id).await? {
+        Some(config) => config,
+        None => return Ok(()),
+    };
+    let guild_lang = Lang::from(&*config.lang);
+
+    if config.captcha.role == Some(role_id) {
+        let update = CaptchaUpdate {
+            enabled: Some(false),
+            role: Some(None),
+            ..Default::default()
+        };
+        state.mongodb().update_captcha(guild_id, &update).await?;
+
+        if config.captcha.enabled {
+            send_logs(&config, guild_lang.captcha_role_deleted(), state).await?;
+        }
+    } else if config.captcha.verified_roles.contains(&role_id) {
+        let update = CaptchaUpdate {
+            remove_verified_role: Some(role_id),
+            ..Default::default()
+        };
+        state.mongodb().update_captcha(guild_id, &update).await?;
+
+        if config.captcha.enabled {
+            let description = guild_lang.captcha_verified_role_deleted(role_id);
+            send_logs(&config, &description, state).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn message_delete(
+    guild_id: Id<GuildMarker>,
+    message_ids: &[Id<MessageMarker>],
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    let config = match state.mongodb().get_guild(guild_id).await? {
+        Some(config) => config,
+        None => return Ok(()),
+    };
+
+    let deleted = match config.captcha.message {
+        Some(message) if message_ids.contains(&message) => message,
+        _ => return Ok(()),
+    };
+
+    // The message is sent again when the captcha is enabled. Otherwise, it
+    // will be sent when the captcha is enabled again.
+    let channel = match config.captcha.channel {
+        Some(channel) if config.captcha.enabled => channel,
+        _ => {
+            state
+                .mongodb()
+                .replace_captcha_message(guild_id, Some(deleted), None)
+                .await?;
+
+            return Ok(());
+        }
+    };
+
+    let guild_lang = Lang::from(&*config.lang);
+    let message = CaptchaVerify::send_message(guild_id, channel, state, guild_lang).await?;
+
+    // If the message has been replaced in the meantime, the message that has
+    // just been sent is a duplicate. The bot can always delete its own
+    // messages, so the permissions are not checked.
+    if !state
+        .mongodb()
+        .replace_captcha_message(guild_id, Some(deleted), Some(message))
+        .await?
+    {
+        state.http().delete_message(channel, message).exec().await?;
+
+        return Ok(());
+    }
+
+    let description = guild_lang.captcha_message_recreated(channel.mention());
+    send_logs(&config, &description, state).await
+}
+
+/// Send a message in the guild's logs channel.
+async fn send_logs(
+    config: &Guild,
+    description: &str,
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    let guild_lang = Lang::from(&*config.lang);
+    let logs_chan = guild_logs_channel(config.id, config.logs_chan, state, guild_lang).await?;
+    let logs_embed = embed::captcha::config_logs_message(description, guild_lang);
+
+    state
+        .cache_http(config.id)
+        .create_message(logs_chan)
+        .await?
+        .embeds(&[logs_embed])?
+        .exec()
+        .await?;
+
+    Ok(())
+}
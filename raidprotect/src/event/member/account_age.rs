@@ -0,0 +1,124 @@
+//! Account age gate.
+//!
+//! Members whose account has been created less than the configured minimum age
+//! before joining are kicked, given a quarantine role or only reported in the
+//! logs channel. Each attempt is recorded in the modlogs, as a kick or as a
+//! flag, so that repeated attempts from the same user are visible.
+
+use raidprotect_model::mongodb::{
+    guild::{AccountAgeAction, Guild},
+    modlog::{ModlogUser, SanctionKind},
+};
+use time::Duration;
+use twilight_http::request::AuditLogReason;
+use twilight_mention::Mention;
+use twilight_model::{
+    guild::Member,
+    id::{marker::UserMarker, Id},
+    util::Timestamp,
+};
+use twilight_util::snowflake::Snowflake;
+
+use crate::{
+    cluster::ClusterState,
+    interaction::command::moderation::{flag_member, Sanction},
+    translations::Lang,
+};
+
+/// Apply the account age gate to a member joining a guild.
+///
+/// Returns whether the member has been kicked.
+pub async fn check_account_age(
+    member: &Member,
+    config: &Guild,
+    state: &ClusterState,
+) -> Result<bool, anyhow::Error> {
+    let age = account_age(member.user.id, member.joined_at);
+    let min_age = Duration::seconds(config.account_age.min_age.into());
+
+    if age >= min_age {
+        return Ok(false);
+    }
+
+    let guild_id = member.guild_id;
+    let guild_lang = Lang::from(&*config.lang);
+    let reason = guild_lang.account_age_reason(age.whole_days(), whole_days_ceil(min_age));
+
+    let moderator = ModlogUser::from(state.bot_user());
+    let notes = match config.account_age.action {
+        AccountAgeAction::Kick => {
+            Sanction {
+                reason: Some(reason),
+                ..Sanction::new(SanctionKind::Kick, member.user.clone())
+            }
+            .execute(guild_id, moderator, config, state)
+            .await?;
+
+            return Ok(true);
+        }
+        AccountAgeAction::Quarantine { role } => {
+            state
+                .cache_http(guild_id)
+                .add_guild_member_role(member.user.id, role)
+                .await?
+                .reason(&reason)?
+                .exec()
+                .await?;
+
+            Some(guild_lang.account_age_quarantine_notes(role.mention()))
+        }
+        AccountAgeAction::Log => None,
+    };
+
+    flag_member(
+        &member.user,
+        reason,
+        notes,
+        guild_id,
+        moderator,
+        config,
+        state,
+    )
+    .await?;
+
+    Ok(false)
+}
+
+/// Compute the age of an account when joining a guild.
+///
+/// The creation date of the account is extracted from the user id.
+fn account_age(user_id: Id<UserMarker>, joined_at: Timestamp) -> Duration {
+    Duration::milliseconds(joined_at.as_micros() / 1000 - user_id.timestamp())
+}
+
+/// Number of whole days of a duration, rounded up.
+fn whole_days_ceil(duration: Duration) -> i64 {
+    let days = duration.whole_days();
+
+    if duration > Duration::days(days) {
+        days + 1
+    } else {
+        days
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Discord epoch, used to create user ids from timestamps.
+    const DISCORD_EPOCH: i64 = 1_420_070_400_000;
+
+    #[test]
+    fn test_account_age() {
+        let created_at = 1_628_594_197_000;
+        let user_id = Id::new(((created_at - DISCORD_EPOCH) as u64) << 22);
+        let joined_at = Timestamp::from_secs(1_628_594_197 + 3 * 86_400 + 60).unwrap();
+
+        let age = account_age(user_id, joined_at);
+        assert_eq!(age, Duration::days(3) + Duration::minutes(1));
+        assert_eq!(age.whole_days(), 3);
+        assert_eq!(whole_days_ceil(age), 4);
+        assert_eq!(whole_days_ceil(Duration::days(7)), 7);
+    }
+}
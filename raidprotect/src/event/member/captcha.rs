@@ -0,0 +1,131 @@
+//! Captcha verification of joining members.
+//!
+//! Members joining a guild with the captcha enabled are given the unverified
+//! role until they complete the verification. Members that do not complete it
+//! before [`CAPTCHA_TIMEOUT`] are kicked by the `captcha_timeouts` task.
+//!
+//! See the [`captcha`] component for the verification itself.
+//!
+//! [`captcha`]: crate::interaction::component::captcha
+
+use raidprotect_model::{
+    cache::model::captcha::{CaptchaMessage, PendingCaptcha},
+    mongodb::guild::Guild,
+};
+use time::{Duration, OffsetDateTime};
+use twilight_http::{error::ErrorType, request::AuditLogReason};
+use twilight_model::{
+    guild::Member,
+    id::{marker::ChannelMarker, Id},
+};
+
+use crate::{cluster::ClusterState, interaction::component::CaptchaVerify, translations::Lang};
+
+/// Delay for members to complete the verification.
+const CAPTCHA_TIMEOUT: Duration = Duration::minutes(10);
+
+/// Start the captcha verification of a member joining a guild.
+///
+/// The verification message is sent by the `captcha enable` command and sent
+/// again when deleted. If it is missing anyway (for example if it has been
+/// deleted while the bot was offline), it is sent before giving the role.
+pub async fn start_captcha(
+    member: &Member,
+    config: &Guild,
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    let (role, channel) = match (config.captcha.role, config.captcha.channel) {
+        (Some(role), Some(channel)) => (role, channel),
+        _ => return Ok(()),
+    };
+
+    let guild_id = member.guild_id;
+    let guild_lang = Lang::from(&*config.lang);
+
+    // The role is not given if the message cannot be sent, since the member
+    // would not be able to verify.
+    check_message(config, channel, state).await?;
+
+    // The verification is stored before giving the role, so that the member
+    // is never left with the unverified role without being able to verify.
+    let pending = PendingCaptcha::new(guild_id, member.user.id);
+    let expires_at = (OffsetDateTime::now_utc() + CAPTCHA_TIMEOUT).unix_timestamp();
+
+    state
+        .redis()
+        .add_pending_captcha(&pending, expires_at)
+        .await?;
+
+    state
+        .cache_http(guild_id)
+        .add_guild_member_role(member.user.id, role)
+        .await?
+        .reason(guild_lang.captcha_role_reason())?
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+/// Ensure that the verification message of a guild exists.
+///
+/// The message is sent if it is missing. Joins may be processed concurrently
+/// during a raid, so the message is only stored if it has not been replaced in
+/// the meantime, otherwise the duplicate is deleted.
+async fn check_message(
+    config: &Guild,
+    channel: Id<ChannelMarker>,
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    let guild_id = config.id;
+    let current = config.captcha.message;
+
+    if let Some(message_id) = current {
+        let cached = state.redis().get::<CaptchaMessage>(&guild_id).await?;
+
+        if cached.map(|cached| cached.message_id) == Some(message_id) {
+            return Ok(());
+        }
+
+        match state.http().message(channel, message_id).exec().await {
+            Ok(_) => {
+                return state
+                    .redis()
+                    .set(&CaptchaMessage {
+                        guild_id,
+                        message_id,
+                    })
+                    .await;
+            }
+            Err(error) => match error.kind() {
+                ErrorType::Response { status, .. } if status.get() == 404 => {}
+                _ => return Err(error.into()),
+            },
+        }
+    }
+
+    let guild_lang = Lang::from(&*config.lang);
+    let message_id = CaptchaVerify::send_message(guild_id, channel, state, guild_lang).await?;
+
+    if !state
+        .mongodb()
+        .replace_captcha_message(guild_id, current, Some(message_id))
+        .await?
+    {
+        state
+            .http()
+            .delete_message(channel, message_id)
+            .exec()
+            .await?;
+
+        return Ok(());
+    }
+
+    state
+        .redis()
+        .set(&CaptchaMessage {
+            guild_id,
+            message_id,
+        })
+        .await
+}
@@ -0,0 +1,54 @@
+//! Member event handling.
+//!
+//! This module contains the checks applied to members joining a guild, such as
+//! raid detection, the account age gate and the captcha verification.
+
+mod account_age;
+mod captcha;
+mod raid;
+
+use std::sync::Arc;
+
+use tracing::error;
+use twilight_model::guild::Member;
+
+use self::{account_age::check_account_age, captcha::start_captcha, raid::check_raid};
+use crate::cluster::ClusterState;
+
+/// Handle incoming [`Member`] joining a guild.
+pub async fn handle_member_add(member: Member, state: Arc<ClusterState>) {
+    if let Err(error) = handle_member_add_inner(&member, &state).await {
+        error!(error = ?error, guild = ?member.guild_id, "error while processing member join");
+    }
+}
+
+async fn handle_member_add_inner(
+    member: &Member,
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    // Bots can only be added by members with the `MANAGE_GUILD` permission.
+    if member.user.bot {
+        return Ok(());
+    }
+
+    let config = match state.mongodb().get_guild(member.guild_id).await? {
+        Some(config) => config,
+        None => return Ok(()),
+    };
+
+    // Members kicked by the anti-raid are not checked further.
+    if config.raid.enabled && check_raid(member, &config, state).await? {
+        return Ok(());
+    }
+
+    // Members kicked by the account age gate are not checked further.
+    if config.account_age.enabled && check_account_age(member, &config, state).await? {
+        return Ok(());
+    }
+
+    if config.captcha.enabled {
+        start_captcha(member, &config, state).await?;
+    }
+
+    Ok(())
+}
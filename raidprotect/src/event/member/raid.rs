@@ -0,0 +1,64 @@
+//! Anti-raid module.
+//!
+//! Raids are detected from the number of members joining a guild during a
+//! sliding window. When a raid is detected, the guild enters raid mode for the
+//! configured cooldown. The raid mode is extended as long as the join rate
+//! stays above the threshold.
+
+use raidprotect_model::mongodb::guild::Guild;
+use twilight_http::request::AuditLogReason;
+use twilight_model::guild::Member;
+
+use crate::{
+    cluster::ClusterState, interaction::embed, translations::Lang, util::guild_logs_channel,
+};
+
+/// Register a member join and kick the member during raid mode.
+///
+/// Returns whether the member has been kicked.
+pub async fn check_raid(
+    member: &Member,
+    config: &Guild,
+    state: &ClusterState,
+) -> Result<bool, anyhow::Error> {
+    let guild_id = member.guild_id;
+    let guild_lang = Lang::from(&*config.lang);
+    let timestamp = member.joined_at.as_micros() / 1000;
+    let joins = state
+        .redis()
+        .add_member_join(guild_id, member.user.id, timestamp, config.raid.window)
+        .await?;
+
+    // The logs message is only sent when the guild enters raid mode.
+    if joins > u64::from(config.raid.max_joins)
+        && state
+            .redis()
+            .enable_raid_mode(guild_id, config.raid.cooldown)
+            .await?
+    {
+        let logs_chan = guild_logs_channel(guild_id, config.logs_chan, state, guild_lang).await?;
+        let logs_embed = embed::raid::logs_message(joins, &config.raid, guild_lang);
+
+        state
+            .cache_http(guild_id)
+            .create_message(logs_chan)
+            .await?
+            .embeds(&[logs_embed])?
+            .exec()
+            .await?;
+    }
+
+    if config.raid.kick && state.redis().is_raid_mode(guild_id).await? {
+        state
+            .cache_http(guild_id)
+            .remove_guild_member(member.user.id)
+            .await?
+            .reason(guild_lang.raid_kick_reason())?
+            .exec()
+            .await?;
+
+        return Ok(true);
+    }
+
+    Ok(false)
+}
@@ -0,0 +1,183 @@
+//! Auto-moderation actions.
+//!
+//! When an auto-moderation module detects a rule violation, the action
+//! configured for the module is applied with [`apply_action`]: the offending
+//! messages may be deleted and a sanction applied to the member. Sanctions are
+//! stored as modlogs like the ones issued by moderators, and a message with the
+//! offending messages is sent in the guild's logs channel.
+
+use std::collections::HashMap;
+
+use raidprotect_model::{
+    cache::model::message::CachedMessage,
+    mongodb::{
+        guild::{AutoModAction, AutoModSanction, Guild},
+        modlog::{ModlogType, ModlogUser, SanctionKind},
+    },
+};
+use time::Duration;
+use tracing::{error, warn};
+use twilight_model::{
+    id::{
+        marker::{ChannelMarker, GuildMarker, MessageMarker},
+        Id,
+    },
+    user::User,
+};
+
+use crate::{
+    cluster::ClusterState,
+    interaction::{
+        command::moderation::{escalate, Sanction, MAX_MUTE_DURATION},
+        embed,
+    },
+    translations::Lang,
+    util::guild_logs_channel,
+};
+
+/// Rule violation detected by an auto-moderation module.
+#[derive(Debug, Clone)]
+pub struct Violation<'a> {
+    /// Member that violated the rule.
+    pub user: &'a User,
+    /// Messages that violated the rule.
+    pub messages: &'a [CachedMessage],
+    /// Reason of the violation, in the guild language.
+    pub reason: String,
+}
+
+/// Apply an auto-moderation action after a rule violation.
+pub async fn apply_action(
+    action: AutoModAction,
+    guild_id: Id<GuildMarker>,
+    violation: Violation<'_>,
+    config: &Guild,
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    let guild_lang = Lang::from(&*config.lang);
+
+    if action.delete {
+        delete_messages(guild_id, violation.messages, state).await;
+    }
+
+    // Errors that occur when applying the sanction are only logged, so that
+    // the violation is still logged.
+    let sanction = match action.sanction {
+        Some(sanction) => match apply_sanction(sanction, guild_id, &violation, config, state).await
+        {
+            Ok(kind) => Some(kind),
+            Err(error) => {
+                error!(error = ?error, "failed to apply auto-moderation sanction");
+
+                None
+            }
+        },
+        None => None,
+    };
+
+    let logs_chan = guild_logs_channel(guild_id, config.logs_chan, state, guild_lang).await?;
+    let logs_embed = embed::automod::logs_message(
+        violation.user,
+        violation.messages,
+        &violation.reason,
+        action.delete,
+        sanction,
+        guild_lang,
+    );
+
+    state
+        .cache_http(guild_id)
+        .create_message(logs_chan)
+        .await?
+        .embeds(&[logs_embed])?
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+/// Delete the offending messages.
+///
+/// Messages are bulk deleted in each channel. Errors are only logged since
+/// messages may have already been deleted.
+async fn delete_messages(
+    guild_id: Id<GuildMarker>,
+    messages: &[CachedMessage],
+    state: &ClusterState,
+) {
+    let mut channels: HashMap<Id<ChannelMarker>, Vec<Id<MessageMarker>>> = HashMap::new();
+    for message in messages {
+        channels
+            .entry(message.channel_id)
+            .or_default()
+            .push(message.id);
+    }
+
+    let http = state.cache_http(guild_id);
+
+    for (channel_id, messages) in channels {
+        let result = async {
+            match &*messages {
+                [message] => {
+                    http.delete_message(channel_id, *message)
+                        .await?
+                        .exec()
+                        .await?
+                }
+                messages => {
+                    http.delete_messages(channel_id, messages)
+                        .await?
+                        .exec()
+                        .await?
+                }
+            };
+
+            Ok::<_, anyhow::Error>(())
+        }
+        .await;
+
+        if let Err(error) = result {
+            warn!(error = ?error, "failed to delete messages in channel {}", channel_id);
+        }
+    }
+}
+
+/// Apply the sanction of an auto-moderation action.
+///
+/// The bot is registered as the moderator of the sanction. Warnings may
+/// trigger the escalation rules of the guild.
+async fn apply_sanction(
+    sanction: AutoModSanction,
+    guild_id: Id<GuildMarker>,
+    violation: &Violation<'_>,
+    config: &Guild,
+    state: &ClusterState,
+) -> Result<ModlogType, anyhow::Error> {
+    let (kind, duration) = match sanction {
+        AutoModSanction::Warn => (SanctionKind::Warn, None),
+        AutoModSanction::Mute { duration } => (
+            SanctionKind::Mute,
+            Some(Duration::seconds(duration).min(MAX_MUTE_DURATION)),
+        ),
+    };
+
+    let moderator = ModlogUser::from(state.bot_user());
+
+    let modlog = Sanction {
+        reason: Some(violation.reason.clone()),
+        duration,
+        ..Sanction::new(kind, violation.user.clone())
+    }
+    .execute(guild_id, moderator, config, state)
+    .await?;
+
+    // Escalation errors are only logged since the warning has already been
+    // applied, so that the violation is still logged.
+    if kind == SanctionKind::Warn {
+        if let Err(error) = escalate(&modlog, violation.user, config, state).await {
+            error!(error = ?error, "failed to apply warnings escalation");
+        }
+    }
+
+    Ok(kind.into())
+}
@@ -0,0 +1,261 @@
+//! Blocklist filter.
+//!
+//! The blocklist of each guild is compiled once and cached in memory by the
+//! [`BlocklistCache`]. Cached blocklists are compiled again when the guild
+//! configuration changes.
+//!
+//! Blocked words and wildcard patterns are matched against the words of the
+//! message, which are normalized by the [parser](super::parser) with
+//! [`any_ascii`]. Blocked terms are normalized the same way, so that "café"
+//! also blocks "cafe" and "ｃａｆé". Regular expressions are matched against
+//! the raw message content.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use any_ascii::any_ascii;
+use raidprotect_model::{cache::model::message::CachedMessage, mongodb::guild::Blocklist};
+use regex::{Regex, RegexBuilder};
+use tracing::warn;
+use twilight_model::id::{
+    marker::{ChannelMarker, GuildMarker, RoleMarker},
+    Id,
+};
+
+/// Maximum size of a compiled regular expression.
+///
+/// This prevents guilds from configuring regular expressions that use too
+/// much memory.
+const REGEX_SIZE_LIMIT: usize = 1 << 16;
+
+/// In-memory cache of compiled blocklists.
+#[derive(Debug, Default)]
+pub struct BlocklistCache(RwLock<HashMap<Id<GuildMarker>, Arc<CompiledBlocklist>>>);
+
+impl BlocklistCache {
+    /// Get the compiled blocklist of a guild.
+    ///
+    /// The blocklist is compiled if it is not cached or if the cached
+    /// blocklist has been compiled from a different configuration.
+    pub fn get(&self, guild_id: Id<GuildMarker>, config: &Blocklist) -> Arc<CompiledBlocklist> {
+        let cached = self
+            .0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&guild_id)
+            .cloned();
+
+        match cached {
+            Some(cached) if cached.source == *config => cached,
+            _ => {
+                let compiled = Arc::new(CompiledBlocklist::compile(config));
+
+                self.0
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .insert(guild_id, compiled.clone());
+
+                compiled
+            }
+        }
+    }
+
+    /// Remove the cached blocklist of a guild.
+    pub fn invalidate(&self, guild_id: Id<GuildMarker>) {
+        self.0
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&guild_id);
+    }
+}
+
+/// Compiled blocklist of a guild.
+#[derive(Debug)]
+pub struct CompiledBlocklist {
+    /// Configuration the blocklist has been compiled from.
+    source: Blocklist,
+    /// Normalized blocked words.
+    words: HashSet<String>,
+    /// Compiled wildcard patterns.
+    wildcards: Vec<Regex>,
+    /// Compiled regular expressions.
+    regexes: Vec<Regex>,
+}
+
+impl CompiledBlocklist {
+    /// Compile a blocklist.
+    ///
+    /// Invalid regular expressions are ignored.
+    pub fn compile(config: &Blocklist) -> Self {
+        let words = config.words.iter().map(|word| normalize(word)).collect();
+        let wildcards = config
+            .wildcards
+            .iter()
+            .filter_map(|pattern| compile_regex(&wildcard_regex(pattern)))
+            .collect();
+        let regexes = config
+            .regexes
+            .iter()
+            .filter_map(|regex| compile_regex(regex))
+            .collect();
+
+        Self {
+            source: config.clone(),
+            words,
+            wildcards,
+            regexes,
+        }
+    }
+
+    /// Check whether a message contains a blocked term.
+    pub fn is_match(&self, message: &CachedMessage) -> bool {
+        let blocked_word = message.words.iter().any(|word| {
+            let word = word.to_lowercase();
+
+            self.words.contains(&word) || self.wildcards.iter().any(|regex| regex.is_match(&word))
+        });
+
+        blocked_word
+            || self
+                .regexes
+                .iter()
+                .any(|regex| regex.is_match(&message.content))
+    }
+}
+
+/// Check whether a message is exempted from the blocklist.
+pub fn is_exempt(
+    config: &Blocklist,
+    channel_id: Id<ChannelMarker>,
+    roles: &[Id<RoleMarker>],
+) -> bool {
+    config.exempt_channels.contains(&channel_id)
+        || roles.iter().any(|role| config.exempt_roles.contains(role))
+}
+
+/// Normalize a blocked term like message words.
+fn normalize(term: &str) -> String {
+    any_ascii(term).to_lowercase()
+}
+
+/// Convert a wildcard pattern into a regular expression.
+fn wildcard_regex(pattern: &str) -> String {
+    let pattern = normalize(pattern)
+        .split('*')
+        .map(|part| {
+            part.split('?')
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join(".")
+        })
+        .collect::<Vec<_>>()
+        .join(".*");
+
+    format!("^{pattern}$")
+}
+
+/// Compile a regular expression, logging invalid expressions.
+fn compile_regex(regex: &str) -> Option<Regex> {
+    match RegexBuilder::new(regex)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+    {
+        Ok(regex) => Some(regex),
+        Err(error) => {
+            warn!(error = ?error, "invalid blocklist regex {:?}", regex);
+
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::message::test_message;
+
+    fn message(content: &str) -> CachedMessage {
+        CachedMessage {
+            content: content.to_string(),
+            words: content.split_whitespace().map(any_ascii).collect(),
+            ..test_message()
+        }
+    }
+
+    #[test]
+    fn test_blocked_words() {
+        let blocklist = CompiledBlocklist::compile(&Blocklist {
+            words: vec!["Spam".to_string(), "café".to_string()],
+            ..Default::default()
+        });
+
+        assert!(blocklist.is_match(&message("buy SPAM now")));
+        assert!(blocklist.is_match(&message("ｓｐａｍ")));
+        assert!(blocklist.is_match(&message("cafe")));
+        assert!(!blocklist.is_match(&message("spammer")));
+    }
+
+    #[test]
+    fn test_wildcard_patterns() {
+        assert_eq!(wildcard_regex("free*nitro"), "^free.*nitro$");
+        assert_eq!(wildcard_regex("b?d."), r"^b.d\.$");
+
+        let blocklist = CompiledBlocklist::compile(&Blocklist {
+            wildcards: vec!["free*nitro".to_string(), "sc?m".to_string()],
+            ..Default::default()
+        });
+
+        assert!(blocklist.is_match(&message("FreeNitro")));
+        assert!(blocklist.is_match(&message("get freediscordnitro")));
+        assert!(blocklist.is_match(&message("scam")));
+        assert!(!blocklist.is_match(&message("free nitro")));
+        assert!(!blocklist.is_match(&message("scream")));
+    }
+
+    #[test]
+    fn test_regexes() {
+        let blocklist = CompiledBlocklist::compile(&Blocklist {
+            regexes: vec![r"(?i)free\s+nitro".to_string(), "(".to_string()],
+            ..Default::default()
+        });
+
+        assert_eq!(blocklist.regexes.len(), 1);
+        assert!(blocklist.is_match(&message("free   Nitro")));
+        assert!(!blocklist.is_match(&message("nitro")));
+    }
+
+    #[test]
+    fn test_exempt() {
+        let config = Blocklist {
+            exempt_roles: vec![Id::new(1)],
+            exempt_channels: vec![Id::new(2)],
+            ..Default::default()
+        };
+
+        assert!(is_exempt(&config, Id::new(2), &[]));
+        assert!(is_exempt(&config, Id::new(3), &[Id::new(4), Id::new(1)]));
+        assert!(!is_exempt(&config, Id::new(3), &[Id::new(4)]));
+    }
+
+    #[test]
+    fn test_cache_invalidation() {
+        let cache = BlocklistCache::default();
+        let mut config = Blocklist {
+            words: vec!["spam".to_string()],
+            ..Default::default()
+        };
+
+        let first = cache.get(Id::new(1), &config);
+        assert!(Arc::ptr_eq(&first, &cache.get(Id::new(1), &config)));
+
+        config.words.push("scam".to_string());
+        let updated = cache.get(Id::new(1), &config);
+        assert!(!Arc::ptr_eq(&first, &updated));
+        assert!(updated.is_match(&message("scam")));
+
+        cache.invalidate(Id::new(1));
+        assert!(!Arc::ptr_eq(&updated, &cache.get(Id::new(1), &config)));
+    }
+}
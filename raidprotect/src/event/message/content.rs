@@ -0,0 +1,226 @@
+//! Content filters.
+//!
+//! These filters check the shape of the message content: excessive use of
+//! capital letters, excessive number of emojis and zalgo text. Each filter
+//! only applies to messages long enough to avoid false positives on short
+//! messages such as "OK" or "LOL".
+
+use once_cell::sync::Lazy;
+use raidprotect_model::{
+    cache::model::message::CachedMessage,
+    mongodb::guild::{AutoModAction, CapsFilter, EmojiFilter, Guild, ZalgoFilter},
+};
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::translations::Lang;
+
+/// Custom emojis, such as `<:name:id>` or `<a:name:id>` for animated ones.
+static CUSTOM_EMOJI: Lazy<Regex> = Lazy::new(|| Regex::new(r"<a?:\w+:\d+>").unwrap());
+
+/// Custom emojis, mentions and links, which are ignored by the caps filter.
+static MARKUP: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<a?:\w+:\d+>|<(?:@[!&]?|#)\d+>|https?://\S+").unwrap());
+
+/// Kind of content detected in a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentKind {
+    /// Excessive use of capital letters.
+    Caps,
+    /// Excessive number of emojis.
+    Emojis,
+    /// Zalgo text.
+    Zalgo,
+}
+
+impl ContentKind {
+    /// Get the localized reason of the sanction.
+    pub fn reason(self, lang: Lang) -> &'static str {
+        match self {
+            ContentKind::Caps => lang.content_reason_caps(),
+            ContentKind::Emojis => lang.content_reason_emojis(),
+            ContentKind::Zalgo => lang.content_reason_zalgo(),
+        }
+    }
+}
+
+/// Check the content of a message with the enabled content filters.
+///
+/// Returns the kind of content detected and the action of the corresponding
+/// filter.
+pub fn detect_content(
+    config: &Guild,
+    message: &CachedMessage,
+) -> Option<(ContentKind, AutoModAction)> {
+    let content = &message.content;
+
+    if config.zalgo.enabled && is_zalgo(&config.zalgo, content) {
+        return Some((ContentKind::Zalgo, config.zalgo.action));
+    }
+
+    if config.caps.enabled && is_excessive_caps(&config.caps, content) {
+        return Some((ContentKind::Caps, config.caps.action));
+    }
+
+    if config.emojis.enabled && is_excessive_emojis(&config.emojis, content) {
+        return Some((ContentKind::Emojis, config.emojis.action));
+    }
+
+    None
+}
+
+/// Check whether a message contains excessive caps.
+fn is_excessive_caps(config: &CapsFilter, content: &str) -> bool {
+    let content = MARKUP.replace_all(content, "");
+    let (letters, uppercase) = content
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .fold((0, 0), |(letters, uppercase), c| {
+            (letters + 1, uppercase + usize::from(c.is_uppercase()))
+        });
+
+    letters >= config.min_length as usize && uppercase * 100 > letters * config.max_ratio as usize
+}
+
+/// Check whether a message contains excessive emojis.
+fn is_excessive_emojis(config: &EmojiFilter, content: &str) -> bool {
+    let custom = CUSTOM_EMOJI.find_iter(content).count();
+    let length = custom
+        + CUSTOM_EMOJI
+            .replace_all(content, "")
+            .graphemes(true)
+            .filter(|grapheme| !grapheme.trim().is_empty())
+            .count();
+
+    length >= config.min_length as usize && count_emojis(content) > config.max_emojis as usize
+}
+
+/// Count the unicode and custom emojis of a message.
+///
+/// Unicode emojis are counted by grapheme, so that emojis composed of several
+/// characters (such as flags or skin tones) are only counted once.
+fn count_emojis(content: &str) -> usize {
+    let custom = CUSTOM_EMOJI.find_iter(content).count();
+    let unicode = CUSTOM_EMOJI
+        .replace_all(content, "")
+        .graphemes(true)
+        .filter(|grapheme| grapheme.chars().next().is_some_and(is_emoji))
+        .count();
+
+    custom + unicode
+}
+
+/// Check whether a message is zalgo text.
+fn is_zalgo(config: &ZalgoFilter, content: &str) -> bool {
+    let combining = content.chars().filter(|c| is_combining(*c)).count();
+    let other = content
+        .chars()
+        .filter(|c| !c.is_whitespace() && !is_combining(*c))
+        .count();
+
+    other >= config.min_length as usize && combining * 100 > other * config.max_ratio as usize
+}
+
+/// Check whether a character is an emoji.
+///
+/// This covers the main emoji blocks, including pictographs, dingbats and
+/// regional indicators used for flags.
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF | 0x2300..=0x23FF | 0x2600..=0x27BF | 0x2B00..=0x2BFF
+    )
+}
+
+/// Check whether a character is a combining diacritical mark.
+fn is_combining(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0300..=0x036F | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF | 0x20D0..=0x20FF | 0xFE20..=0xFE2F
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::id::Id;
+
+    use super::*;
+    use crate::event::message::test_message;
+
+    #[test]
+    fn test_excessive_caps() {
+        let config = CapsFilter::default();
+
+        assert!(is_excessive_caps(&config, "THIS IS A VERY LOUD MESSAGE"));
+        assert!(is_excessive_caps(&config, "THIS IS A VERY LOUD msg"));
+        assert!(!is_excessive_caps(&config, "This is a normal message"));
+        assert!(!is_excessive_caps(&config, "LOL OK"));
+        assert!(!is_excessive_caps(
+            &config,
+            "<:KEKW:123> <@456> https://EXAMPLE.COM/ABCDEF lol"
+        ));
+    }
+
+    #[test]
+    fn test_count_emojis() {
+        assert_eq!(count_emojis("hello"), 0);
+        assert_eq!(count_emojis("🎉🎉 ❤️ ⭐"), 4);
+        assert_eq!(count_emojis("<:pepe:123><a:party:456> 😀"), 3);
+        // Flags, skin tones and ZWJ sequences are counted once.
+        assert_eq!(count_emojis("🇫🇷 👍🏽 👨‍👩‍👧"), 3);
+    }
+
+    #[test]
+    fn test_excessive_emojis() {
+        let config = EmojiFilter {
+            min_length: 15,
+            max_emojis: 3,
+            ..Default::default()
+        };
+
+        assert!(is_excessive_emojis(&config, "look at this party 🎉🎉🎉🎉"));
+        assert!(is_excessive_emojis(
+            &config,
+            "<:pepe:123> 😀😀😀 hello world!"
+        ));
+        assert!(!is_excessive_emojis(&config, "look at this 🎉🎉"));
+        // Too short to be checked.
+        assert!(!is_excessive_emojis(&config, "🎉 🎉 🎉 🎉"));
+    }
+
+    #[test]
+    fn test_zalgo() {
+        let config = ZalgoFilter::default();
+
+        assert!(is_zalgo(
+            &config,
+            "h\u{0335}\u{0321}\u{0358}e\u{0334}\u{0322}\u{0360}llo\u{0336}\u{0327}"
+        ));
+        assert!(!is_zalgo(&config, "Cre\u{0301}me bru\u{0302}le\u{0301}e"));
+        assert!(!is_zalgo(&config, "a\u{0335}\u{0321}\u{0358}"));
+    }
+
+    #[test]
+    fn test_detect_content() {
+        let mut config = Guild::new(Id::new(1));
+        let message = |content: &str| CachedMessage {
+            content: content.to_string(),
+            ..test_message()
+        };
+
+        let loud = message("THIS IS A VERY LOUD MESSAGE 🎉🎉🎉🎉🎉🎉🎉🎉🎉🎉🎉");
+        assert_eq!(detect_content(&config, &loud), None);
+
+        config.emojis.enabled = true;
+        assert_eq!(
+            detect_content(&config, &loud).map(|(kind, _)| kind),
+            Some(ContentKind::Emojis)
+        );
+
+        config.caps.enabled = true;
+        assert_eq!(
+            detect_content(&config, &loud).map(|(kind, _)| kind),
+            Some(ContentKind::Caps)
+        );
+    }
+}
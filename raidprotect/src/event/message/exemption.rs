@@ -0,0 +1,138 @@
+//! Auto-moderation exemptions.
+//!
+//! Exemptions are checked once for each message, before any auto-moderation
+//! module runs. A message is exempted if it has been sent:
+//!
+//! - by a member with an exempted role,
+//! - in an exempted channel, a thread of an exempted channel or a channel of
+//!   an exempted category,
+//! - by a member with the `MANAGE_MESSAGES` permission in the channel, if
+//!   enabled.
+
+use raidprotect_model::{cache::model::CachedChannel, mongodb::guild::Exemptions};
+use twilight_model::{
+    guild::Permissions,
+    id::{
+        marker::{ChannelMarker, GuildMarker, RoleMarker, UserMarker},
+        Id,
+    },
+};
+
+use crate::cluster::ClusterState;
+
+/// Channel in which a message has been sent, with its parents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ChannelHierarchy {
+    /// Channel of the message.
+    channel: Id<ChannelMarker>,
+    /// Parent channel, if the message has been sent in a thread.
+    parent: Option<Id<ChannelMarker>>,
+    /// Category of the channel.
+    category: Option<Id<ChannelMarker>>,
+}
+
+impl ChannelHierarchy {
+    /// Resolve the parents of a channel from the cache.
+    ///
+    /// Parents that are not found in the cache are ignored.
+    async fn resolve(
+        channel_id: Id<ChannelMarker>,
+        state: &ClusterState,
+    ) -> Result<Self, anyhow::Error> {
+        let mut hierarchy = Self {
+            channel: channel_id,
+            parent: None,
+            category: None,
+        };
+
+        let mut channel = state.redis().get::<CachedChannel>(&channel_id).await?;
+
+        if let Some(CachedChannel::Thread(thread)) = &channel {
+            hierarchy.parent = Some(thread.parent_id);
+            channel = state
+                .redis()
+                .get::<CachedChannel>(&thread.parent_id)
+                .await?;
+        }
+
+        if let Some(CachedChannel::Text(channel)) = channel {
+            hierarchy.category = channel.parent_id;
+        }
+
+        Ok(hierarchy)
+    }
+
+    /// Check whether the channel is exempted.
+    fn is_exempt(&self, config: &Exemptions) -> bool {
+        let exempt_channel = |channel: Id<ChannelMarker>| config.channels.contains(&channel);
+
+        exempt_channel(self.channel)
+            || self.parent.is_some_and(exempt_channel)
+            || self
+                .category
+                .is_some_and(|category| config.categories.contains(&category))
+    }
+}
+
+/// Check whether a message is exempted from auto-moderation.
+pub async fn is_exempt(
+    config: &Exemptions,
+    guild_id: Id<GuildMarker>,
+    channel_id: Id<ChannelMarker>,
+    author_id: Id<UserMarker>,
+    roles: &[Id<RoleMarker>],
+    state: &ClusterState,
+) -> Result<bool, anyhow::Error> {
+    if roles.iter().any(|role| config.roles.contains(role)) {
+        return Ok(true);
+    }
+
+    if !config.channels.is_empty() || !config.categories.is_empty() {
+        let hierarchy = ChannelHierarchy::resolve(channel_id, state).await?;
+
+        if hierarchy.is_exempt(config) {
+            return Ok(true);
+        }
+    }
+
+    if config.manage_messages {
+        let (permissions, _) = state
+            .redis()
+            .permissions(guild_id)
+            .await?
+            .member(author_id, roles)
+            .await?
+            .channel(channel_id)
+            .await?;
+
+        return Ok(permissions.contains(Permissions::MANAGE_MESSAGES));
+    }
+
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_exempt() {
+        let config = Exemptions {
+            channels: vec![Id::new(1)],
+            categories: vec![Id::new(2)],
+            ..Default::default()
+        };
+        let hierarchy = |channel, parent: Option<u64>, category: Option<u64>| ChannelHierarchy {
+            channel: Id::new(channel),
+            parent: parent.map(Id::new),
+            category: category.map(Id::new),
+        };
+
+        assert!(hierarchy(1, None, None).is_exempt(&config));
+        assert!(hierarchy(3, Some(1), None).is_exempt(&config));
+        assert!(hierarchy(3, None, Some(2)).is_exempt(&config));
+        assert!(hierarchy(4, Some(3), Some(2)).is_exempt(&config));
+        assert!(!hierarchy(3, Some(4), Some(5)).is_exempt(&config));
+        assert!(!hierarchy(3, None, None).is_exempt(&Exemptions::default()));
+    }
+}
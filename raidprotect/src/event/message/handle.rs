@@ -1,18 +1,183 @@
-use std::sync::Arc;
+use std::{slice, sync::Arc};
 
-use tracing::info;
-use twilight_model::channel::Message;
+use anyhow::Context;
+use tracing::error;
+use twilight_model::{channel::Message, guild::Permissions};
 
-use super::parser::parse_message;
-use crate::cluster::ClusterState;
+use super::{
+    action::{apply_action, Violation},
+    blocklist,
+    content::detect_content,
+    exemption::is_exempt,
+    invite::{has_forbidden_invite, HttpInviteResolver},
+    mention::{detect_mention_spam, mentions_everyone},
+    parser::parse_message,
+    phishing::detect_phishing,
+    spam::detect_spam,
+};
+use crate::{cluster::ClusterState, translations::Lang};
 
 /// Handle incoming [`Message`].
 ///
 /// This method will forward message to the cache and various auto-moderation
 /// modules.
 pub async fn handle_message(message: Message, state: Arc<ClusterState>) {
+    if let Err(error) = handle_message_inner(message, &state).await {
+        error!(error = ?error, "error while processing message");
+    }
+}
+
+async fn handle_message_inner(message: Message, state: &ClusterState) -> Result<(), anyhow::Error> {
+    let guild_id = message.guild_id.context("message not sent in a guild")?;
     let parsed = parse_message(&message);
-    state.redis().set(&parsed).await.ok();
 
-    info!("received message: {}", message.content) // Debug util real implementation
+    state.redis().add_recent_message(guild_id, &parsed).await?;
+
+    // Messages sent by bots and webhooks are not moderated.
+    if message.author.bot || message.webhook_id.is_some() {
+        return Ok(());
+    }
+
+    let config = match state.mongodb().get_guild(guild_id).await? {
+        Some(config) => config,
+        None => return Ok(()),
+    };
+
+    let guild_lang = Lang::from(&*config.lang);
+    let roles = message
+        .member
+        .as_ref()
+        .map(|member| &*member.roles)
+        .unwrap_or_default();
+
+    if is_exempt(
+        &config.exemptions,
+        guild_id,
+        message.channel_id,
+        message.author.id,
+        roles,
+        state,
+    )
+    .await?
+    {
+        return Ok(());
+    }
+
+    // Recent messages are used by rules that use a sliding window.
+    let messages = if config.antispam.enabled || config.mentions.enabled {
+        state
+            .redis()
+            .recent_messages(guild_id, message.author.id)
+            .await?
+    } else {
+        Vec::new()
+    };
+
+    if config.antispam.enabled {
+        if let Some((kind, window)) = detect_spam(&config.antispam, &messages) {
+            // The recent messages are cleared to avoid applying the action
+            // again for the next messages.
+            state
+                .redis()
+                .clear_recent_messages(guild_id, message.author.id)
+                .await?;
+
+            let violation = Violation {
+                user: &message.author,
+                messages: window,
+                reason: kind.reason(guild_lang).to_string(),
+            };
+
+            return apply_action(config.antispam.action, guild_id, violation, &config, state).await;
+        }
+    }
+
+    if config.mentions.enabled {
+        // Permissions are only computed if the message mentions everyone.
+        let can_mention_everyone = if mentions_everyone(&parsed) {
+            let (permissions, _) = state
+                .redis()
+                .permissions(guild_id)
+                .await?
+                .member(message.author.id, roles)
+                .await?
+                .channel(message.channel_id)
+                .await?;
+
+            permissions.contains(Permissions::MENTION_EVERYONE)
+        } else {
+            true
+        };
+
+        if let Some((kind, offending)) =
+            detect_mention_spam(&config.mentions, &messages, can_mention_everyone)
+        {
+            state
+                .redis()
+                .clear_recent_messages(guild_id, message.author.id)
+                .await?;
+
+            let violation = Violation {
+                user: &message.author,
+                messages: offending,
+                reason: kind.reason(guild_lang).to_string(),
+            };
+
+            return apply_action(config.mentions.action, guild_id, violation, &config, state).await;
+        }
+    }
+
+    if config.blocklist.enabled
+        && !blocklist::is_exempt(&config.blocklist, message.channel_id, roles)
+        && state
+            .blocklists()
+            .get(guild_id, &config.blocklist)
+            .is_match(&parsed)
+    {
+        let violation = Violation {
+            user: &message.author,
+            messages: slice::from_ref(&parsed),
+            reason: guild_lang.blocklist_reason().to_string(),
+        };
+
+        return apply_action(config.blocklist.action, guild_id, violation, &config, state).await;
+    }
+
+    if config.phishing.enabled {
+        if let Some(kind) = detect_phishing(&parsed, state.phishing_domains()) {
+            let violation = Violation {
+                user: &message.author,
+                messages: slice::from_ref(&parsed),
+                reason: kind.reason(guild_lang).to_string(),
+            };
+
+            return apply_action(config.phishing.action, guild_id, violation, &config, state).await;
+        }
+    }
+
+    if config.invites.enabled {
+        let resolver = HttpInviteResolver(state);
+
+        if has_forbidden_invite(&parsed, guild_id, &config.invites, &resolver).await? {
+            let violation = Violation {
+                user: &message.author,
+                messages: slice::from_ref(&parsed),
+                reason: guild_lang.invites_reason().to_string(),
+            };
+
+            return apply_action(config.invites.action, guild_id, violation, &config, state).await;
+        }
+    }
+
+    if let Some((kind, action)) = detect_content(&config, &parsed) {
+        let violation = Violation {
+            user: &message.author,
+            messages: slice::from_ref(&parsed),
+            reason: kind.reason(guild_lang).to_string(),
+        };
+
+        return apply_action(action, guild_id, violation, &config, state).await;
+    }
+
+    Ok(())
 }
@@ -0,0 +1,185 @@
+//! Invite filter.
+//!
+//! The parser classifies Discord invite links as [`MessageLink::Invite`]. The
+//! invite code of each link is resolved to the guild it belongs to, so that
+//! invites to the guild itself and to partner guilds are allowed.
+//!
+//! Invites are resolved through the [`InviteResolver`] trait, which allows to
+//! use a mock implementation in tests. Resolved invites are cached, so that an
+//! invite spammed during a raid does not exhaust the bot rate limit.
+
+use async_trait::async_trait;
+use raidprotect_model::{
+    cache::model::{
+        invite::CachedInvite,
+        message::{CachedMessage, MessageLink},
+    },
+    mongodb::guild::InviteFilter,
+};
+use twilight_http::error::ErrorType;
+use twilight_model::id::{marker::GuildMarker, Id};
+use url::Url;
+
+use crate::cluster::ClusterState;
+
+/// Resolve invite codes to the guild they belong to.
+#[async_trait]
+pub trait InviteResolver {
+    /// Get the guild of an invite.
+    ///
+    /// Returns [`None`] if the invite is invalid or does not belong to a guild
+    /// (such as group DM invites).
+    async fn resolve(&self, code: &str) -> Result<Option<Id<GuildMarker>>, anyhow::Error>;
+}
+
+/// [`InviteResolver`] using the Discord API.
+///
+/// Resolved invites are stored in the cache as [`CachedInvite`], and the API
+/// is only called for invites that are not cached.
+#[derive(Debug)]
+pub struct HttpInviteResolver<'a>(pub &'a ClusterState);
+
+#[async_trait]
+impl InviteResolver for HttpInviteResolver<'_> {
+    async fn resolve(&self, code: &str) -> Result<Option<Id<GuildMarker>>, anyhow::Error> {
+        if let Some(invite) = self.0.redis().get::<CachedInvite>(code).await? {
+            return Ok(invite.guild_id);
+        }
+
+        let guild_id = match self.0.http().invite(code).exec().await {
+            Ok(response) => response.model().await?.guild.map(|guild| guild.id),
+            Err(error) => match error.kind() {
+                ErrorType::Response { status, .. } if status.get() == 404 => None,
+                _ => return Err(error.into()),
+            },
+        };
+
+        let invite = CachedInvite {
+            code: code.to_string(),
+            guild_id,
+        };
+        self.0.redis().set(&invite).await?;
+
+        Ok(guild_id)
+    }
+}
+
+/// Check whether a message contains an invite to a guild that is not allowed.
+///
+/// Invites to the current guild and to the allowed guilds of the filter are
+/// ignored, as well as invalid invites.
+pub async fn has_forbidden_invite(
+    message: &CachedMessage,
+    guild_id: Id<GuildMarker>,
+    config: &InviteFilter,
+    resolver: &(impl InviteResolver + Sync),
+) -> Result<bool, anyhow::Error> {
+    let codes = message.links.iter().filter_map(|link| match link {
+        MessageLink::Invite(url) => invite_code(url),
+        _ => None,
+    });
+
+    for code in codes {
+        if let Some(invite_guild) = resolver.resolve(code).await? {
+            if invite_guild != guild_id && !config.allowed_guilds.contains(&invite_guild) {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+/// Extract the invite code from an invite URL.
+///
+/// Invite URLs are in the form `discord.gg/<code>` or
+/// `discord.com/invite/<code>`.
+fn invite_code(url: &Url) -> Option<&str> {
+    let mut segments = url.path_segments()?;
+
+    let code = match url.domain()? {
+        "discord.gg" => segments.next()?,
+        _ => match segments.next()? {
+            "invite" => segments.next()?,
+            _ => return None,
+        },
+    };
+
+    (!code.is_empty()).then_some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::event::message::test_message;
+
+    /// Mock [`InviteResolver`] with a fixed list of invites.
+    struct MockResolver(HashMap<&'static str, Id<GuildMarker>>);
+
+    #[async_trait]
+    impl InviteResolver for MockResolver {
+        async fn resolve(&self, code: &str) -> Result<Option<Id<GuildMarker>>, anyhow::Error> {
+            Ok(self.0.get(code).copied())
+        }
+    }
+
+    fn resolver() -> MockResolver {
+        MockResolver(HashMap::from([
+            ("own", Id::new(1)),
+            ("partner", Id::new(2)),
+            ("other", Id::new(3)),
+        ]))
+    }
+
+    fn message(links: &[&str]) -> CachedMessage {
+        CachedMessage {
+            links: links
+                .iter()
+                .map(|link| MessageLink::Invite(Url::parse(link).unwrap()))
+                .collect(),
+            ..test_message()
+        }
+    }
+
+    #[test]
+    fn test_invite_code() {
+        let code = |url: &str| invite_code(&Url::parse(url).unwrap()).map(String::from);
+
+        assert_eq!(
+            code("https://discord.gg/raidprotect"),
+            Some("raidprotect".into())
+        );
+        assert_eq!(
+            code("https://discord.com/invite/raidprotect"),
+            Some("raidprotect".into())
+        );
+        assert_eq!(
+            code("https://discordapp.com/invite/raidprotect"),
+            Some("raidprotect".into())
+        );
+        assert_eq!(code("https://discord.com/channels/1/2"), None);
+        assert_eq!(code("https://discord.gg/"), None);
+    }
+
+    async fn check(links: &[&str]) -> bool {
+        let config = InviteFilter {
+            allowed_guilds: vec![Id::new(2)],
+            ..Default::default()
+        };
+
+        has_forbidden_invite(&message(links), Id::new(1), &config, &resolver())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_forbidden_invite() {
+        assert!(!check(&["https://discord.gg/own"]).await);
+        assert!(!check(&["https://discord.gg/partner"]).await);
+        assert!(!check(&["https://discord.gg/unknown"]).await);
+        assert!(check(&["https://discord.com/invite/other"]).await);
+        assert!(check(&["https://discord.gg/own", "https://discord.gg/other"]).await);
+    }
+}
@@ -0,0 +1,178 @@
+//! Mention spam filter.
+//!
+//! The filter counts the unique users and roles mentioned in each message, as
+//! well as the users mentioned during a sliding window. Attempts to mention
+//! `@everyone`, `@here` or roles by members without the `MENTION_EVERYONE`
+//! permission can also be blocked.
+
+use std::{collections::HashSet, hash::Hash};
+
+use raidprotect_model::{cache::model::message::CachedMessage, mongodb::guild::MentionFilter};
+
+use super::spam::message_window;
+use crate::translations::Lang;
+
+/// Kind of mention spam detected in the messages of a member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MentionSpamKind {
+    /// Too many mentions in a single message.
+    Message,
+    /// Too many users mentioned during the window.
+    Window,
+    /// Attempt to mention everyone or a role without permission.
+    Everyone,
+}
+
+impl MentionSpamKind {
+    /// Get the localized reason of the sanction.
+    pub fn reason(self, lang: Lang) -> &'static str {
+        match self {
+            MentionSpamKind::Message => lang.mentions_reason_message(),
+            MentionSpamKind::Window => lang.mentions_reason_window(),
+            MentionSpamKind::Everyone => lang.mentions_reason_everyone(),
+        }
+    }
+}
+
+/// Check whether a message attempts to mention everyone or a role.
+///
+/// The message content is checked since `mention_everyone` is only set when
+/// the member is allowed to mention everyone.
+pub fn mentions_everyone(message: &CachedMessage) -> bool {
+    message.mention_everyone
+        || !message.mention_roles.is_empty()
+        || message.content.contains("@everyone")
+        || message.content.contains("@here")
+}
+
+/// Detect mention spam in the recent messages of a member.
+///
+/// The `messages` must be sorted from the oldest to the most recent, the last
+/// message being the one that has just been sent. The `can_mention_everyone`
+/// parameter indicates whether the member has the `MENTION_EVERYONE`
+/// permission.
+///
+/// Returns the kind of mention spam detected and the offending messages.
+pub fn detect_mention_spam<'a>(
+    config: &MentionFilter,
+    messages: &'a [CachedMessage],
+    can_mention_everyone: bool,
+) -> Option<(MentionSpamKind, &'a [CachedMessage])> {
+    let last = messages.last()?;
+    let last_slice = &messages[messages.len() - 1..];
+
+    if config.block_everyone && !can_mention_everyone && mentions_everyone(last) {
+        return Some((MentionSpamKind::Everyone, last_slice));
+    }
+
+    let mentions = unique_count(&last.mention_users) + unique_count(&last.mention_roles);
+    if mentions > config.max_mentions as usize {
+        return Some((MentionSpamKind::Message, last_slice));
+    }
+
+    let window = message_window(messages, config.window);
+    let window_mentions = window
+        .iter()
+        .flat_map(|message| &message.mention_users)
+        .collect::<HashSet<_>>()
+        .len();
+
+    if window_mentions > config.max_window_mentions as usize {
+        return Some((MentionSpamKind::Window, window));
+    }
+
+    None
+}
+
+/// Count the unique items of a slice.
+fn unique_count<T: Eq + Hash>(items: &[T]) -> usize {
+    items.iter().collect::<HashSet<_>>().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::{id::Id, util::Timestamp};
+
+    use super::*;
+    use crate::event::message::test_message;
+
+    fn message(secs: i64, users: &[u64], roles: &[u64], content: &str) -> CachedMessage {
+        CachedMessage {
+            content: content.to_string(),
+            timestamp: Timestamp::from_secs(1_628_594_000 + secs).unwrap(),
+            mention_users: users.iter().copied().map(Id::new).collect(),
+            mention_roles: roles.iter().copied().map(Id::new).collect(),
+            ..test_message()
+        }
+    }
+
+    #[test]
+    fn test_no_mention_spam() {
+        let config = MentionFilter::default();
+        let messages = vec![message(0, &[1, 2], &[], "hello"), message(1, &[3], &[], "")];
+
+        assert_eq!(detect_mention_spam(&config, &messages, false), None);
+        assert_eq!(detect_mention_spam(&config, &[], false), None);
+    }
+
+    #[test]
+    fn test_message_mentions() {
+        let config = MentionFilter {
+            max_mentions: 3,
+            ..Default::default()
+        };
+        let messages = vec![message(0, &[1, 2, 3, 3], &[], "")];
+        assert_eq!(detect_mention_spam(&config, &messages, true), None);
+
+        let messages = vec![message(0, &[1, 2, 3], &[4], "")];
+        let (kind, offending) = detect_mention_spam(&config, &messages, true).unwrap();
+        assert_eq!(kind, MentionSpamKind::Message);
+        assert_eq!(offending.len(), 1);
+    }
+
+    #[test]
+    fn test_window_mentions() {
+        let config = MentionFilter {
+            max_window_mentions: 4,
+            window: 10,
+            ..Default::default()
+        };
+        let messages = vec![
+            message(0, &[1, 2, 3], &[], ""),
+            message(20, &[1, 2], &[], ""),
+            message(21, &[3, 4], &[], ""),
+        ];
+        assert_eq!(detect_mention_spam(&config, &messages, false), None);
+
+        let messages = vec![
+            message(0, &[1, 2, 3], &[], ""),
+            message(1, &[4, 5], &[], ""),
+        ];
+        let (kind, offending) = detect_mention_spam(&config, &messages, false).unwrap();
+        assert_eq!(kind, MentionSpamKind::Window);
+        assert_eq!(offending.len(), 2);
+    }
+
+    #[test]
+    fn test_everyone_attempt() {
+        let config = MentionFilter::default();
+        let everyone = vec![message(0, &[], &[], "hey @everyone")];
+        let role = vec![message(0, &[], &[1], "")];
+
+        assert_eq!(
+            detect_mention_spam(&config, &everyone, false).map(|(kind, _)| kind),
+            Some(MentionSpamKind::Everyone)
+        );
+        assert_eq!(
+            detect_mention_spam(&config, &role, false).map(|(kind, _)| kind),
+            Some(MentionSpamKind::Everyone)
+        );
+        assert_eq!(detect_mention_spam(&config, &everyone, true), None);
+
+        let config = MentionFilter {
+            block_everyone: false,
+            ..Default::default()
+        };
+        assert_eq!(detect_mention_spam(&config, &everyone, false), None);
+    }
+}
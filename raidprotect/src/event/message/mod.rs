@@ -3,11 +3,21 @@
 //! This module contain logic used to handle incoming message, such as spam
 //! detection.
 
+mod action;
+mod blocklist;
+mod content;
+mod exemption;
 mod handle;
+mod invite;
+mod mention;
+mod phishing;
+mod spam;
 
 pub mod parser;
 
+pub use blocklist::BlocklistCache;
 pub use handle::handle_message;
+pub use phishing::{DomainList, DomainSource, FileDomainSource};
 use twilight_model::channel::message::MessageType;
 
 /// Messages types processed by the bot.
@@ -16,3 +26,28 @@ pub const ALLOWED_MESSAGES_TYPES: [MessageType; 3] = [
     MessageType::Reply,
     MessageType::ThreadStarterMessage,
 ];
+
+/// Create an empty [`CachedMessage`] for tests.
+///
+/// Tests only set the fields they need with the struct update syntax.
+///
+/// [`CachedMessage`]: raidprotect_model::cache::model::message::CachedMessage
+#[cfg(test)]
+pub fn test_message() -> raidprotect_model::cache::model::message::CachedMessage {
+    use raidprotect_model::cache::model::message::CachedMessage;
+    use twilight_model::{id::Id, util::Timestamp};
+
+    CachedMessage {
+        id: Id::new(1),
+        author_id: Id::new(2),
+        channel_id: Id::new(3),
+        content: String::new(),
+        timestamp: Timestamp::from_secs(1_628_594_197).unwrap(),
+        words: Vec::new(),
+        attachments: Vec::new(),
+        links: Vec::new(),
+        mention_everyone: false,
+        mention_users: Vec::new(),
+        mention_roles: Vec::new(),
+    }
+}
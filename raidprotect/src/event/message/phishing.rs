@@ -0,0 +1,341 @@
+//! Phishing filter.
+//!
+//! The domain of each link sent in messages is checked against two sources:
+//!
+//! - a [`DomainList`] of known malicious domains, loaded from a
+//!   [`DomainSource`] and periodically refreshed (see [`phishing_domains`]).
+//! - a list of popular websites targeted by phishing. Domains that are close
+//!   to one of these websites (such as `dlscord.com`) or that use lookalike
+//!   unicode characters are considered malicious.
+//!
+//! [`phishing_domains`]: crate::task::phishing_domains
+
+use std::{
+    collections::HashSet,
+    iter,
+    path::PathBuf,
+    sync::{PoisonError, RwLock},
+};
+
+use any_ascii::any_ascii;
+use anyhow::Context;
+use async_trait::async_trait;
+use raidprotect_model::cache::model::message::{CachedMessage, MessageLink};
+
+use crate::translations::Lang;
+
+/// Websites targeted by phishing and the maximum edit distance of domains
+/// considered as imitating them.
+const PROTECTED_DOMAINS: [(&str, usize); 4] = [
+    ("discord.com", 1),
+    ("discord.gift", 2),
+    ("discordapp.com", 2),
+    ("steamcommunity.com", 2),
+];
+
+/// Official domains of the protected websites.
+///
+/// These domains and their subdomains are never considered malicious.
+const OFFICIAL_DOMAINS: [&str; 11] = [
+    "discord.com",
+    "discord.gg",
+    "discord.gift",
+    "discord.media",
+    "discord.new",
+    "discordapp.com",
+    "discordapp.net",
+    "discordstatus.com",
+    "steamcommunity.com",
+    "steampowered.com",
+    "steamstatic.com",
+];
+
+/// Kind of malicious link detected in a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhishingKind {
+    /// Link to a known malicious domain.
+    Blocklisted,
+    /// Link to a domain imitating a popular website.
+    Lookalike,
+}
+
+impl PhishingKind {
+    /// Get the localized reason of the sanction.
+    pub fn reason(self, lang: Lang) -> &'static str {
+        match self {
+            PhishingKind::Blocklisted => lang.phishing_reason_blocklist(),
+            PhishingKind::Lookalike => lang.phishing_reason_lookalike(),
+        }
+    }
+}
+
+/// Source of known malicious domains.
+#[async_trait]
+pub trait DomainSource {
+    /// Load the list of malicious domains.
+    async fn load(&self) -> Result<HashSet<String>, anyhow::Error>;
+}
+
+/// [`DomainSource`] reading domains from a local file.
+///
+/// The file contains one domain per line. Empty lines and lines starting with
+/// `#` are ignored.
+#[derive(Debug, Clone)]
+pub struct FileDomainSource(pub PathBuf);
+
+#[async_trait]
+impl DomainSource for FileDomainSource {
+    async fn load(&self) -> Result<HashSet<String>, anyhow::Error> {
+        let content = tokio::fs::read_to_string(&self.0)
+            .await
+            .with_context(|| format!("failed to read {}", self.0.display()))?;
+
+        Ok(parse_domains(&content))
+    }
+}
+
+/// List of known malicious domains.
+#[derive(Debug, Default)]
+pub struct DomainList(RwLock<HashSet<String>>);
+
+impl DomainList {
+    /// Replace the domains of the list with the ones of a [`DomainSource`].
+    ///
+    /// Returns the number of loaded domains. The list is left untouched if
+    /// the source fails to load.
+    pub async fn refresh(
+        &self,
+        source: &(impl DomainSource + Sync),
+    ) -> Result<usize, anyhow::Error> {
+        let domains = source.load().await?;
+        let count = domains.len();
+
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = domains;
+
+        Ok(count)
+    }
+
+    /// Check whether a host or one of its parent domains is in the list.
+    pub fn contains(&self, host: &str) -> bool {
+        let domains = self.0.read().unwrap_or_else(PoisonError::into_inner);
+
+        parent_domains(host).any(|domain| domains.contains(domain))
+    }
+}
+
+/// Detect links to malicious domains in a message.
+///
+/// Invite links are ignored since they can only point to Discord.
+pub fn detect_phishing(message: &CachedMessage, domains: &DomainList) -> Option<PhishingKind> {
+    let hosts = message.links.iter().filter_map(|link| match link {
+        MessageLink::Media(url) | MessageLink::Other(url) => url.domain(),
+        MessageLink::Invite(_) => None,
+    });
+
+    for host in hosts {
+        let host = host.trim_end_matches('.').to_lowercase();
+
+        if domains.contains(&host) {
+            return Some(PhishingKind::Blocklisted);
+        }
+
+        if is_lookalike(&host) {
+            return Some(PhishingKind::Lookalike);
+        }
+    }
+
+    None
+}
+
+/// Check whether a domain imitates one of the [`PROTECTED_DOMAINS`].
+///
+/// Internationalized domains are decoded from punycode and converted to
+/// ASCII, so that unicode lookalike characters are detected. The domain and
+/// each of its parent domains are compared, so that subdomains of a lookalike
+/// domain (such as `login.dlscord.com`) are also detected.
+fn is_lookalike(host: &str) -> bool {
+    if parent_domains(host).any(|domain| OFFICIAL_DOMAINS.contains(&domain)) {
+        return false;
+    }
+
+    let (unicode, _) = idna::domain_to_unicode(host);
+    let skeleton = any_ascii(&unicode).to_lowercase();
+
+    let lookalike = parent_domains(&skeleton).any(|domain| {
+        PROTECTED_DOMAINS.iter().any(|(protected, max_distance)| {
+            domain.starts_with(&format!("{protected}."))
+                || edit_distance(domain, protected) <= *max_distance
+        })
+    });
+
+    lookalike
+}
+
+/// Parse a list of domains.
+fn parse_domains(content: &str) -> HashSet<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Iterate over a domain and its parent domains.
+///
+/// For example, `a.example.com` yields `a.example.com`, `example.com` and
+/// `com`.
+fn parent_domains(host: &str) -> impl Iterator<Item = &str> {
+    iter::once(host).chain(host.match_indices('.').map(|(index, _)| &host[index + 1..]))
+}
+
+/// Compute the edit distance between two strings.
+///
+/// This is the optimal string alignment distance, which counts insertions,
+/// deletions, substitutions and transpositions of adjacent characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::*;
+    use crate::event::message::test_message;
+
+    fn fixture() -> FileDomainSource {
+        FileDomainSource(
+            [
+                env!("CARGO_MANIFEST_DIR"),
+                "fixtures",
+                "phishing_domains.txt",
+            ]
+            .iter()
+            .collect(),
+        )
+    }
+
+    fn message(links: &[&str]) -> CachedMessage {
+        CachedMessage {
+            content: links.join(" "),
+            links: links
+                .iter()
+                .map(|link| MessageLink::Other(Url::parse(link).unwrap()))
+                .collect(),
+            ..test_message()
+        }
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("discord.com", "discord.com"), 0);
+        assert_eq!(edit_distance("dlscord.com", "discord.com"), 1);
+        assert_eq!(edit_distance("discrod.com", "discord.com"), 1);
+        assert_eq!(edit_distance("discorcl.com", "discord.com"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_parent_domains() {
+        assert_eq!(
+            parent_domains("a.example.com").collect::<Vec<_>>(),
+            ["a.example.com", "example.com", "com"]
+        );
+    }
+
+    #[test]
+    fn test_lookalike() {
+        assert!(is_lookalike("dlscord.com"));
+        assert!(is_lookalike("www.discordd.com"));
+        assert!(is_lookalike("steamcomunity.com"));
+        assert!(is_lookalike("discord.com.example.ru"));
+
+        assert!(!is_lookalike("discord.com"));
+        assert!(!is_lookalike("cdn.discordapp.com"));
+        assert!(!is_lookalike("discord.gift"));
+        assert!(!is_lookalike("discogs.com"));
+        assert!(!is_lookalike("example.com"));
+    }
+
+    #[test]
+    fn test_subdomain_lookalike() {
+        assert!(is_lookalike("login.dlscord.com"));
+        assert!(is_lookalike("cdn.discorcl.gift"));
+        assert!(is_lookalike("a.b.steamcomunity.com"));
+
+        assert!(!is_lookalike("media.discordapp.net"));
+        assert!(!is_lookalike("login.example.com"));
+    }
+
+    #[test]
+    fn test_punycode_lookalike() {
+        // Cyrillic "ԁ" instead of latin "d".
+        let url = Url::parse("https://\u{0501}iscord.com").unwrap();
+        let host = url.domain().unwrap();
+
+        assert!(host.starts_with("xn--"));
+        assert!(is_lookalike(host));
+    }
+
+    #[tokio::test]
+    async fn test_file_source() {
+        let domains = fixture().load().await.unwrap();
+
+        assert_eq!(domains.len(), 3);
+        assert!(domains.contains("free-nitro.example"));
+        assert!(!domains.iter().any(|domain| domain.starts_with('#')));
+    }
+
+    #[tokio::test]
+    async fn test_detect_phishing() {
+        let domains = DomainList::default();
+        assert_eq!(domains.refresh(&fixture()).await.unwrap(), 3);
+
+        assert_eq!(
+            detect_phishing(&message(&["https://free-nitro.example/gift"]), &domains),
+            Some(PhishingKind::Blocklisted)
+        );
+        assert_eq!(
+            detect_phishing(&message(&["https://login.steam-trade.example"]), &domains),
+            Some(PhishingKind::Blocklisted)
+        );
+        assert_eq!(
+            detect_phishing(
+                &message(&["https://example.com", "https://dlscord.com/login"]),
+                &domains
+            ),
+            Some(PhishingKind::Lookalike)
+        );
+        assert_eq!(
+            detect_phishing(&message(&["https://discord.com/channels/1/2"]), &domains),
+            None
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Spam detection.
+//!
+//! The anti-spam uses the recent messages of each member stored in the cache
+//! (see [`RedisClient::recent_messages`]). Only the messages sent during the
+//! sliding window configured for the guild are used to detect spam.
+//!
+//! [`RedisClient::recent_messages`]: raidprotect_model::cache::RedisClient::recent_messages
+
+use std::collections::HashMap;
+
+use raidprotect_model::{cache::model::message::CachedMessage, mongodb::guild::AntiSpam};
+
+use crate::translations::Lang;
+
+/// Kind of spam detected in the messages of a member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpamKind {
+    /// Too many messages sent during the window.
+    MessageRate,
+    /// Too many messages with the same content.
+    DuplicateContent,
+    /// Same word repeated too many times.
+    RepeatedWords,
+}
+
+impl SpamKind {
+    /// Get the localized reason of the sanction.
+    pub fn reason(self, lang: Lang) -> &'static str {
+        match self {
+            SpamKind::MessageRate => lang.antispam_reason_rate(),
+            SpamKind::DuplicateContent => lang.antispam_reason_duplicates(),
+            SpamKind::RepeatedWords => lang.antispam_reason_words(),
+        }
+    }
+}
+
+/// Detect spam in the recent messages of a member.
+///
+/// The `messages` must be sorted from the oldest to the most recent, the last
+/// message being the one that has just been sent. Returns the kind of spam
+/// detected and the messages of the window.
+pub fn detect_spam<'a>(
+    config: &AntiSpam,
+    messages: &'a [CachedMessage],
+) -> Option<(SpamKind, &'a [CachedMessage])> {
+    let last = messages.last()?;
+    let window = message_window(messages, config.window);
+
+    if window.len() > config.max_messages as usize {
+        return Some((SpamKind::MessageRate, window));
+    }
+
+    let content = last.content.trim().to_lowercase();
+    let duplicates = window
+        .iter()
+        .filter(|message| message.content.trim().to_lowercase() == content)
+        .count();
+
+    if !content.is_empty() && duplicates > config.max_duplicates as usize {
+        return Some((SpamKind::DuplicateContent, window));
+    }
+
+    let mut words: HashMap<String, usize> = HashMap::new();
+    for word in window.iter().flat_map(|message| &message.words) {
+        *words.entry(word.to_lowercase()).or_default() += 1;
+    }
+
+    if words
+        .values()
+        .any(|count| *count > config.max_repeated_words as usize)
+    {
+        return Some((SpamKind::RepeatedWords, window));
+    }
+
+    None
+}
+
+/// Get the messages sent during the last `window` seconds.
+///
+/// The window ends with the last message. The `messages` must be sorted from
+/// the oldest to the most recent.
+pub fn message_window(messages: &[CachedMessage], window: u32) -> &[CachedMessage] {
+    let last = match messages.last() {
+        Some(last) => last,
+        None => return messages,
+    };
+
+    let window_start = last.timestamp.as_secs() - i64::from(window);
+    let start = messages
+        .iter()
+        .position(|message| message.timestamp.as_secs() >= window_start)
+        .unwrap_or(messages.len() - 1);
+
+    &messages[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::{id::Id, util::Timestamp};
+
+    use super::*;
+    use crate::event::message::test_message;
+
+    fn message(id: u64, secs: i64, content: &str) -> CachedMessage {
+        CachedMessage {
+            id: Id::new(id),
+            content: content.to_string(),
+            timestamp: Timestamp::from_secs(1_628_594_000 + secs).unwrap(),
+            words: content.split_whitespace().map(String::from).collect(),
+            ..test_message()
+        }
+    }
+
+    #[test]
+    fn test_no_spam() {
+        let config = AntiSpam::default();
+        let messages = vec![message(1, 0, "hello"), message(2, 5, "how are you?")];
+
+        assert_eq!(detect_spam(&config, &messages), None);
+        assert_eq!(detect_spam(&config, &[]), None);
+    }
+
+    #[test]
+    fn test_message_rate() {
+        let config = AntiSpam {
+            max_messages: 3,
+            ..Default::default()
+        };
+        let messages: Vec<_> = (1..=4)
+            .map(|id| message(id, id as i64, &format!("message {id}")))
+            .collect();
+
+        let (kind, window) = detect_spam(&config, &messages).unwrap();
+        assert_eq!(kind, SpamKind::MessageRate);
+        assert_eq!(window.len(), 4);
+    }
+
+    #[test]
+    fn test_message_rate_window() {
+        let config = AntiSpam {
+            window: 10,
+            max_messages: 3,
+            ..Default::default()
+        };
+        let messages = vec![
+            message(1, 0, "first"),
+            message(2, 20, "second"),
+            message(3, 21, "third"),
+            message(4, 22, "fourth"),
+        ];
+
+        assert_eq!(detect_spam(&config, &messages), None);
+    }
+
+    #[test]
+    fn test_duplicate_content() {
+        let config = AntiSpam {
+            max_duplicates: 2,
+            ..Default::default()
+        };
+        let messages = vec![
+            message(1, 0, "spam"),
+            message(2, 1, "hello"),
+            message(3, 2, "SPAM"),
+            message(4, 3, "spam "),
+        ];
+
+        let (kind, window) = detect_spam(&config, &messages).unwrap();
+        assert_eq!(kind, SpamKind::DuplicateContent);
+        assert_eq!(window.len(), 4);
+    }
+
+    #[test]
+    fn test_repeated_words() {
+        let config = AntiSpam {
+            max_repeated_words: 5,
+            ..Default::default()
+        };
+        let messages = vec![
+            message(1, 0, "buy buy buy"),
+            message(2, 1, "please buy buy buy"),
+        ];
+
+        let (kind, _) = detect_spam(&config, &messages).unwrap();
+        assert_eq!(kind, SpamKind::RepeatedWords);
+    }
+}
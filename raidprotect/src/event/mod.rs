@@ -5,7 +5,9 @@
 //!
 //! The user-side event handling is done in the `raidprotect_handler` crate.
 
-mod message;
+mod captcha;
+mod member;
+pub mod message;
 mod process;
 
 pub use process::ProcessEvent;
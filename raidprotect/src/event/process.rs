@@ -26,7 +26,7 @@ macro_rules! process_events {
     };
 }
 
-async fn process_cache_event<E: UpdateCache + Debug>(event: E, state: &ClusterState) {
+async fn process_cache_event<E: UpdateCache + Debug>(event: &E, state: &ClusterState) {
     if let Err(error) = event.update(state.redis(), state.current_user()).await {
         error!(error = ?error, kind = E::NAME, "failed to update cache");
         debug!(event = ?event);
@@ -39,7 +39,7 @@ macro_rules! process_cache_events {
             #[async_trait]
             impl ProcessEvent for incoming::$event {
                 async fn process(self, state: Arc<ClusterState>) {
-                    process_cache_event(self, &state).await;
+                    process_cache_event(&self, &state).await;
                 }
             }
         )+
@@ -68,7 +68,9 @@ impl ProcessEvent for GatewayEvent {
             RoleDelete,
             MemberAdd,
             MemberUpdate,
-            MessageCreate
+            MessageCreate,
+            MessageDelete,
+            MessageDeleteBulk
         }
     }
 }
@@ -76,21 +78,52 @@ impl ProcessEvent for GatewayEvent {
 // Implementation of events only processed in cache
 process_cache_events! {
     GuildCreate,
-    GuildDelete,
     UnavailableGuild,
     GuildUpdate,
     ChannelCreate,
-    ChannelDelete,
     ChannelUpdate,
     ThreadCreate,
     ThreadDelete,
     ThreadUpdate,
     RoleCreate,
-    RoleDelete,
-    MemberAdd,
     MemberUpdate
 }
 
+#[async_trait]
+impl ProcessEvent for incoming::GuildDelete {
+    async fn process(self, state: Arc<ClusterState>) {
+        state.blocklists().invalidate(self.id);
+        process_cache_event(&self, &state).await;
+    }
+}
+
+#[async_trait]
+impl ProcessEvent for incoming::ChannelDelete {
+    async fn process(self, state: Arc<ClusterState>) {
+        process_cache_event(&self, &state).await;
+
+        if let Some(guild_id) = self.guild_id {
+            super::captcha::handle_channel_delete(guild_id, self.id, state).await;
+        }
+    }
+}
+
+#[async_trait]
+impl ProcessEvent for incoming::RoleDelete {
+    async fn process(self, state: Arc<ClusterState>) {
+        process_cache_event(&self, &state).await;
+        super::captcha::handle_role_delete(self.guild_id, self.role_id, state).await;
+    }
+}
+
+#[async_trait]
+impl ProcessEvent for incoming::MemberAdd {
+    async fn process(self, state: Arc<ClusterState>) {
+        process_cache_event(&self, &state).await;
+        super::member::handle_member_add(self.0, state).await;
+    }
+}
+
 #[async_trait]
 impl ProcessEvent for incoming::InteractionCreate {
     async fn process(self, state: Arc<ClusterState>) {
@@ -106,3 +139,21 @@ impl ProcessEvent for incoming::MessageCreate {
         }
     }
 }
+
+#[async_trait]
+impl ProcessEvent for incoming::MessageDelete {
+    async fn process(self, state: Arc<ClusterState>) {
+        if let Some(guild_id) = self.guild_id {
+            super::captcha::handle_message_delete(guild_id, &[self.id], state).await;
+        }
+    }
+}
+
+#[async_trait]
+impl ProcessEvent for incoming::MessageDeleteBulk {
+    async fn process(self, state: Arc<ClusterState>) {
+        if let Some(guild_id) = self.guild_id {
+            super::captcha::handle_message_delete(guild_id, &self.ids, state).await;
+        }
+    }
+}
@@ -0,0 +1,473 @@
+//! Captcha command.
+//!
+//! The `captcha enable` subcommand sets up and enables the captcha
+//! verification of new members:
+//!
+//! - the unverified role is created, or reused if it already exists.
+//! - the verification channel is created, or reused if it already exists. It
+//!   is only visible to unverified members.
+//! - unverified members are denied access to all the other text, voice and
+//!   stage channels and categories.
+//! - the verification message is sent in the verification channel.
+//!
+//! The role and channel ids are then stored in the guild configuration, along
+//! with the difficulty of the captcha images if specified.
+//!
+//! The position of a reused role and the permissions of the bot in a reused
+//! channel are checked before any change is made. Channels whose permissions
+//! cannot be updated are reported without interrupting the setup. Since the
+//! setup may take a while, the response to this command is deferred.
+
+use anyhow::Context;
+use raidprotect_model::{
+    cache::{
+        model::{CachedChannel, CachedGuild, CachedRole},
+        permission::CachePermissions,
+        UpdateCache,
+    },
+    mongodb::guild::{CaptchaDifficulty, CaptchaUpdate, Guild},
+};
+use tracing::warn;
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
+use twilight_model::{
+    application::interaction::Interaction,
+    channel::{
+        permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        ChannelType,
+    },
+    gateway::payload::incoming::{ChannelCreate, RoleCreate},
+    guild::Permissions,
+    http::permission_overwrite::{
+        PermissionOverwrite as HttpPermissionOverwrite,
+        PermissionOverwriteType as HttpPermissionOverwriteType,
+    },
+    id::{
+        marker::{ChannelMarker, GuildMarker, RoleMarker, UserMarker},
+        Id,
+    },
+};
+
+use crate::{
+    cluster::ClusterState,
+    desc_localizations, impl_command_handle,
+    interaction::{
+        component::CaptchaVerify, embed, response::InteractionResponse, util::InteractionExt,
+    },
+    translations::Lang,
+};
+
+/// Name of the unverified role.
+const DEFAULT_ROLE_NAME: &str = "Unverified";
+
+/// Name of the verification channel.
+const DEFAULT_CHANNEL_NAME: &str = "verification";
+
+/// Permissions of unverified members in the verification channel.
+const VERIFICATION_PERMISSIONS: Permissions =
+    Permissions::VIEW_CHANNEL.union(Permissions::READ_MESSAGE_HISTORY);
+
+/// Permissions of the bot in the verification channel.
+const BOT_PERMISSIONS: Permissions = Permissions::VIEW_CHANNEL
+    .union(Permissions::SEND_MESSAGES)
+    .union(Permissions::EMBED_LINKS)
+    .union(Permissions::READ_MESSAGE_HISTORY);
+
+/// Permissions required by the bot to update the verification channel.
+///
+/// Discord only allows the bot to allow or deny permissions it has in the
+/// channel.
+const VERIFICATION_REQUIRED_PERMISSIONS: Permissions = BOT_PERMISSIONS
+    .union(Permissions::ADD_REACTIONS)
+    .union(Permissions::MANAGE_ROLES);
+
+/// Permissions required by the bot to deny unverified members access to a
+/// channel.
+const RESTRICT_REQUIRED_PERMISSIONS: Permissions =
+    Permissions::VIEW_CHANNEL.union(Permissions::MANAGE_ROLES);
+
+/// Captcha command model.
+///
+/// See the [`module`][self] documentation for more information.
+#[derive(Debug, Clone, CommandModel, CreateCommand)]
+#[command(
+    name = "captcha",
+    desc = "Configure the captcha verification of new members",
+    desc_localizations = "captcha_description",
+    default_permissions = "CaptchaCommand::default_permissions",
+    dm_permission = false
+)]
+pub enum CaptchaCommand {
+    #[command(name = "enable")]
+    Enable(CaptchaEnableCommand),
+}
+
+impl_command_handle!(CaptchaCommand);
+desc_localizations!(captcha_description);
+
+impl CaptchaCommand {
+    fn default_permissions() -> Permissions {
+        Permissions::MANAGE_GUILD
+    }
+
+    async fn exec(
+        self,
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        match self {
+            Self::Enable(command) => command.exec(interaction, state).await,
+        }
+    }
+}
+
+/// Captcha enable subcommand model.
+#[derive(Debug, Clone, CommandModel, CreateCommand)]
+#[command(
+    name = "enable",
+    desc = "Set up and enable the captcha verification",
+    desc_localizations = "captcha_enable_description"
+)]
+pub struct CaptchaEnableCommand {
+    /// Difficulty of the captcha images, defaults to the current difficulty.
+    pub difficulty: Option<CaptchaDifficultyOption>,
+}
+
+desc_localizations!(captcha_enable_description);
+
+/// Difficulty of the captcha images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CommandOption, CreateOption)]
+pub enum CaptchaDifficultyOption {
+    #[option(name = "Easy", value = "easy")]
+    Easy,
+    #[option(name = "Medium", value = "medium")]
+    Medium,
+    #[option(name = "Hard", value = "hard")]
+    Hard,
+}
+
+impl From<CaptchaDifficultyOption> for CaptchaDifficulty {
+    fn from(option: CaptchaDifficultyOption) -> Self {
+        match option {
+            CaptchaDifficultyOption::Easy => CaptchaDifficulty::Easy,
+            CaptchaDifficultyOption::Medium => CaptchaDifficulty::Medium,
+            CaptchaDifficultyOption::Hard => CaptchaDifficulty::Hard,
+        }
+    }
+}
+
+impl CaptchaEnableCommand {
+    async fn exec(
+        self,
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let guild = interaction.guild()?;
+        let author_id = interaction.author_id().context("missing author_id")?;
+        let lang = interaction.locale()?;
+
+        let permissions = state.redis().permissions(guild.id).await?;
+        let author_permissions = permissions.member(author_id, &guild.member.roles).await?;
+        let bot_permissions = permissions.current_member().await?;
+
+        if !author_permissions
+            .guild()
+            .contains(Permissions::MANAGE_GUILD)
+        {
+            return Ok(embed::captcha::missing_permission(lang));
+        }
+
+        if !bot_permissions
+            .guild()
+            .contains(Permissions::MANAGE_ROLES | Permissions::MANAGE_CHANNELS)
+        {
+            return Ok(embed::captcha::bot_missing_permission(lang));
+        }
+
+        let config = state.mongodb().get_guild_or_create(guild.id).await?;
+        let guild_lang = Lang::from(&*config.lang);
+
+        // Check the reused role and channel before making any change.
+        let existing_role = find_role(guild.id, &config, state).await?;
+        if let Some(role) = &existing_role {
+            if role.as_ordering() >= bot_permissions.highest_role() {
+                return Ok(embed::captcha::role_hierarchy(role.id, lang));
+            }
+        }
+
+        let existing_channel = find_channel(guild.id, &config, state).await?;
+        if let Some(channel) = existing_channel {
+            let (channel_permissions, _) = bot_permissions.channel(channel).await?;
+
+            if !channel_permissions.contains(VERIFICATION_REQUIRED_PERMISSIONS) {
+                return Ok(embed::captcha::channel_missing_permission(channel, lang));
+            }
+        }
+
+        let role = match existing_role {
+            Some(role) => role.id,
+            None => create_role(guild.id, state).await?,
+        };
+        let channel = setup_channel(guild.id, role, existing_channel, state).await?;
+        let failed = restrict_channels(guild.id, role, channel, &bot_permissions, state).await?;
+
+        let mut update = CaptchaUpdate {
+            enabled: Some(true),
+            channel: Some(Some(channel)),
+            role: Some(Some(role)),
+            difficulty: self.difficulty.map(Into::into),
+            ..Default::default()
+        };
+
+        // A new verification message is sent if the channel has changed.
+        if config.captcha.channel != Some(channel) || config.captcha.message.is_none() {
+            let message = CaptchaVerify::send_message(guild.id, channel, state, guild_lang).await?;
+            update.message = Some(Some(message));
+        }
+
+        state.mongodb().update_captcha(guild.id, &update).await?;
+
+        Ok(embed::captcha::enabled(channel, role, &failed, lang))
+    }
+}
+
+/// Find an existing unverified role.
+///
+/// The configured role is reused if it still exists, otherwise a role named
+/// [`DEFAULT_ROLE_NAME`] is reused.
+async fn find_role(
+    guild_id: Id<GuildMarker>,
+    config: &Guild,
+    state: &ClusterState,
+) -> Result<Option<CachedRole>, anyhow::Error> {
+    if let Some(role) = config.captcha.role {
+        if let Some(role) = state.redis().get::<CachedRole>(&role).await? {
+            return Ok(Some(role));
+        }
+    }
+
+    let roles = state.redis().guild_roles(guild_id).await?;
+
+    Ok(roles
+        .into_iter()
+        .find(|role| role.name == DEFAULT_ROLE_NAME))
+}
+
+/// Create the unverified role.
+async fn create_role(
+    guild_id: Id<GuildMarker>,
+    state: &ClusterState,
+) -> Result<Id<RoleMarker>, anyhow::Error> {
+    let role = state
+        .cache_http(guild_id)
+        .create_role()
+        .await?
+        .name(DEFAULT_ROLE_NAME)
+        .permissions(Permissions::empty())
+        .exec()
+        .await?
+        .model()
+        .await?;
+    let role_id = role.id;
+
+    // The role is cached immediately to be available before the gateway
+    // event is received.
+    RoleCreate { guild_id, role }
+        .update(state.redis(), state.current_user())
+        .await?;
+
+    Ok(role_id)
+}
+
+/// Find an existing verification channel.
+///
+/// The configured channel is reused if it still exists, otherwise a channel
+/// named [`DEFAULT_CHANNEL_NAME`] is reused.
+async fn find_channel(
+    guild_id: Id<GuildMarker>,
+    config: &Guild,
+    state: &ClusterState,
+) -> Result<Option<Id<ChannelMarker>>, anyhow::Error> {
+    let channels = state.redis().guild_channels(guild_id).await?;
+
+    let existing = channels
+        .iter()
+        .filter_map(|channel| match channel {
+            CachedChannel::Text(channel) => Some(channel),
+            _ => None,
+        })
+        .find(|channel| {
+            Some(channel.id) == config.captcha.channel || channel.name == DEFAULT_CHANNEL_NAME
+        });
+
+    Ok(existing.map(|channel| channel.id))
+}
+
+/// Set up the verification channel.
+///
+/// The permissions of the `existing` channel are updated, otherwise a channel
+/// named [`DEFAULT_CHANNEL_NAME`] is created.
+async fn setup_channel(
+    guild_id: Id<GuildMarker>,
+    role: Id<RoleMarker>,
+    existing: Option<Id<ChannelMarker>>,
+    state: &ClusterState,
+) -> Result<Id<ChannelMarker>, anyhow::Error> {
+    let bot_id = state
+        .redis()
+        .get::<CachedGuild>(&guild_id)
+        .await?
+        .and_then(|guild| guild.current_member)
+        .context("missing bot member in cache")?
+        .id;
+
+    if let Some(channel) = existing {
+        let http = state.cache_http(guild_id);
+
+        for overwrite in verification_overwrites(guild_id, role, bot_id) {
+            let overwrite = HttpPermissionOverwrite {
+                allow: Some(overwrite.allow),
+                deny: Some(overwrite.deny),
+                id: overwrite.id,
+                kind: match overwrite.kind {
+                    PermissionOverwriteType::Member => HttpPermissionOverwriteType::Member,
+                    _ => HttpPermissionOverwriteType::Role,
+                },
+            };
+
+            http.update_channel_permission(channel, &overwrite)
+                .await?
+                .exec()
+                .await?;
+        }
+
+        return Ok(channel);
+    }
+
+    let channel = state
+        .cache_http(guild_id)
+        .create_guild_channel(DEFAULT_CHANNEL_NAME)
+        .await?
+        .kind(ChannelType::GuildText)
+        .permission_overwrites(&verification_overwrites(guild_id, role, bot_id))
+        .exec()
+        .await?
+        .model()
+        .await?;
+    let channel_id = channel.id;
+
+    // The channel is cached immediately so that the verification message can
+    // be sent before the gateway event is received.
+    ChannelCreate(channel)
+        .update(state.redis(), state.current_user())
+        .await?;
+
+    Ok(channel_id)
+}
+
+/// Deny unverified members access to all the channels except the
+/// verification channel.
+///
+/// Returns the channels whose permissions could not be updated, including
+/// channels where the bot is missing permissions.
+async fn restrict_channels(
+    guild_id: Id<GuildMarker>,
+    role: Id<RoleMarker>,
+    verification_channel: Id<ChannelMarker>,
+    bot_permissions: &CachePermissions<'_>,
+    state: &ClusterState,
+) -> Result<Vec<Id<ChannelMarker>>, anyhow::Error> {
+    let http = state.cache_http(guild_id);
+    let overwrite = HttpPermissionOverwrite {
+        allow: Some(Permissions::empty()),
+        deny: Some(Permissions::VIEW_CHANNEL),
+        id: role.cast(),
+        kind: HttpPermissionOverwriteType::Role,
+    };
+
+    let mut failed = Vec::new();
+
+    for channel in state.redis().guild_channels(guild_id).await? {
+        let id = match channel {
+            CachedChannel::Text(channel) if channel.id != verification_channel => channel.id,
+            CachedChannel::Category(category) => category.id,
+            CachedChannel::Voice(channel) => channel.id,
+            _ => continue,
+        };
+
+        let (channel_permissions, _) = bot_permissions.channel(id).await?;
+        if !channel_permissions.contains(RESTRICT_REQUIRED_PERMISSIONS) {
+            failed.push(id);
+            continue;
+        }
+
+        let result = async {
+            http.update_channel_permission(id, &overwrite)
+                .await?
+                .exec()
+                .await?;
+
+            Ok::<_, anyhow::Error>(())
+        }
+        .await;
+
+        if let Err(error) = result {
+            warn!(error = ?error, "failed to restrict channel {}", id);
+            failed.push(id);
+        }
+    }
+
+    Ok(failed)
+}
+
+/// Permission overwrites of the verification channel.
+///
+/// The channel is only visible to unverified members and the bot.
+fn verification_overwrites(
+    guild_id: Id<GuildMarker>,
+    role: Id<RoleMarker>,
+    bot_id: Id<UserMarker>,
+) -> [PermissionOverwrite; 3] {
+    [
+        PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            id: guild_id.cast(),
+            kind: PermissionOverwriteType::Role,
+        },
+        PermissionOverwrite {
+            allow: VERIFICATION_PERMISSIONS,
+            deny: Permissions::SEND_MESSAGES | Permissions::ADD_REACTIONS,
+            id: role.cast(),
+            kind: PermissionOverwriteType::Role,
+        },
+        PermissionOverwrite {
+            allow: BOT_PERMISSIONS,
+            deny: Permissions::empty(),
+            id: bot_id.cast(),
+            kind: PermissionOverwriteType::Member,
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verification_overwrites() {
+        let overwrites = verification_overwrites(Id::new(1), Id::new(2), Id::new(3));
+
+        assert_eq!(overwrites[0].id, Id::new(1));
+        assert_eq!(overwrites[0].deny, Permissions::VIEW_CHANNEL);
+        assert!(overwrites[1].allow.contains(Permissions::VIEW_CHANNEL));
+        assert!(overwrites[1].deny.contains(Permissions::SEND_MESSAGES));
+        assert_eq!(overwrites[2].kind, PermissionOverwriteType::Member);
+    }
+
+    #[test]
+    fn test_difficulty_option() {
+        let difficulty = CaptchaDifficulty::from(CaptchaDifficultyOption::Hard);
+
+        assert_eq!(difficulty, CaptchaDifficulty::Hard);
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! This module contains implementations of the bot slash commands.
 
+pub mod captcha;
 pub mod help;
 pub mod moderation;
 pub mod profile;
@@ -0,0 +1,117 @@
+//! Ban command.
+//!
+//! The command allows to ban a user from the server. Unlike the kick command,
+//! the user does not need to be a member of the server. User can specify a
+//! reason directly in the command (as an optional parameter), or in the modal
+//! that is shown if it hasn't been set in the command.
+//!
+//! The ban can be temporary if a duration is provided, in which case the ban
+//! is automatically lifted once expired (see the [`task`] module). Temporary
+//! bans cannot exceed one year. The messages sent by the banned user in the
+//! last days can also be deleted.
+//!
+//! When a user is banned, the action is logged in the database and a message
+//! is sent in the guild's logs channel. The banned user receives a pm with the
+//! reason of the ban.
+//!
+//! [`task`]: crate::task
+
+use anyhow::Context;
+use raidprotect_model::mongodb::modlog::SanctionKind;
+use time::Duration;
+use twilight_interactions::command::{CommandModel, CreateCommand, ResolvedUser};
+use twilight_model::{application::interaction::Interaction, guild::Permissions};
+
+use super::Sanction;
+use crate::{
+    cluster::ClusterState,
+    desc_localizations, impl_command_handle,
+    interaction::{embed, response::InteractionResponse, util::InteractionExt},
+    util::parse_duration,
+};
+
+/// Maximum duration of a temporary ban.
+pub const MAX_BAN_DURATION: Duration = Duration::days(365);
+
+/// Ban command model.
+///
+/// See the [`module`][self] documentation for more information.
+#[derive(Debug, Clone, CommandModel, CreateCommand)]
+#[command(
+    name = "ban",
+    desc = "Bans a user from the server",
+    desc_localizations = "ban_description",
+    default_permissions = "BanCommand::default_permissions",
+    dm_permission = false
+)]
+pub struct BanCommand {
+    /// User to ban.
+    pub user: ResolvedUser,
+    /// Duration of the ban (e.g. 1d, 12h). Permanent if not set.
+    pub duration: Option<String>,
+    /// Number of days of messages to delete.
+    #[command(min_value = 0, max_value = 7)]
+    pub delete_messages: Option<i64>,
+    /// Reason for ban.
+    #[command(max_length = 100)]
+    pub reason: Option<String>,
+}
+
+impl_command_handle!(BanCommand);
+desc_localizations!(ban_description);
+
+impl BanCommand {
+    fn default_permissions() -> Permissions {
+        Permissions::BAN_MEMBERS
+    }
+
+    async fn exec(
+        self,
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let guild = interaction.guild()?;
+        let author_id = interaction.author_id().context("missing author_id")?;
+        let lang = interaction.locale()?;
+
+        let duration = match self.duration.as_deref().map(parse_duration) {
+            Some(Some(duration)) => Some(duration),
+            Some(None) => return Ok(embed::sanction::invalid_duration(lang)),
+            None => None,
+        };
+
+        if duration.is_some_and(|duration| duration > MAX_BAN_DURATION) {
+            return Ok(embed::sanction::ban_duration_too_long(lang));
+        }
+
+        let sanction = Sanction {
+            reason: self.reason,
+            duration,
+            delete_message_days: self.delete_messages.map(|days| days as u16),
+            ..Sanction::new(SanctionKind::Ban, self.user.resolved)
+        };
+
+        // The role hierarchy is only checked if the user is a server member.
+        let member_roles = self.user.member.as_ref().map(|member| &*member.roles);
+
+        if let Some(response) = sanction
+            .check_permissions(&guild, author_id, member_roles, state, lang)
+            .await?
+        {
+            return Ok(response);
+        }
+
+        // Ban the user directly if a reason is provided, otherwise show the
+        // reason modal.
+        let config = state.mongodb().get_guild_or_create(guild.id).await?;
+
+        match sanction.reason {
+            Some(_) => sanction.apply(&guild, &config, state, lang).await,
+            None => {
+                let enforce_reason = config.moderation.enforce_reason;
+
+                sanction.reason_modal(enforce_reason, state, lang).await
+            }
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! Case command.
+//!
+//! The `case edit` subcommand allows moderators to edit the reason and notes
+//! of an existing modlog, identified by its case number. A modal prefilled
+//! with the current values is shown to the moderator.
+//!
+//! The previous values are kept in the edit history of the modlog, and the
+//! message sent in the logs channel when the sanction was issued is updated.
+
+use anyhow::Context;
+use nanoid::nanoid;
+use raidprotect_model::{
+    cache::model::interaction::{PendingCaseEdit, PendingModal},
+    mongodb::modlog::{ModlogEdit, ModlogUser},
+};
+use time::OffsetDateTime;
+use tracing::debug;
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::application::{
+    component::{text_input::TextInputStyle, ActionRow, Component, TextInput},
+    interaction::Interaction,
+};
+
+use super::{modlogs::is_moderator, sanction::MAX_REASON_LENGTH};
+use crate::{
+    cluster::ClusterState,
+    desc_localizations, impl_command_handle,
+    interaction::{
+        embed,
+        response::InteractionResponse,
+        util::{parse_modal_data, parse_modal_field, InteractionExt},
+    },
+    translations::Lang,
+};
+
+/// Case command model.
+///
+/// See the [`module`][self] documentation for more information.
+#[derive(Debug, Clone, CommandModel, CreateCommand)]
+#[command(
+    name = "case",
+    desc = "Manage moderation cases",
+    desc_localizations = "case_description",
+    dm_permission = false
+)]
+pub enum CaseCommand {
+    #[command(name = "edit")]
+    Edit(CaseEditCommand),
+}
+
+impl_command_handle!(CaseCommand);
+desc_localizations!(case_description);
+
+impl CaseCommand {
+    async fn exec(
+        self,
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        match self {
+            Self::Edit(command) => command.exec(interaction, state).await,
+        }
+    }
+
+    /// Handle the submission of the case edit modal.
+    pub async fn handle_modal(
+        mut interaction: Interaction,
+        pending: PendingCaseEdit,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let data = parse_modal_data(&mut interaction)?;
+        let guild = interaction.guild()?;
+        let author_id = interaction.author_id().context("missing author_id")?;
+        let lang = interaction.locale()?;
+
+        // The moderator roles could have changed since the modal has been
+        // shown.
+        let config = state.mongodb().get_guild_or_create(guild.id).await?;
+
+        if !is_moderator(&guild, author_id, &config, state).await? {
+            return Ok(embed::modlogs::missing_permission(lang));
+        }
+
+        let mut modlog = match state
+            .mongodb()
+            .get_modlog_by_case(guild.id, pending.case)
+            .await?
+        {
+            Some(modlog) => modlog,
+            None => return Ok(embed::case::not_found(pending.case, lang)),
+        };
+
+        let editor = guild
+            .member
+            .user
+            .as_ref()
+            .context("missing interaction member user")?;
+        let reason = parse_modal_field(&data, "reason").map(String::from);
+        let notes = parse_modal_field(&data, "notes").map(String::from);
+
+        // Update the modlog and keep the previous values in the edit history.
+        let edit = ModlogEdit {
+            editor: ModlogUser::from(editor),
+            date: OffsetDateTime::now_utc(),
+            reason: modlog.reason.clone(),
+            notes: modlog.notes.clone(),
+        };
+        let id = modlog.id.context("missing modlog id")?;
+
+        state
+            .mongodb()
+            .edit_modlog(id, reason.as_deref(), notes.as_deref(), &edit)
+            .await?;
+
+        modlog.reason = reason;
+        modlog.notes = notes;
+        modlog.edits.push(edit);
+
+        // Edit the message sent in the logs channel. The message may have been
+        // deleted, so errors are ignored.
+        if let Some(logs_message) = modlog.logs_message {
+            let guild_lang = Lang::from(&*config.lang);
+            let logs_embed = embed::sanction::logs_message(&modlog, guild_lang);
+
+            if let Err(error) = state
+                .http()
+                .update_message(logs_message.channel_id, logs_message.message_id)
+                .embeds(Some(&[logs_embed]))?
+                .exec()
+                .await
+            {
+                debug!(error = ?error, "failed to edit logs message of case {}", pending.case);
+            }
+        }
+
+        Ok(embed::case::edit_success(pending.case, lang))
+    }
+}
+
+/// Case edit subcommand model.
+#[derive(Debug, Clone, CommandModel, CreateCommand)]
+#[command(
+    name = "edit",
+    desc = "Edit the reason and notes of a case",
+    desc_localizations = "case_edit_description"
+)]
+pub struct CaseEditCommand {
+    /// Number of the case to edit.
+    #[command(min_value = 1)]
+    pub case: i64,
+}
+
+desc_localizations!(case_edit_description);
+
+impl CaseEditCommand {
+    async fn exec(
+        self,
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let guild = interaction.guild()?;
+        let author_id = interaction.author_id().context("missing author_id")?;
+        let lang = interaction.locale()?;
+        let case = self.case as u64;
+
+        // Check if the author is allowed to edit the modlogs.
+        let config = state.mongodb().get_guild_or_create(guild.id).await?;
+
+        if !is_moderator(&guild, author_id, &config, state).await? {
+            return Ok(embed::modlogs::missing_permission(lang));
+        }
+
+        let modlog = match state.mongodb().get_modlog_by_case(guild.id, case).await? {
+            Some(modlog) => modlog,
+            None => return Ok(embed::case::not_found(case, lang)),
+        };
+
+        let components = edit_components(
+            modlog.reason,
+            modlog.notes,
+            config.moderation.enforce_reason,
+            lang,
+        );
+
+        // Add pending component in Redis
+        let custom_id = nanoid!();
+        let pending = PendingModal::CaseEdit(PendingCaseEdit {
+            id: custom_id.clone(),
+            case,
+        });
+
+        state.redis().set(&pending).await?;
+
+        Ok(InteractionResponse::Modal {
+            custom_id,
+            title: lang.modal_case_edit_title(case),
+            components,
+        })
+    }
+}
+
+/// Components of the case edit modal, prefilled with the current values.
+///
+/// Reasons longer than [`MAX_REASON_LENGTH`] (such as reasons set before the
+/// limit was enforced) are truncated, since Discord rejects modals whose
+/// values exceed their maximum length.
+fn edit_components(
+    reason: Option<String>,
+    notes: Option<String>,
+    enforce_reason: bool,
+    lang: Lang,
+) -> Vec<Component> {
+    let reason = reason.map(|reason| reason.chars().take(MAX_REASON_LENGTH).collect());
+
+    vec![
+        Component::ActionRow(ActionRow {
+            components: vec![Component::TextInput(TextInput {
+                custom_id: "reason".to_string(),
+                label: lang.modal_case_reason_label().to_string(),
+                max_length: Some(MAX_REASON_LENGTH as u16),
+                min_length: None,
+                placeholder: Some(lang.modal_reason_placeholder().to_string()),
+                required: Some(enforce_reason),
+                style: TextInputStyle::Short,
+                value: reason,
+            })],
+        }),
+        Component::ActionRow(ActionRow {
+            components: vec![Component::TextInput(TextInput {
+                custom_id: "notes".to_string(),
+                label: lang.modal_notes_label().to_string(),
+                max_length: Some(1000),
+                min_length: None,
+                placeholder: Some(lang.modal_notes_placeholder().to_string()),
+                required: Some(false),
+                style: TextInputStyle::Paragraph,
+                value: notes,
+            })],
+        }),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use rosetta_i18n::Language;
+
+    use super::*;
+
+    #[test]
+    fn test_edit_components_long_reason() {
+        let reason = "é".repeat(500);
+        let components = edit_components(Some(reason), None, false, Lang::fallback());
+
+        let input = match &components[0] {
+            Component::ActionRow(row) => match &row.components[0] {
+                Component::TextInput(input) => input,
+                other => panic!("expected text input, got {other:?}"),
+            },
+            other => panic!("expected action row, got {other:?}"),
+        };
+        let length = input.value.as_ref().map(|value| value.chars().count());
+
+        assert_eq!(length, Some(MAX_REASON_LENGTH));
+        assert_eq!(input.max_length, Some(MAX_REASON_LENGTH as u16));
+    }
+}
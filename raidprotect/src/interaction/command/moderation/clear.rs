@@ -0,0 +1,339 @@
+//! Clear command.
+//!
+//! The command allows to bulk delete recent messages in the channel where it
+//! is used. Messages can be filtered by author, links, invites, attachments or
+//! bots. Discord does not allow to bulk delete messages older than 14 days, so
+//! older messages are ignored.
+//!
+//! A summary of the deleted messages is sent in the guild's logs channel.
+//!
+//! Fetching and deleting the messages may take a few seconds, so the response
+//! to this command is deferred.
+
+use anyhow::Context;
+use raidprotect_model::cache::model::message::{CachedMessage, MessageLink};
+use time::{Duration, OffsetDateTime};
+use twilight_interactions::command::{CommandModel, CreateCommand, ResolvedUser};
+use twilight_model::{
+    application::interaction::Interaction,
+    channel::Message,
+    guild::Permissions,
+    id::{
+        marker::{ChannelMarker, GuildMarker, MessageMarker, UserMarker},
+        Id,
+    },
+};
+
+use crate::{
+    cluster::ClusterState,
+    desc_localizations,
+    event::message::{parser::parse_message, ALLOWED_MESSAGES_TYPES},
+    impl_command_handle,
+    interaction::{embed, response::InteractionResponse, util::InteractionExt},
+    translations::Lang,
+    util::guild_logs_channel,
+};
+
+/// Maximum age of messages that can be bulk deleted.
+///
+/// Discord allows 14 days, a small margin is kept to avoid failures if the
+/// request takes time to be processed.
+const MAX_MESSAGE_AGE: Duration = Duration::minutes(14 * 24 * 60 - 5);
+
+/// Maximum number of messages fetched to find messages matching the filters.
+const MAX_FETCHED_MESSAGES: usize = 500;
+
+/// Clear command model.
+///
+/// See the [`module`][self] documentation for more information.
+#[derive(Debug, Clone, CommandModel, CreateCommand)]
+#[command(
+    name = "clear",
+    desc = "Deletes recent messages in the channel",
+    desc_localizations = "clear_description",
+    default_permissions = "ClearCommand::default_permissions",
+    dm_permission = false
+)]
+pub struct ClearCommand {
+    /// Number of messages to delete.
+    #[command(min_value = 1, max_value = 100)]
+    pub count: i64,
+    /// Only delete messages sent by this user.
+    pub user: Option<ResolvedUser>,
+    /// Only delete messages containing links.
+    pub links: Option<bool>,
+    /// Only delete messages containing invites.
+    pub invites: Option<bool>,
+    /// Only delete messages with attachments.
+    pub attachments: Option<bool>,
+    /// Only delete messages sent by bots.
+    pub bots: Option<bool>,
+}
+
+impl_command_handle!(ClearCommand);
+desc_localizations!(clear_description);
+
+impl ClearCommand {
+    fn default_permissions() -> Permissions {
+        Permissions::MANAGE_MESSAGES
+    }
+
+    async fn exec(
+        self,
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let guild = interaction.guild()?;
+        let author_id = interaction.author_id().context("missing author_id")?;
+        let channel_id = interaction.channel_id.context("missing channel_id")?;
+        let lang = interaction.locale()?;
+
+        // Check that the author and the bot can delete messages in the channel.
+        let permissions = state.redis().permissions(guild.id).await?;
+        let (author_permissions, _) = permissions
+            .member(author_id, &guild.member.roles)
+            .await?
+            .channel(channel_id)
+            .await?;
+        let (bot_permissions, _) = permissions
+            .current_member()
+            .await?
+            .channel(channel_id)
+            .await?;
+
+        if !author_permissions.contains(Permissions::MANAGE_MESSAGES) {
+            return Ok(embed::clear::missing_permission(lang));
+        }
+
+        if !bot_permissions.contains(Permissions::MANAGE_MESSAGES) {
+            return Ok(embed::clear::bot_missing_permission(lang));
+        }
+
+        let filter = ClearFilter {
+            user: self.user.as_ref().map(|user| user.resolved.id),
+            links: self.links.unwrap_or(false),
+            invites: self.invites.unwrap_or(false),
+            attachments: self.attachments.unwrap_or(false),
+            bots: self.bots.unwrap_or(false),
+        };
+
+        let messages =
+            find_messages(guild.id, channel_id, &filter, self.count as usize, state).await?;
+
+        // Delete the messages. Bulk deletion requires at least two messages.
+        let http = state.cache_http(guild.id);
+
+        match &*messages {
+            [] => return Ok(embed::clear::no_messages(lang)),
+            [message] => {
+                http.delete_message(channel_id, *message)
+                    .await?
+                    .exec()
+                    .await?
+            }
+            messages => {
+                http.delete_messages(channel_id, messages)
+                    .await?
+                    .exec()
+                    .await?
+            }
+        };
+
+        // Send a summary in the logs channel.
+        let config = state.mongodb().get_guild_or_create(guild.id).await?;
+        let guild_lang = Lang::from(&*config.lang);
+        let moderator = guild
+            .member
+            .user
+            .as_ref()
+            .context("missing interaction member user")?;
+
+        let logs_chan = guild_logs_channel(guild.id, config.logs_chan, state, guild_lang).await?;
+        let logs_embed = embed::clear::logs_message(
+            moderator.id,
+            channel_id,
+            messages.len(),
+            &filter,
+            guild_lang,
+        );
+
+        http.create_message(logs_chan)
+            .await?
+            .embeds(&[logs_embed])?
+            .exec()
+            .await?;
+
+        Ok(embed::clear::success(messages.len(), lang))
+    }
+}
+
+/// Filters applied to the deleted messages.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClearFilter {
+    /// Only match messages sent by this user.
+    pub user: Option<Id<UserMarker>>,
+    /// Only match messages containing links.
+    pub links: bool,
+    /// Only match messages containing invites.
+    pub invites: bool,
+    /// Only match messages with attachments.
+    pub attachments: bool,
+    /// Only match messages sent by bots.
+    pub bots: bool,
+}
+
+impl ClearFilter {
+    /// Check whether a message matches the filters.
+    ///
+    /// The `bot` parameter indicates whether the message author is a bot.
+    pub fn matches(&self, message: &CachedMessage, bot: bool) -> bool {
+        if matches!(self.user, Some(user) if user != message.author_id) {
+            return false;
+        }
+
+        if self.links && message.links.is_empty() {
+            return false;
+        }
+
+        if self.invites
+            && !message
+                .links
+                .iter()
+                .any(|link| matches!(link, MessageLink::Invite(_)))
+        {
+            return false;
+        }
+
+        if self.attachments && message.attachments.is_empty() {
+            return false;
+        }
+
+        !self.bots || bot
+    }
+}
+
+/// Find the most recent messages of a channel matching the filters.
+///
+/// Messages are fetched until `count` matching messages are found, or until
+/// messages are too old to be bulk deleted.
+async fn find_messages(
+    guild_id: Id<GuildMarker>,
+    channel_id: Id<ChannelMarker>,
+    filter: &ClearFilter,
+    count: usize,
+    state: &ClusterState,
+) -> Result<Vec<Id<MessageMarker>>, anyhow::Error> {
+    let min_timestamp = (OffsetDateTime::now_utc() - MAX_MESSAGE_AGE).unix_timestamp();
+    let mut matching = Vec::new();
+    let mut fetched = 0;
+    let mut before = None;
+
+    while fetched < MAX_FETCHED_MESSAGES {
+        let request = state.http().channel_messages(channel_id);
+        let messages: Vec<Message> = match before {
+            Some(before) => request.before(before).limit(100)?.exec().await?,
+            None => request.limit(100)?.exec().await?,
+        }
+        .models()
+        .await?;
+
+        fetched += messages.len();
+        before = messages.last().map(|message| message.id);
+
+        for mut message in messages {
+            if message.timestamp.as_secs() < min_timestamp {
+                return Ok(matching);
+            }
+
+            if !ALLOWED_MESSAGES_TYPES.contains(&message.kind) {
+                continue;
+            }
+
+            // Messages fetched with HTTP requests do not include the guild id.
+            message.guild_id = Some(guild_id);
+
+            if filter.matches(&parse_message(&message), message.author.bot) {
+                matching.push(message.id);
+
+                if matching.len() >= count {
+                    return Ok(matching);
+                }
+            }
+        }
+
+        if before.is_none() {
+            break;
+        }
+    }
+
+    Ok(matching)
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::*;
+    use crate::event::message::test_message;
+
+    fn message(links: Vec<MessageLink>) -> CachedMessage {
+        CachedMessage {
+            links,
+            ..test_message()
+        }
+    }
+
+    #[test]
+    fn test_filter_default() {
+        let filter = ClearFilter::default();
+
+        assert!(filter.matches(&message(Vec::new()), false));
+        assert!(filter.matches(&message(Vec::new()), true));
+    }
+
+    #[test]
+    fn test_filter_user() {
+        let filter = ClearFilter {
+            user: Some(Id::new(2)),
+            ..Default::default()
+        };
+        let other = ClearFilter {
+            user: Some(Id::new(4)),
+            ..Default::default()
+        };
+
+        assert!(filter.matches(&message(Vec::new()), false));
+        assert!(!other.matches(&message(Vec::new()), false));
+    }
+
+    #[test]
+    fn test_filter_links() {
+        let invite = MessageLink::Invite(Url::parse("https://discord.gg/raidprotect").unwrap());
+        let other = MessageLink::Other(Url::parse("https://raidprotect.org").unwrap());
+
+        let links = ClearFilter {
+            links: true,
+            ..Default::default()
+        };
+        let invites = ClearFilter {
+            invites: true,
+            ..Default::default()
+        };
+
+        assert!(!links.matches(&message(Vec::new()), false));
+        assert!(links.matches(&message(vec![other.clone()]), false));
+        assert!(!invites.matches(&message(vec![other]), false));
+        assert!(invites.matches(&message(vec![invite]), false));
+    }
+
+    #[test]
+    fn test_filter_bots() {
+        let filter = ClearFilter {
+            bots: true,
+            ..Default::default()
+        };
+
+        assert!(!filter.matches(&message(Vec::new()), false));
+        assert!(filter.matches(&message(Vec::new()), true));
+    }
+}
@@ -0,0 +1,279 @@
+//! Warnings escalation.
+//!
+//! Guilds can configure [`EscalationRule`]s that automatically apply a
+//! sanction when a member receives too many warnings, such as "3 warnings in 7
+//! days → 1 hour mute". The automatic sanction is stored as a separate modlog
+//! that references the warnings that triggered it.
+
+use futures::TryStreamExt;
+use raidprotect_model::mongodb::{
+    guild::{EscalationRule, EscalationSanction, Guild},
+    modlog::{Modlog, SanctionKind},
+    ObjectId,
+};
+use time::{Duration, OffsetDateTime};
+use tracing::warn;
+use twilight_model::user::User;
+
+use super::{ban::MAX_BAN_DURATION, mute::MAX_MUTE_DURATION, Sanction};
+use crate::{cluster::ClusterState, translations::Lang};
+
+/// Apply the escalation rules after a warning.
+///
+/// The `warn` modlog must have been stored in the database before calling
+/// this function.
+pub async fn escalate(
+    warn: &Modlog,
+    user: &User,
+    config: &Guild,
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    let rules = &config.moderation.escalation;
+
+    if rules.is_empty() {
+        return Ok(());
+    }
+
+    let warns: Vec<Modlog> = state
+        .mongodb()
+        .find_warns(warn.guild_id, warn.user.id)
+        .await?
+        .try_collect()
+        .await?;
+
+    let (rule, warns) = match triggered_rule(rules, &warns, warn.date) {
+        Some(triggered) => triggered,
+        None => return Ok(()),
+    };
+
+    let (kind, duration) = match sanction_kind(rule.sanction) {
+        Some(sanction) => sanction,
+        None => {
+            warn!(guild = ?warn.guild_id, "invalid escalation rule: {:?}", rule);
+            return Ok(());
+        }
+    };
+
+    let guild_lang = Lang::from(&*config.lang);
+    let sanction = Sanction {
+        reason: Some(guild_lang.sanction_escalation_reason(rule.warns)),
+        duration,
+        warns,
+        ..Sanction::new(kind, user.clone())
+    };
+
+    sanction
+        .execute(warn.guild_id, warn.moderator.clone(), config, state)
+        .await?;
+
+    Ok(())
+}
+
+/// Get the type and duration of the sanction applied by a rule.
+///
+/// Mute durations longer than the maximum allowed by Discord are shortened.
+/// Returns [`None`] if the rule has a negative or zero duration, or a ban
+/// duration longer than [`MAX_BAN_DURATION`].
+fn sanction_kind(sanction: EscalationSanction) -> Option<(SanctionKind, Option<Duration>)> {
+    match sanction {
+        EscalationSanction::Mute { duration } if duration > 0 => Some((
+            SanctionKind::Mute,
+            Some(Duration::seconds(duration).min(MAX_MUTE_DURATION)),
+        )),
+        EscalationSanction::Mute { .. } => None,
+        EscalationSanction::Kick => Some((SanctionKind::Kick, None)),
+        EscalationSanction::Ban { duration: None } => Some((SanctionKind::Ban, None)),
+        EscalationSanction::Ban {
+            duration: Some(duration),
+        } => {
+            let duration = Duration::seconds(duration);
+
+            (duration.is_positive() && duration <= MAX_BAN_DURATION)
+                .then_some((SanctionKind::Ban, Some(duration)))
+        }
+    }
+}
+
+/// Find the escalation rule triggered by the latest warning.
+///
+/// A rule is triggered when the number of warnings during its period reaches
+/// exactly its threshold, so that it is only applied once. If multiple rules
+/// are triggered, the one with the highest threshold is used.
+///
+/// The ids of the warnings that triggered the rule are returned with it.
+/// Periods too long to be represented are treated as unlimited.
+fn triggered_rule<'a>(
+    rules: &'a [EscalationRule],
+    warns: &[Modlog],
+    now: OffsetDateTime,
+) -> Option<(&'a EscalationRule, Vec<ObjectId>)> {
+    rules
+        .iter()
+        .filter_map(|rule| {
+            let since = rule
+                .period
+                .and_then(|period| now.checked_sub(Duration::seconds(period)));
+            let warns: Vec<ObjectId> = warns
+                .iter()
+                .filter(|warn| since.is_none_or(|since| warn.date >= since))
+                .filter_map(|warn| warn.id)
+                .collect();
+
+            (warns.len() == rule.warns as usize).then_some((rule, warns))
+        })
+        .max_by_key(|(rule, _)| rule.warns)
+}
+
+#[cfg(test)]
+mod tests {
+    use raidprotect_model::mongodb::modlog::{ModlogType, ModlogUser};
+    use twilight_model::id::Id;
+
+    use super::*;
+
+    fn warn(id: u8, date: OffsetDateTime) -> Modlog {
+        let user = ModlogUser {
+            id: Id::new(1),
+            name: "user".to_string(),
+            discriminator: 1,
+            avatar: None,
+        };
+
+        Modlog {
+            id: Some(ObjectId::from_bytes([id; 12])),
+            case: None,
+            kind: ModlogType::Warn,
+            guild_id: Id::new(2),
+            user: user.clone(),
+            moderator: user,
+            date,
+            reason: None,
+            notes: None,
+            expires_at: None,
+            expired: false,
+            warns: Vec::new(),
+            revoked: None,
+            notified: None,
+            logs_message: None,
+            edits: Vec::new(),
+        }
+    }
+
+    fn rules() -> Vec<EscalationRule> {
+        vec![
+            EscalationRule {
+                warns: 3,
+                period: Some(Duration::days(7).whole_seconds()),
+                sanction: EscalationSanction::Mute { duration: 3600 },
+            },
+            EscalationRule {
+                warns: 5,
+                period: None,
+                sanction: EscalationSanction::Kick,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_triggered_rule_none() {
+        let now = OffsetDateTime::now_utc();
+        let warns = vec![warn(1, now - Duration::days(10)), warn(2, now)];
+
+        assert_eq!(triggered_rule(&rules(), &warns, now), None);
+    }
+
+    #[test]
+    fn test_triggered_rule_period() {
+        let rules = rules();
+        let now = OffsetDateTime::now_utc();
+        let warns = vec![
+            warn(1, now - Duration::days(10)),
+            warn(2, now - Duration::days(2)),
+            warn(3, now - Duration::days(1)),
+            warn(4, now),
+        ];
+
+        let expected = vec![
+            ObjectId::from_bytes([2; 12]),
+            ObjectId::from_bytes([3; 12]),
+            ObjectId::from_bytes([4; 12]),
+        ];
+
+        assert_eq!(
+            triggered_rule(&rules, &warns, now),
+            Some((&rules[0], expected))
+        );
+    }
+
+    #[test]
+    fn test_triggered_rule_highest() {
+        let rules = rules();
+        let now = OffsetDateTime::now_utc();
+        let warns = vec![
+            warn(1, now - Duration::days(20)),
+            warn(2, now - Duration::days(10)),
+            warn(3, now - Duration::days(2)),
+            warn(4, now - Duration::days(1)),
+            warn(5, now),
+        ];
+
+        // Both rules are triggered, the one with the highest threshold is used.
+        let (rule, warns) = triggered_rule(&rules, &warns, now).unwrap();
+
+        assert_eq!(rule, &rules[1]);
+        assert_eq!(warns.len(), 5);
+    }
+
+    #[test]
+    fn test_sanction_kind() {
+        assert_eq!(
+            sanction_kind(EscalationSanction::Mute { duration: 3600 }),
+            Some((SanctionKind::Mute, Some(Duration::hours(1))))
+        );
+        assert_eq!(
+            sanction_kind(EscalationSanction::Mute { duration: i64::MAX }),
+            Some((SanctionKind::Mute, Some(MAX_MUTE_DURATION)))
+        );
+        assert_eq!(
+            sanction_kind(EscalationSanction::Ban { duration: None }),
+            Some((SanctionKind::Ban, None))
+        );
+
+        assert_eq!(
+            sanction_kind(EscalationSanction::Mute { duration: 0 }),
+            None
+        );
+        assert_eq!(
+            sanction_kind(EscalationSanction::Mute { duration: -60 }),
+            None
+        );
+        assert_eq!(
+            sanction_kind(EscalationSanction::Ban {
+                duration: Some(-60)
+            }),
+            None
+        );
+        assert_eq!(
+            sanction_kind(EscalationSanction::Ban {
+                duration: Some(i64::MAX)
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_triggered_rule_overflow() {
+        let rules = vec![EscalationRule {
+            warns: 2,
+            period: Some(i64::MAX),
+            sanction: EscalationSanction::Kick,
+        }];
+        let now = OffsetDateTime::now_utc();
+        let warns = vec![warn(1, now - Duration::days(1000)), warn(2, now)];
+
+        assert_eq!(
+            triggered_rule(&rules, &warns, now).map(|(_, warns)| warns.len()),
+            Some(2)
+        );
+    }
+}
@@ -9,27 +9,15 @@
 //! reason of the kick.
 
 use anyhow::Context;
-use nanoid::nanoid;
-use raidprotect_model::{
-    cache::model::interaction::{PendingModal, PendingSanction},
-    mongodb::modlog::ModlogType,
-};
+use raidprotect_model::mongodb::modlog::SanctionKind;
 use twilight_interactions::command::{CommandModel, CreateCommand, ResolvedUser};
-use twilight_model::{
-    application::{
-        component::{text_input::TextInputStyle, ActionRow, Component, TextInput},
-        interaction::Interaction,
-    },
-    guild::Permissions,
-    user::User,
-};
+use twilight_model::{application::interaction::Interaction, guild::Permissions};
 
+use super::Sanction;
 use crate::{
     cluster::ClusterState,
     desc_localizations, impl_command_handle,
     interaction::{embed, response::InteractionResponse, util::InteractionExt},
-    translations::Lang,
-    util::TextProcessExt,
 };
 
 /// Kick command model.
@@ -48,6 +36,7 @@ pub struct KickCommand {
     #[command(rename = "member")]
     pub user: ResolvedUser,
     /// Reason for kick.
+    #[command(max_length = 100)]
     pub reason: Option<String>,
 }
 
@@ -71,102 +60,32 @@ impl KickCommand {
         let lang = interaction.locale()?;
         let member = match self.user.member {
             Some(member) => member,
-            None => return Ok(embed::kick::not_member(user.name, lang)),
+            None => return Ok(embed::sanction::not_member(user.name, lang)),
         };
 
-        // Fetch the author and the bot permissions.
-        let permissions = state.redis().permissions(guild.id).await?;
-        let author_permissions = permissions.member(author_id, &member.roles).await?;
-        let member_permissions = permissions.member(user.id, &member.roles).await?;
-        let bot_permissions = permissions.current_member().await?;
-
-        // Check if the author and the bot have required permissions.
-        if member_permissions.is_owner() {
-            return Ok(embed::kick::member_owner(lang));
-        }
-
-        if !bot_permissions.guild().contains(Permissions::KICK_MEMBERS) {
-            return Ok(embed::kick::bot_missing_permission(lang));
-        }
-
-        // Check if the role hierarchy allow the author and the bot to perform
-        // the kick.
-        let member_highest_role = member_permissions.highest_role();
-
-        if member_highest_role >= author_permissions.highest_role() {
-            return Ok(embed::kick::user_hierarchy(lang));
-        }
-
-        if member_highest_role >= bot_permissions.highest_role() {
-            return Ok(embed::kick::bot_hierarchy(lang));
-        }
+        let sanction = Sanction {
+            reason: self.reason,
+            ..Sanction::new(SanctionKind::Kick, user)
+        };
 
-        // Send reason modal.
-        let enforce_reason = state
-            .mongodb()
-            .get_guild_or_create(guild.id)
+        if let Some(response) = sanction
+            .check_permissions(&guild, author_id, Some(&member.roles), state, lang)
             .await?
-            .moderation
-            .enforce_reason;
-
-        match self.reason {
-            Some(_reason) => Ok(InteractionResponse::EphemeralDeferredMessage),
-            None => KickCommand::reason_modal(user, enforce_reason, state, lang).await,
+        {
+            return Ok(response);
         }
-    }
 
-    /// Modal that asks the user to enter a reason for the kick.
-    ///
-    /// This modal is only shown if the user has not specified a reason in the
-    /// initial command.
-    async fn reason_modal(
-        user: User,
-        enforce_reason: bool,
-        state: &ClusterState,
-        lang: Lang,
-    ) -> Result<InteractionResponse, anyhow::Error> {
-        let username = user.name.truncate(15);
-        let components = vec![
-            Component::ActionRow(ActionRow {
-                components: vec![Component::TextInput(TextInput {
-                    custom_id: "reason".to_string(),
-                    label: lang.modal_kick_reason_label().to_string(),
-                    max_length: Some(100),
-                    min_length: None,
-                    placeholder: Some(lang.modal_reason_placeholder().to_string()),
-                    required: Some(enforce_reason),
-                    style: TextInputStyle::Short,
-                    value: None,
-                })],
-            }),
-            Component::ActionRow(ActionRow {
-                components: vec![Component::TextInput(TextInput {
-                    custom_id: "notes".to_string(),
-                    label: lang.modal_notes_label().to_string(),
-                    max_length: Some(1000),
-                    min_length: None,
-                    placeholder: Some(lang.modal_notes_placeholder().to_string()),
-                    required: Some(false),
-                    style: TextInputStyle::Paragraph,
-                    value: None,
-                })],
-            }),
-        ];
+        // Kick the member directly if a reason is provided, otherwise show the
+        // reason modal.
+        let config = state.mongodb().get_guild_or_create(guild.id).await?;
 
-        // Add pending component in Redis
-        let custom_id = nanoid!();
-        let pending = PendingModal::Sanction(PendingSanction {
-            id: custom_id.clone(),
-            kind: ModlogType::Kick,
-            user,
-        });
+        match sanction.reason {
+            Some(_) => sanction.apply(&guild, &config, state, lang).await,
+            None => {
+                let enforce_reason = config.moderation.enforce_reason;
 
-        state.redis().set(&pending).await?;
-
-        Ok(InteractionResponse::Modal {
-            custom_id,
-            title: lang.modal_kick_title(username),
-            components,
-        })
+                sanction.reason_modal(enforce_reason, state, lang).await
+            }
+        }
     }
 }
@@ -0,0 +1,406 @@
+//! Lockdown command.
+//!
+//! The command prevents members from sending messages in a channel, in all the
+//! channels of a category or in all the channels of the server, by denying
+//! the `SEND_MESSAGES` permission to the `@everyone` role.
+//!
+//! The previous permission overwrite of the `@everyone` role is saved in the
+//! database before each channel is locked, so that the `unlock` command can
+//! restore it exactly. Channels that cannot be updated are reported to the
+//! moderator without interrupting the lockdown of other channels. Since
+//! locking all the channels of the server may take a while, the response to
+//! this command is deferred.
+
+use anyhow::Context;
+use raidprotect_model::{
+    cache::model::{CachedChannel, CachedTextChannel},
+    mongodb::lockdown::{Lockdown, LockdownOverwrite},
+};
+use time::OffsetDateTime;
+use tracing::warn;
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    application::interaction::{application_command::InteractionChannel, Interaction},
+    channel::permission_overwrite::PermissionOverwriteType,
+    guild::Permissions,
+    http::permission_overwrite::{
+        PermissionOverwrite as HttpPermissionOverwrite,
+        PermissionOverwriteType as HttpPermissionOverwriteType,
+    },
+    id::{
+        marker::{ChannelMarker, GuildMarker, UserMarker},
+        Id,
+    },
+};
+
+use crate::{
+    cluster::ClusterState,
+    desc_localizations, impl_command_handle,
+    interaction::{
+        embed,
+        response::InteractionResponse,
+        util::{GuildInteraction, InteractionExt},
+    },
+    translations::Lang,
+    util::guild_logs_channel,
+};
+
+/// Permissions denied to the `@everyone` role in locked channels.
+const LOCKED_PERMISSIONS: Permissions =
+    Permissions::SEND_MESSAGES.union(Permissions::SEND_MESSAGES_IN_THREADS);
+
+/// Lockdown command model.
+///
+/// See the [`module`][self] documentation for more information.
+#[derive(Debug, Clone, CommandModel, CreateCommand)]
+#[command(
+    name = "lockdown",
+    desc = "Prevents members from sending messages in a channel, a category or the server",
+    desc_localizations = "lockdown_description",
+    default_permissions = "LockdownCommand::default_permissions",
+    dm_permission = false
+)]
+pub struct LockdownCommand {
+    /// Channel or category to lock, defaults to the current channel.
+    #[command(channel_types = "guild_text guild_news guild_category")]
+    pub channel: Option<InteractionChannel>,
+    /// Lock all the channels of the server.
+    pub server: Option<bool>,
+}
+
+impl_command_handle!(LockdownCommand);
+desc_localizations!(lockdown_description);
+
+impl LockdownCommand {
+    fn default_permissions() -> Permissions {
+        Permissions::MANAGE_CHANNELS
+    }
+
+    async fn exec(
+        self,
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let guild = interaction.guild()?;
+        let lang = interaction.locale()?;
+        let scope = LockdownScope::new(
+            self.channel.map(|channel| channel.id),
+            self.server.unwrap_or(false),
+            &interaction,
+        )?;
+
+        let moderator = match scope.check_permissions(&guild, state).await? {
+            Some(moderator) => moderator,
+            None => return Ok(embed::lockdown::missing_permission(lang)),
+        };
+
+        let mut report = LockdownReport::default();
+
+        for channel in scope.channels(guild.id, state).await? {
+            if state.mongodb().get_lockdown(channel.id).await?.is_some() {
+                report.skipped += 1;
+                continue;
+            }
+
+            match lock_channel(&channel, moderator, state).await {
+                Ok(()) => report.updated.push(channel.id),
+                Err(error) => {
+                    warn!(error = ?error, "failed to lock channel {}", channel.id);
+                    report.failed.push(channel.id);
+                }
+            }
+        }
+
+        report.send_logs(guild.id, moderator, true, state).await?;
+
+        Ok(embed::lockdown::report(&report, true, lang))
+    }
+}
+
+/// Channels targeted by the `lockdown` and `unlock` commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockdownScope {
+    /// A text channel or a category.
+    Channel(Id<ChannelMarker>),
+    /// All the text channels of the server.
+    Server,
+}
+
+impl LockdownScope {
+    /// Initialize a new [`LockdownScope`] from the command options.
+    ///
+    /// The channel where the command has been used is targeted if no option
+    /// is provided.
+    pub fn new(
+        channel: Option<Id<ChannelMarker>>,
+        server: bool,
+        interaction: &Interaction,
+    ) -> Result<Self, anyhow::Error> {
+        if server {
+            return Ok(Self::Server);
+        }
+
+        match channel {
+            Some(channel) => Ok(Self::Channel(channel)),
+            None => Ok(Self::Channel(
+                interaction.channel_id.context("missing channel_id")?,
+            )),
+        }
+    }
+
+    /// Ensure the author of the command has the `MANAGE_CHANNELS` permission.
+    ///
+    /// The permission is checked in the targeted channel or category, or in the
+    /// whole server. Returns the id of the author if the permission is granted.
+    pub async fn check_permissions(
+        self,
+        guild: &GuildInteraction<'_>,
+        state: &ClusterState,
+    ) -> Result<Option<Id<UserMarker>>, anyhow::Error> {
+        let author = guild
+            .member
+            .user
+            .as_ref()
+            .context("missing interaction member user")?;
+        let permissions = state
+            .redis()
+            .permissions(guild.id)
+            .await?
+            .member(author.id, &guild.member.roles)
+            .await?;
+
+        let permissions = match self {
+            Self::Channel(channel) => permissions.channel(channel).await?.0,
+            Self::Server => permissions.guild(),
+        };
+
+        Ok(permissions
+            .contains(Permissions::MANAGE_CHANNELS)
+            .then_some(author.id))
+    }
+
+    /// Get the text channels targeted by the scope.
+    ///
+    /// If a thread is targeted, its parent channel is returned.
+    pub async fn channels(
+        self,
+        guild_id: Id<GuildMarker>,
+        state: &ClusterState,
+    ) -> Result<Vec<CachedTextChannel>, anyhow::Error> {
+        let channel = match self {
+            Self::Channel(channel) => state
+                .redis()
+                .get::<CachedChannel>(&channel)
+                .await?
+                .context("channel not found in cache")?,
+            Self::Server => return text_channels(guild_id, None, state).await,
+        };
+
+        match channel {
+            CachedChannel::Text(channel) => Ok(vec![channel]),
+            CachedChannel::Category(category) => {
+                text_channels(guild_id, Some(category.id), state).await
+            }
+            CachedChannel::Thread(thread) => match state
+                .redis()
+                .get::<CachedChannel>(&thread.parent_id)
+                .await?
+            {
+                Some(CachedChannel::Text(channel)) => Ok(vec![channel]),
+                _ => Ok(Vec::new()),
+            },
+            CachedChannel::Voice(_) => Ok(Vec::new()),
+        }
+    }
+}
+
+/// Result of the `lockdown` and `unlock` commands.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LockdownReport {
+    /// Channels that have been updated.
+    pub updated: Vec<Id<ChannelMarker>>,
+    /// Number of channels that were already locked or unlocked.
+    pub skipped: usize,
+    /// Channels that could not be updated.
+    pub failed: Vec<Id<ChannelMarker>>,
+}
+
+impl LockdownReport {
+    /// Send a summary of the updated channels in the logs channel.
+    ///
+    /// No message is sent if no channel has been updated.
+    pub async fn send_logs(
+        &self,
+        guild_id: Id<GuildMarker>,
+        moderator: Id<UserMarker>,
+        locked: bool,
+        state: &ClusterState,
+    ) -> Result<(), anyhow::Error> {
+        if self.updated.is_empty() {
+            return Ok(());
+        }
+
+        let config = state.mongodb().get_guild_or_create(guild_id).await?;
+        let guild_lang = Lang::from(&*config.lang);
+
+        let logs_chan = guild_logs_channel(guild_id, config.logs_chan, state, guild_lang).await?;
+        let logs_embed = embed::lockdown::logs_message(moderator, self, locked, guild_lang);
+
+        state
+            .cache_http(guild_id)
+            .create_message(logs_chan)
+            .await?
+            .embeds(&[logs_embed])?
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Build the permission overwrite of the `@everyone` role.
+pub fn everyone_overwrite(
+    guild_id: Id<GuildMarker>,
+    allow: Permissions,
+    deny: Permissions,
+) -> HttpPermissionOverwrite {
+    HttpPermissionOverwrite {
+        allow: Some(allow),
+        deny: Some(deny),
+        id: guild_id.cast(),
+        kind: HttpPermissionOverwriteType::Role,
+    }
+}
+
+/// Lock a channel and save its previous permission overwrite.
+///
+/// The lockdown is saved before updating the channel, so that the channel can
+/// still be unlocked if the bot stops in the meantime.
+async fn lock_channel(
+    channel: &CachedTextChannel,
+    moderator: Id<UserMarker>,
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    let previous = previous_overwrite(channel);
+    let lockdown = Lockdown {
+        channel_id: channel.id,
+        guild_id: channel.guild_id,
+        overwrite: previous,
+        moderator,
+        date: OffsetDateTime::now_utc(),
+    };
+
+    state.mongodb().create_lockdown(&lockdown).await?;
+
+    let previous = previous.unwrap_or(LockdownOverwrite {
+        allow: Permissions::empty(),
+        deny: Permissions::empty(),
+    });
+    let overwrite = everyone_overwrite(
+        channel.guild_id,
+        previous.allow - LOCKED_PERMISSIONS,
+        previous.deny | LOCKED_PERMISSIONS,
+    );
+
+    let result = async {
+        state
+            .cache_http(channel.guild_id)
+            .update_channel_permission(channel.id, &overwrite)
+            .await?
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+    .await;
+
+    if result.is_err() {
+        state.mongodb().delete_lockdown(channel.id).await?;
+    }
+
+    result
+}
+
+/// Get the permission overwrite of the `@everyone` role of a channel.
+fn previous_overwrite(channel: &CachedTextChannel) -> Option<LockdownOverwrite> {
+    channel
+        .permission_overwrites
+        .iter()
+        .find(|overwrite| {
+            overwrite.kind == PermissionOverwriteType::Role
+                && overwrite.id == channel.guild_id.cast()
+        })
+        .map(|overwrite| LockdownOverwrite {
+            allow: overwrite.allow,
+            deny: overwrite.deny,
+        })
+}
+
+/// Get the text channels of a guild, optionally filtered by category.
+///
+/// Channels are sorted by position.
+async fn text_channels(
+    guild_id: Id<GuildMarker>,
+    category: Option<Id<ChannelMarker>>,
+    state: &ClusterState,
+) -> Result<Vec<CachedTextChannel>, anyhow::Error> {
+    let mut channels = state
+        .redis()
+        .guild_channels(guild_id)
+        .await?
+        .into_iter()
+        .filter_map(|channel| match channel {
+            CachedChannel::Text(channel) if category.is_none() || channel.parent_id == category => {
+                Some(channel)
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    channels.sort_by_key(|channel| channel.position);
+
+    Ok(channels)
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::channel::permission_overwrite::PermissionOverwrite;
+
+    use super::*;
+
+    #[test]
+    fn test_previous_overwrite() {
+        let overwrite = |id: u64, kind| PermissionOverwrite {
+            allow: Permissions::ADD_REACTIONS,
+            deny: Permissions::ATTACH_FILES,
+            id: Id::new(id),
+            kind,
+        };
+        let mut channel = CachedTextChannel {
+            id: Id::new(2),
+            guild_id: Id::new(1),
+            name: "general".to_string(),
+            parent_id: None,
+            position: 0,
+            permission_overwrites: vec![
+                overwrite(1, PermissionOverwriteType::Member),
+                overwrite(3, PermissionOverwriteType::Role),
+            ],
+            rate_limit_per_user: None,
+        };
+
+        assert_eq!(previous_overwrite(&channel), None);
+
+        channel
+            .permission_overwrites
+            .push(overwrite(1, PermissionOverwriteType::Role));
+
+        assert_eq!(
+            previous_overwrite(&channel),
+            Some(LockdownOverwrite {
+                allow: Permissions::ADD_REACTIONS,
+                deny: Permissions::ATTACH_FILES,
+            })
+        );
+    }
+}
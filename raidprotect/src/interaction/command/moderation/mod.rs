@@ -16,7 +16,46 @@
 //! The sanctioned user receive a private message with the reason, and the
 //! sanction is applied. It is also logged in the guild's logs channel and in
 //! the bot database.
+//!
+//! Bans and mutes can be revoked with the `unban` and `unmute` commands, which
+//! mark the active sanction as revoked (see the [`revoke`] module).
+//!
+//! Warnings may trigger an automatic sanction if the guild has configured
+//! escalation rules (see the [`escalation`] module).
+//!
+//! The sanctions received by a user can be listed with the `modlogs` command,
+//! and the reason and notes of a sanction can be edited with the `case edit`
+//! command.
+//!
+//! Recent messages of a channel can be bulk deleted with the `clear` command.
+//! Channels can be locked during an incident with the `lockdown` command, and
+//! unlocked with the `unlock` command.
 
+mod ban;
+mod case;
+mod clear;
+mod escalation;
 mod kick;
+mod lockdown;
+mod modlogs;
+mod mute;
+mod revoke;
+mod sanction;
+mod unban;
+mod unlock;
+mod unmute;
+mod warn;
 
+pub use ban::BanCommand;
+pub use case::CaseCommand;
+pub use clear::{ClearCommand, ClearFilter};
+pub use escalation::escalate;
 pub use kick::KickCommand;
+pub use lockdown::{LockdownCommand, LockdownReport};
+pub use modlogs::ModlogsCommand;
+pub use mute::{MuteCommand, MAX_MUTE_DURATION};
+pub use sanction::{flag_member, Sanction};
+pub use unban::UnbanCommand;
+pub use unlock::UnlockCommand;
+pub use unmute::UnmuteCommand;
+pub use warn::WarnCommand;
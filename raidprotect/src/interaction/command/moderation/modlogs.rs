@@ -0,0 +1,90 @@
+//! Modlogs command.
+//!
+//! The command shows the sanctions received by a user, as paginated embeds.
+//! It is restricted to the moderator roles configured for the guild, as well
+//! as the guild owner and administrators.
+
+use anyhow::Context;
+use raidprotect_model::mongodb::guild::Guild;
+use twilight_interactions::command::{CommandModel, CreateCommand, ResolvedUser};
+use twilight_model::{
+    application::interaction::Interaction,
+    guild::Permissions,
+    id::{marker::UserMarker, Id},
+};
+
+use crate::{
+    cluster::ClusterState,
+    desc_localizations, impl_command_handle,
+    interaction::{
+        component::ModlogsPage,
+        embed,
+        response::InteractionResponse,
+        util::{GuildInteraction, InteractionExt},
+    },
+};
+
+/// Modlogs command model.
+///
+/// See the [`module`][self] documentation for more information.
+#[derive(Debug, Clone, CommandModel, CreateCommand)]
+#[command(
+    name = "modlogs",
+    desc = "Show the sanctions of a user",
+    desc_localizations = "modlogs_description",
+    dm_permission = false
+)]
+pub struct ModlogsCommand {
+    /// User whose sanctions are shown.
+    pub user: ResolvedUser,
+}
+
+impl_command_handle!(ModlogsCommand);
+desc_localizations!(modlogs_description);
+
+impl ModlogsCommand {
+    async fn exec(
+        self,
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let guild = interaction.guild()?;
+        let author_id = interaction.author_id().context("missing author_id")?;
+        let lang = interaction.locale()?;
+
+        // Check if the author is allowed to see the modlogs.
+        let config = state.mongodb().get_guild_or_create(guild.id).await?;
+
+        if !is_moderator(&guild, author_id, &config, state).await? {
+            return Ok(embed::modlogs::missing_permission(lang));
+        }
+
+        ModlogsPage::create(guild.id, self.user.resolved, state, lang).await
+    }
+}
+
+/// Check if a member is allowed to access the guild modlogs.
+///
+/// Access is granted to the guild owner, administrators and members with one
+/// of the moderator roles configured for the guild.
+pub async fn is_moderator(
+    guild: &GuildInteraction<'_>,
+    author_id: Id<UserMarker>,
+    config: &Guild,
+    state: &ClusterState,
+) -> Result<bool, anyhow::Error> {
+    let permissions = state
+        .redis()
+        .permissions(guild.id)
+        .await?
+        .member(author_id, &guild.member.roles)
+        .await?;
+
+    Ok(permissions.is_owner()
+        || permissions.guild().contains(Permissions::ADMINISTRATOR)
+        || guild
+            .member
+            .roles
+            .iter()
+            .any(|role| config.moderation.roles.contains(role)))
+}
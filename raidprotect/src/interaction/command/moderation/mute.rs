@@ -0,0 +1,110 @@
+//! Mute command.
+//!
+//! The command allows to temporarily mute a member of the server using Discord
+//! timeouts. The duration of the mute is required and cannot exceed 28 days,
+//! which is the maximum allowed by Discord. User can specify a reason directly
+//! in the command (as an optional parameter), or in the modal that is shown if
+//! it hasn't been set in the command.
+//!
+//! When a user is muted, the action is logged in the database and a message is
+//! sent in the guild's logs channel. The muted user receives a pm with the
+//! reason of the mute.
+
+use anyhow::Context;
+use raidprotect_model::mongodb::modlog::SanctionKind;
+use time::Duration;
+use twilight_interactions::command::{CommandModel, CreateCommand, ResolvedUser};
+use twilight_model::{application::interaction::Interaction, guild::Permissions};
+
+use super::Sanction;
+use crate::{
+    cluster::ClusterState,
+    desc_localizations, impl_command_handle,
+    interaction::{embed, response::InteractionResponse, util::InteractionExt},
+    util::parse_duration,
+};
+
+/// Maximum duration of a mute allowed by Discord.
+pub const MAX_MUTE_DURATION: Duration = Duration::days(28);
+
+/// Mute command model.
+///
+/// See the [`module`][self] documentation for more information.
+#[derive(Debug, Clone, CommandModel, CreateCommand)]
+#[command(
+    name = "mute",
+    desc = "Temporarily mutes a member of the server",
+    desc_localizations = "mute_description",
+    default_permissions = "MuteCommand::default_permissions",
+    dm_permission = false
+)]
+pub struct MuteCommand {
+    /// Member to mute.
+    #[command(rename = "member")]
+    pub user: ResolvedUser,
+    /// Duration of the mute (e.g. 10m, 2h, 7d).
+    pub duration: String,
+    /// Reason for mute.
+    #[command(max_length = 100)]
+    pub reason: Option<String>,
+}
+
+impl_command_handle!(MuteCommand);
+desc_localizations!(mute_description);
+
+impl MuteCommand {
+    fn default_permissions() -> Permissions {
+        Permissions::MODERATE_MEMBERS
+    }
+
+    async fn exec(
+        self,
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let guild = interaction.guild()?;
+        let author_id = interaction.author_id().context("missing author_id")?;
+
+        let user = self.user.resolved;
+        let lang = interaction.locale()?;
+        let member = match self.user.member {
+            Some(member) => member,
+            None => return Ok(embed::sanction::not_member(user.name, lang)),
+        };
+
+        let duration = match parse_duration(&self.duration) {
+            Some(duration) => duration,
+            None => return Ok(embed::sanction::invalid_duration(lang)),
+        };
+
+        if duration > MAX_MUTE_DURATION {
+            return Ok(embed::sanction::mute_duration_too_long(lang));
+        }
+
+        let sanction = Sanction {
+            reason: self.reason,
+            duration: Some(duration),
+            ..Sanction::new(SanctionKind::Mute, user)
+        };
+
+        if let Some(response) = sanction
+            .check_permissions(&guild, author_id, Some(&member.roles), state, lang)
+            .await?
+        {
+            return Ok(response);
+        }
+
+        // Mute the member directly if a reason is provided, otherwise show the
+        // reason modal.
+        let config = state.mongodb().get_guild_or_create(guild.id).await?;
+
+        match sanction.reason {
+            Some(_) => sanction.apply(&guild, &config, state, lang).await,
+            None => {
+                let enforce_reason = config.moderation.enforce_reason;
+
+                sanction.reason_modal(enforce_reason, state, lang).await
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Revocation of active sanctions.
+//!
+//! When a sanction is manually lifted (e.g. with the `unban` command), the
+//! active sanction of the user is marked as revoked in the database so that it
+//! is not lifted again once expired.
+
+use anyhow::Context;
+use raidprotect_model::mongodb::modlog::{ModlogRevocation, ModlogType, ModlogUser};
+use time::OffsetDateTime;
+use twilight_model::user::User;
+
+use crate::{
+    cluster::ClusterState,
+    interaction::{embed, util::GuildInteraction},
+    translations::Lang,
+    util::guild_logs_channel,
+};
+
+/// Revoke the active sanction of a user.
+///
+/// The sanction must have been lifted through the Discord API before calling
+/// this function. The active [`Modlog`] of the user, if any, is marked as
+/// revoked and a message is sent in the guild's logs channel.
+///
+/// [`Modlog`]: raidprotect_model::mongodb::modlog::Modlog
+pub async fn revoke_sanction(
+    kind: ModlogType,
+    user: &User,
+    reason: Option<String>,
+    guild: &GuildInteraction<'_>,
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    let config = state.mongodb().get_guild_or_create(guild.id).await?;
+    let guild_lang = Lang::from(&*config.lang);
+    let moderator = guild
+        .member
+        .user
+        .as_ref()
+        .context("missing interaction member user")?;
+
+    let revocation = ModlogRevocation {
+        moderator: ModlogUser::from(moderator),
+        date: OffsetDateTime::now_utc(),
+        reason,
+    };
+
+    // The sanction may have been applied without the bot.
+    let active = state
+        .mongodb()
+        .find_active_sanction(guild.id, user.id, kind)
+        .await?;
+
+    let case = active.as_ref().and_then(|modlog| modlog.case);
+
+    if let Some(id) = active.and_then(|modlog| modlog.id) {
+        state.mongodb().revoke_modlog(id, &revocation).await?;
+    }
+
+    // Send a message in the logs channel.
+    let logs_chan = guild_logs_channel(guild.id, config.logs_chan, state, guild_lang).await?;
+    let logs_embed = embed::sanction::revoke_logs_message(
+        kind,
+        &ModlogUser::from(user),
+        &revocation,
+        case,
+        guild_lang,
+    );
+
+    state
+        .cache_http(guild.id)
+        .create_message(logs_chan)
+        .await?
+        .embeds(&[logs_embed])?
+        .exec()
+        .await?;
+
+    Ok(())
+}
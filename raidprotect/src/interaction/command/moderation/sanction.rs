@@ -0,0 +1,679 @@
+//! Sanction shared between moderation commands.
+//!
+//! The [`Sanction`] type holds the information about a sanction being applied
+//! to a user, and implement the behavior shared between each moderation
+//! command: permission checks, reason modal and application of the sanction.
+//!
+//! Members flagged by automatic checks are not sanctioned, the flag is only
+//! recorded in the modlogs with [`flag_member`].
+
+use anyhow::Context;
+use nanoid::nanoid;
+use raidprotect_model::{
+    cache::model::{
+        interaction::{PendingModal, PendingSanction},
+        CachedGuild,
+    },
+    mongodb::{
+        guild::Guild,
+        modlog::{Modlog, ModlogMessage, ModlogType, ModlogUser, SanctionKind},
+        ObjectId,
+    },
+};
+use time::{Duration, OffsetDateTime};
+use tracing::{debug, error};
+use twilight_http::{error::ErrorType, request::AuditLogReason};
+use twilight_model::{
+    application::{
+        component::{text_input::TextInputStyle, ActionRow, Component, TextInput},
+        interaction::Interaction,
+    },
+    channel::embed::Embed,
+    guild::Permissions,
+    id::{
+        marker::{GuildMarker, RoleMarker, UserMarker},
+        Id,
+    },
+    user::User,
+    util::Timestamp,
+};
+
+use super::escalation::escalate;
+use crate::{
+    cluster::ClusterState,
+    interaction::{
+        embed,
+        response::InteractionResponse,
+        util::{parse_modal_data, parse_modal_field, GuildInteraction, InteractionExt},
+    },
+    translations::Lang,
+    util::{guild_logs_channel, TextProcessExt},
+};
+
+/// Maximum length of a sanction reason.
+///
+/// The `reason` option of the sanction commands has the same limit.
+pub const MAX_REASON_LENGTH: usize = 100;
+
+/// Sanction applied to a user.
+#[derive(Debug, Clone)]
+pub struct Sanction {
+    /// Type of the sanction.
+    pub kind: SanctionKind,
+    /// User targeted by the sanction.
+    pub user: User,
+    /// Reason of the sanction, sent to the user.
+    pub reason: Option<String>,
+    /// Internal notes, only visible to moderators.
+    pub notes: Option<String>,
+    /// Duration of the sanction, if the sanction is temporary.
+    pub duration: Option<Duration>,
+    /// Number of days of messages to delete (only used with bans).
+    pub delete_message_days: Option<u16>,
+    /// Warnings that triggered the sanction (only used with escalation).
+    pub warns: Vec<ObjectId>,
+}
+
+impl Sanction {
+    /// Initialize a new [`Sanction`] without reason.
+    pub fn new(kind: SanctionKind, user: User) -> Self {
+        Self {
+            kind,
+            user,
+            reason: None,
+            notes: None,
+            duration: None,
+            delete_message_days: None,
+            warns: Vec::new(),
+        }
+    }
+
+    /// Handle the submission of the reason modal.
+    ///
+    /// The role hierarchy is checked again since it could have changed since
+    /// the modal has been shown.
+    pub async fn handle_modal(
+        mut interaction: Interaction,
+        pending: PendingSanction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let data = parse_modal_data(&mut interaction)?;
+        let guild = interaction.guild()?;
+        let author_id = interaction.author_id().context("missing author_id")?;
+        let lang = interaction.locale()?;
+
+        let sanction = Self {
+            kind: pending.kind,
+            user: pending.user,
+            reason: parse_modal_field(&data, "reason").map(String::from),
+            notes: parse_modal_field(&data, "notes").map(String::from),
+            duration: pending.duration.map(Duration::seconds),
+            delete_message_days: pending.delete_message_days,
+            warns: Vec::new(),
+        };
+
+        // Fetch the member to get its current roles.
+        let member = match state
+            .http()
+            .guild_member(guild.id, sanction.user.id)
+            .exec()
+            .await
+        {
+            Ok(response) => Some(response.model().await?),
+            Err(error) => match error.kind() {
+                ErrorType::Response { status, .. } if status.get() == 404 => None,
+                _ => return Err(error.into()),
+            },
+        };
+
+        let member_roles = member.as_ref().map(|member| &*member.roles);
+        if member_roles.is_none() && sanction.kind != SanctionKind::Ban {
+            return Ok(embed::sanction::not_member(sanction.user.name, lang));
+        }
+
+        if let Some(response) = sanction
+            .check_permissions(&guild, author_id, member_roles, state, lang)
+            .await?
+        {
+            return Ok(response);
+        }
+
+        let config = state.mongodb().get_guild_or_create(guild.id).await?;
+
+        sanction.apply(&guild, &config, state, lang).await
+    }
+
+    /// Check if the author and the bot have the required permissions to apply
+    /// the sanction.
+    ///
+    /// The `member_roles` should be [`None`] if the user is not a member of the
+    /// guild. In that case, the role hierarchy is not checked.
+    ///
+    /// If the sanction cannot be applied, the response to send is returned.
+    pub async fn check_permissions(
+        &self,
+        guild: &GuildInteraction<'_>,
+        author_id: Id<UserMarker>,
+        member_roles: Option<&[Id<RoleMarker>]>,
+        state: &ClusterState,
+        lang: Lang,
+    ) -> Result<Option<InteractionResponse>, anyhow::Error> {
+        // Fetch the author and the bot permissions.
+        let permissions = state.redis().permissions(guild.id).await?;
+        let author_permissions = permissions.member(author_id, &guild.member.roles).await?;
+        let bot_permissions = permissions.current_member().await?;
+
+        if !bot_permissions
+            .guild()
+            .contains(required_permission(self.kind))
+        {
+            return Ok(Some(embed::sanction::bot_missing_permission(
+                self.kind, lang,
+            )));
+        }
+
+        let member_roles = match member_roles {
+            Some(member_roles) => member_roles,
+            None => return Ok(None),
+        };
+
+        // Check if the author and the bot have required permissions.
+        let member_permissions = permissions.member(self.user.id, member_roles).await?;
+
+        if member_permissions.is_owner() {
+            return Ok(Some(embed::sanction::member_owner(self.kind, lang)));
+        }
+
+        // Discord does not allow to timeout administrators.
+        if self.kind == SanctionKind::Mute
+            && member_permissions
+                .guild()
+                .contains(Permissions::ADMINISTRATOR)
+        {
+            return Ok(Some(embed::sanction::mute_member_administrator(lang)));
+        }
+
+        // Check if the role hierarchy allow the author and the bot to perform
+        // the sanction.
+        let member_highest_role = member_permissions.highest_role();
+
+        if member_highest_role >= author_permissions.highest_role() {
+            return Ok(Some(embed::sanction::user_hierarchy(self.kind, lang)));
+        }
+
+        if member_highest_role >= bot_permissions.highest_role() {
+            return Ok(Some(embed::sanction::bot_hierarchy(self.kind, lang)));
+        }
+
+        Ok(None)
+    }
+
+    /// Modal that asks the user to enter a reason for the sanction.
+    ///
+    /// This modal is only shown if the user has not specified a reason in the
+    /// initial command.
+    pub async fn reason_modal(
+        self,
+        enforce_reason: bool,
+        state: &ClusterState,
+        lang: Lang,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let username = self.user.name.truncate(15);
+        let (title, reason_label) = match self.kind {
+            SanctionKind::Kick => (
+                lang.modal_kick_title(username),
+                lang.modal_kick_reason_label(),
+            ),
+            SanctionKind::Ban => (
+                lang.modal_ban_title(username),
+                lang.modal_ban_reason_label(),
+            ),
+            SanctionKind::Mute => (
+                lang.modal_mute_title(username),
+                lang.modal_mute_reason_label(),
+            ),
+            SanctionKind::Warn => (
+                lang.modal_warn_title(username),
+                lang.modal_warn_reason_label(),
+            ),
+        };
+
+        let components = vec![
+            Component::ActionRow(ActionRow {
+                components: vec![Component::TextInput(TextInput {
+                    custom_id: "reason".to_string(),
+                    label: reason_label.to_string(),
+                    max_length: Some(MAX_REASON_LENGTH as u16),
+                    min_length: None,
+                    placeholder: Some(lang.modal_reason_placeholder().to_string()),
+                    required: Some(enforce_reason),
+                    style: TextInputStyle::Short,
+                    value: None,
+                })],
+            }),
+            Component::ActionRow(ActionRow {
+                components: vec![Component::TextInput(TextInput {
+                    custom_id: "notes".to_string(),
+                    label: lang.modal_notes_label().to_string(),
+                    max_length: Some(1000),
+                    min_length: None,
+                    placeholder: Some(lang.modal_notes_placeholder().to_string()),
+                    required: Some(false),
+                    style: TextInputStyle::Paragraph,
+                    value: None,
+                })],
+            }),
+        ];
+
+        // Add pending component in Redis
+        let custom_id = nanoid!();
+        let pending = PendingModal::Sanction(PendingSanction {
+            id: custom_id.clone(),
+            kind: self.kind,
+            user: self.user,
+            duration: self.duration.map(|duration| duration.whole_seconds()),
+            delete_message_days: self.delete_message_days,
+        });
+
+        state.redis().set(&pending).await?;
+
+        Ok(InteractionResponse::Modal {
+            custom_id,
+            title,
+            components,
+        })
+    }
+
+    /// Apply the sanction.
+    ///
+    /// The sanctioned user is notified with a private message before the
+    /// sanction is applied, and receives another message if the sanction
+    /// fails. The sanction is then stored in the database and logged in the
+    /// guild's logs channel.
+    ///
+    /// If the sanction is a warning, the escalation rules of the guild are
+    /// checked and the corresponding sanction is applied.
+    pub async fn apply(
+        self,
+        guild: &GuildInteraction<'_>,
+        config: &Guild,
+        state: &ClusterState,
+        lang: Lang,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let moderator = guild
+            .member
+            .user
+            .as_ref()
+            .context("missing interaction member user")?;
+
+        // The expiration date is computed again when executing the sanction,
+        // this only ensures that an out of range duration is reported.
+        if self.expires_at(OffsetDateTime::now_utc()).is_err() {
+            return Ok(embed::sanction::invalid_duration(lang));
+        }
+
+        let kind = self.kind;
+        let user = self.user.clone();
+        let modlog = self
+            .execute(guild.id, ModlogUser::from(moderator), config, state)
+            .await?;
+
+        // The warning has already been applied, so errors that occur during
+        // escalation are only logged.
+        if kind == SanctionKind::Warn {
+            if let Err(error) = escalate(&modlog, &user, config, state).await {
+                error!(error = ?error, "failed to apply warnings escalation");
+            }
+        }
+
+        Ok(embed::sanction::success(kind, user.name, lang))
+    }
+
+    /// Execute the sanction and store it in the database.
+    ///
+    /// This method does not check permissions nor apply escalation rules.
+    pub async fn execute(
+        self,
+        guild_id: Id<GuildMarker>,
+        moderator: ModlogUser,
+        config: &Guild,
+        state: &ClusterState,
+    ) -> Result<Modlog, anyhow::Error> {
+        let date = OffsetDateTime::now_utc();
+        let expires_at = self.expires_at(date)?;
+
+        // Send a private message to the user before the sanction, otherwise
+        // the bot may not be able to reach them anymore.
+        let notified = self
+            .notify(guild_id, expires_at, &moderator, config, state)
+            .await;
+
+        // If the sanction fails, the user is told to ignore the notification.
+        if let Err(error) = self.perform(guild_id, expires_at, state).await {
+            if notified {
+                self.notify_failed(guild_id, config, state).await;
+            }
+
+            return Err(error);
+        }
+
+        let modlog = Modlog {
+            id: None,
+            case: None,
+            kind: self.kind.into(),
+            guild_id,
+            user: ModlogUser::from(&self.user),
+            moderator,
+            date,
+            reason: self.reason,
+            notes: self.notes,
+            expires_at,
+            expired: false,
+            warns: self.warns,
+            revoked: None,
+            notified: Some(notified),
+            logs_message: None,
+            edits: Vec::new(),
+        };
+
+        store_modlog(modlog, config, state).await
+    }
+
+    /// Apply the sanction on Discord.
+    async fn perform(
+        &self,
+        guild_id: Id<GuildMarker>,
+        expires_at: Option<OffsetDateTime>,
+        state: &ClusterState,
+    ) -> Result<(), anyhow::Error> {
+        let http = state.cache_http(guild_id);
+
+        match self.kind {
+            SanctionKind::Kick => {
+                let mut request = http.remove_guild_member(self.user.id).await?;
+                if let Some(reason) = &self.reason {
+                    request = request.reason(reason)?;
+                }
+
+                request.exec().await?;
+            }
+            SanctionKind::Ban => {
+                let mut request = http.create_ban(self.user.id).await?;
+                if let Some(days) = self.delete_message_days {
+                    request = request.delete_message_days(days)?;
+                }
+                if let Some(reason) = &self.reason {
+                    request = request.reason(reason)?;
+                }
+
+                request.exec().await?;
+            }
+            SanctionKind::Mute => {
+                let expires_at = expires_at.context("missing mute duration")?;
+                let timestamp = Timestamp::from_secs(expires_at.unix_timestamp())?;
+
+                let mut request = http
+                    .update_guild_member(self.user.id)
+                    .await?
+                    .communication_disabled_until(Some(timestamp))?;
+                if let Some(reason) = &self.reason {
+                    request = request.reason(reason)?;
+                }
+
+                request.exec().await?;
+            }
+            // Warnings are only stored in the database.
+            SanctionKind::Warn => {}
+        }
+
+        Ok(())
+    }
+
+    /// Compute the expiration date of the sanction applied at `date`.
+    ///
+    /// Returns an error if the expiration date is out of range.
+    fn expires_at(&self, date: OffsetDateTime) -> Result<Option<OffsetDateTime>, anyhow::Error> {
+        self.duration
+            .map(|duration| {
+                date.checked_add(duration)
+                    .context("sanction duration is out of range")
+            })
+            .transpose()
+    }
+
+    /// Notify the user of the sanction by private message.
+    ///
+    /// Returns whether the message has been sent. Failures are not returned
+    /// since the sanction is applied even if the user cannot be notified.
+    async fn notify(
+        &self,
+        guild_id: Id<GuildMarker>,
+        expires_at: Option<OffsetDateTime>,
+        moderator: &ModlogUser,
+        config: &Guild,
+        state: &ClusterState,
+    ) -> bool {
+        let guild_name = match guild_name(guild_id, state).await {
+            Some(name) => name,
+            None => return false,
+        };
+        let dm_moderator = (!config.moderation.anonymize).then_some(moderator);
+        let dm_embed = embed::sanction::user_notification(
+            self.kind,
+            guild_name,
+            self.reason.clone(),
+            expires_at,
+            dm_moderator,
+            config.moderation.appeal_url.as_deref(),
+            Lang::from(&*config.lang),
+        );
+
+        match send_dm(self.user.id, dm_embed, state).await {
+            Ok(()) => true,
+            Err(error) => {
+                debug!(error = ?error, "failed to send sanction notification to {}", self.user.id);
+
+                false
+            }
+        }
+    }
+
+    /// Tell the user to ignore the notification of a sanction that could not
+    /// be applied.
+    async fn notify_failed(&self, guild_id: Id<GuildMarker>, config: &Guild, state: &ClusterState) {
+        let guild_name = match guild_name(guild_id, state).await {
+            Some(name) => name,
+            None => return,
+        };
+        let dm_embed =
+            embed::sanction::user_notification_failed(guild_name, Lang::from(&*config.lang));
+
+        if let Err(error) = send_dm(self.user.id, dm_embed, state).await {
+            debug!(error = ?error, "failed to send sanction failure notification to {}", self.user.id);
+        }
+    }
+}
+
+/// Get the name of a guild from the cache.
+async fn guild_name(guild_id: Id<GuildMarker>, state: &ClusterState) -> Option<String> {
+    match state.redis().get::<CachedGuild>(&guild_id).await {
+        Ok(Some(guild)) => Some(guild.name),
+        Ok(None) => {
+            debug!(guild = ?guild_id, "guild not found in cache");
+
+            None
+        }
+        Err(error) => {
+            error!(error = ?error, guild = ?guild_id, "failed to get guild from cache");
+
+            None
+        }
+    }
+}
+
+/// Record a member flagged by an automatic check in the modlogs.
+///
+/// Unlike sanctions, flags are not applied on Discord and the member is not
+/// notified. The flag is stored in the database and logged in the guild's
+/// logs channel.
+pub async fn flag_member(
+    user: &User,
+    reason: String,
+    notes: Option<String>,
+    guild_id: Id<GuildMarker>,
+    moderator: ModlogUser,
+    config: &Guild,
+    state: &ClusterState,
+) -> Result<Modlog, anyhow::Error> {
+    let modlog = Modlog {
+        id: None,
+        case: None,
+        kind: ModlogType::Flag,
+        guild_id,
+        user: ModlogUser::from(user),
+        moderator,
+        date: OffsetDateTime::now_utc(),
+        reason: Some(reason),
+        notes,
+        expires_at: None,
+        expired: false,
+        warns: Vec::new(),
+        revoked: None,
+        notified: None,
+        logs_message: None,
+        edits: Vec::new(),
+    };
+
+    store_modlog(modlog, config, state).await
+}
+
+/// Store a modlog in the database and log it in the guild's logs channel.
+async fn store_modlog(
+    mut modlog: Modlog,
+    config: &Guild,
+    state: &ClusterState,
+) -> Result<Modlog, anyhow::Error> {
+    let guild_id = modlog.guild_id;
+    let guild_lang = Lang::from(&*config.lang);
+
+    state.mongodb().create_modlog(&mut modlog).await?;
+
+    // Send a message in the logs channel.
+    let logs_chan = guild_logs_channel(guild_id, config.logs_chan, state, guild_lang).await?;
+    let logs_embed = embed::sanction::logs_message(&modlog, guild_lang);
+
+    let logs_message = state
+        .cache_http(guild_id)
+        .create_message(logs_chan)
+        .await?
+        .embeds(&[logs_embed])?
+        .exec()
+        .await?
+        .model()
+        .await?;
+
+    // Store the logs message to allow editing it later.
+    let logs_message = ModlogMessage {
+        channel_id: logs_chan,
+        message_id: logs_message.id,
+    };
+    let id = modlog.id.context("missing modlog id")?;
+
+    state
+        .mongodb()
+        .set_modlog_logs_message(id, logs_message)
+        .await?;
+    modlog.logs_message = Some(logs_message);
+
+    Ok(modlog)
+}
+
+/// Permission required to apply a sanction.
+fn required_permission(kind: SanctionKind) -> Permissions {
+    match kind {
+        SanctionKind::Kick => Permissions::KICK_MEMBERS,
+        SanctionKind::Ban => Permissions::BAN_MEMBERS,
+        SanctionKind::Mute => Permissions::MODERATE_MEMBERS,
+        SanctionKind::Warn => Permissions::empty(),
+    }
+}
+
+/// Send an embed as private message to a user.
+async fn send_dm(
+    user_id: Id<UserMarker>,
+    embed: Embed,
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    let channel = state
+        .http()
+        .create_private_channel(user_id)
+        .exec()
+        .await?
+        .model()
+        .await?;
+
+    state
+        .http()
+        .create_message(channel.id)
+        .embeds(&[embed])?
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse_duration;
+
+    fn user() -> User {
+        User {
+            accent_color: None,
+            avatar: None,
+            banner: None,
+            bot: false,
+            discriminator: 1,
+            email: None,
+            flags: None,
+            id: Id::new(1),
+            locale: None,
+            mfa_enabled: None,
+            name: "user".to_string(),
+            premium_type: None,
+            public_flags: None,
+            system: None,
+            verified: None,
+        }
+    }
+
+    #[test]
+    fn test_expires_at() {
+        let date = OffsetDateTime::now_utc();
+        let sanction = Sanction {
+            duration: parse_duration("1d"),
+            ..Sanction::new(SanctionKind::Ban, user())
+        };
+
+        assert_eq!(
+            sanction.expires_at(date).unwrap(),
+            Some(date + Duration::days(1))
+        );
+        assert_eq!(
+            Sanction::new(SanctionKind::Kick, user())
+                .expires_at(date)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_expires_at_out_of_range() {
+        let sanction = Sanction {
+            duration: parse_duration("99999999w"),
+            ..Sanction::new(SanctionKind::Ban, user())
+        };
+
+        assert!(sanction.expires_at(OffsetDateTime::now_utc()).is_err());
+    }
+}
@@ -0,0 +1,84 @@
+//! Unban command.
+//!
+//! The command allows to revoke the ban of a user. If the ban has been issued
+//! by the bot, the corresponding modlog is marked as revoked so that temporary
+//! bans are not lifted again once expired.
+
+use anyhow::Context;
+use raidprotect_model::mongodb::modlog::{ModlogType, SanctionKind};
+use twilight_http::{error::ErrorType, request::AuditLogReason};
+use twilight_interactions::command::{CommandModel, CreateCommand, ResolvedUser};
+use twilight_model::{application::interaction::Interaction, guild::Permissions};
+
+use super::{revoke::revoke_sanction, Sanction};
+use crate::{
+    cluster::ClusterState,
+    desc_localizations, impl_command_handle,
+    interaction::{embed, response::InteractionResponse, util::InteractionExt},
+};
+
+/// Unban command model.
+///
+/// See the [`module`][self] documentation for more information.
+#[derive(Debug, Clone, CommandModel, CreateCommand)]
+#[command(
+    name = "unban",
+    desc = "Revokes the ban of a user",
+    desc_localizations = "unban_description",
+    default_permissions = "UnbanCommand::default_permissions",
+    dm_permission = false
+)]
+pub struct UnbanCommand {
+    /// User to unban.
+    pub user: ResolvedUser,
+    /// Reason for unban.
+    pub reason: Option<String>,
+}
+
+impl_command_handle!(UnbanCommand);
+desc_localizations!(unban_description);
+
+impl UnbanCommand {
+    fn default_permissions() -> Permissions {
+        Permissions::BAN_MEMBERS
+    }
+
+    async fn exec(
+        self,
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let guild = interaction.guild()?;
+        let author_id = interaction.author_id().context("missing author_id")?;
+        let lang = interaction.locale()?;
+        let user = self.user.resolved;
+
+        // Only the bot permissions are checked since the user is not a member.
+        let sanction = Sanction::new(SanctionKind::Ban, user);
+        if let Some(response) = sanction
+            .check_permissions(&guild, author_id, None, state, lang)
+            .await?
+        {
+            return Ok(response);
+        }
+
+        let user = sanction.user;
+        let mut request = state.cache_http(guild.id).delete_ban(user.id).await?;
+        if let Some(reason) = &self.reason {
+            request = request.reason(reason)?;
+        }
+
+        if let Err(error) = request.exec().await {
+            return match error.kind() {
+                ErrorType::Response { status, .. } if status.get() == 404 => {
+                    Ok(embed::sanction::not_banned(user.name, lang))
+                }
+                _ => Err(error.into()),
+            };
+        }
+
+        revoke_sanction(ModlogType::Ban, &user, self.reason, &guild, state).await?;
+
+        Ok(embed::sanction::unban_success(user.name, lang))
+    }
+}
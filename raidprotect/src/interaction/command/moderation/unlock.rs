@@ -0,0 +1,162 @@
+//! Unlock command.
+//!
+//! The command unlocks channels locked with the `lockdown` command, and
+//! restores the permission overwrite of the `@everyone` role saved in the
+//! database. Channels that have not been locked with the bot are ignored.
+//!
+//! As for the `lockdown` command, the response to this command is deferred.
+
+use futures::TryStreamExt;
+use raidprotect_model::{
+    cache::model::CachedChannel,
+    mongodb::lockdown::{Lockdown, LockdownOverwrite},
+};
+use tracing::warn;
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    application::interaction::{application_command::InteractionChannel, Interaction},
+    guild::Permissions,
+    id::{marker::GuildMarker, Id},
+};
+
+use super::lockdown::{everyone_overwrite, LockdownReport, LockdownScope};
+use crate::{
+    cluster::ClusterState,
+    desc_localizations, impl_command_handle,
+    interaction::{embed, response::InteractionResponse, util::InteractionExt},
+};
+
+/// Unlock command model.
+///
+/// See the [`module`][self] documentation for more information.
+#[derive(Debug, Clone, CommandModel, CreateCommand)]
+#[command(
+    name = "unlock",
+    desc = "Unlocks a channel, a category or the server after a lockdown",
+    desc_localizations = "unlock_description",
+    default_permissions = "UnlockCommand::default_permissions",
+    dm_permission = false
+)]
+pub struct UnlockCommand {
+    /// Channel or category to unlock, defaults to the current channel.
+    #[command(channel_types = "guild_text guild_news guild_category")]
+    pub channel: Option<InteractionChannel>,
+    /// Unlock all the channels of the server.
+    pub server: Option<bool>,
+}
+
+impl_command_handle!(UnlockCommand);
+desc_localizations!(unlock_description);
+
+impl UnlockCommand {
+    fn default_permissions() -> Permissions {
+        Permissions::MANAGE_CHANNELS
+    }
+
+    async fn exec(
+        self,
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let guild = interaction.guild()?;
+        let lang = interaction.locale()?;
+        let scope = LockdownScope::new(
+            self.channel.map(|channel| channel.id),
+            self.server.unwrap_or(false),
+            &interaction,
+        )?;
+
+        let moderator = match scope.check_permissions(&guild, state).await? {
+            Some(moderator) => moderator,
+            None => return Ok(embed::lockdown::missing_permission(lang)),
+        };
+
+        let mut report = LockdownReport::default();
+
+        for lockdown in find_lockdowns(guild.id, scope, &mut report, state).await? {
+            // Lockdowns of deleted channels are removed.
+            if state
+                .redis()
+                .get::<CachedChannel>(&lockdown.channel_id)
+                .await?
+                .is_none()
+            {
+                state.mongodb().delete_lockdown(lockdown.channel_id).await?;
+                continue;
+            }
+
+            match unlock_channel(&lockdown, state).await {
+                Ok(()) => report.updated.push(lockdown.channel_id),
+                Err(error) => {
+                    warn!(error = ?error, "failed to unlock channel {}", lockdown.channel_id);
+                    report.failed.push(lockdown.channel_id);
+                }
+            }
+        }
+
+        report.send_logs(guild.id, moderator, false, state).await?;
+
+        Ok(embed::lockdown::report(&report, false, lang))
+    }
+}
+
+/// Find the lockdowns of the channels targeted by a scope.
+///
+/// Targeted channels that are not locked are counted as skipped in the
+/// report.
+async fn find_lockdowns(
+    guild_id: Id<GuildMarker>,
+    scope: LockdownScope,
+    report: &mut LockdownReport,
+    state: &ClusterState,
+) -> Result<Vec<Lockdown>, anyhow::Error> {
+    if scope == LockdownScope::Server {
+        return Ok(state
+            .mongodb()
+            .find_lockdowns(guild_id)
+            .await?
+            .try_collect()
+            .await?);
+    }
+
+    let mut lockdowns = Vec::new();
+
+    for channel in scope.channels(guild_id, state).await? {
+        match state.mongodb().get_lockdown(channel.id).await? {
+            Some(lockdown) => lockdowns.push(lockdown),
+            None => report.skipped += 1,
+        }
+    }
+
+    Ok(lockdowns)
+}
+
+/// Restore the permission overwrite of a locked channel.
+///
+/// The overwrite of the `@everyone` role is deleted if the channel had none
+/// before the lockdown.
+async fn unlock_channel(lockdown: &Lockdown, state: &ClusterState) -> Result<(), anyhow::Error> {
+    let http = state.cache_http(lockdown.guild_id);
+
+    match lockdown.overwrite {
+        Some(LockdownOverwrite { allow, deny }) => {
+            let overwrite = everyone_overwrite(lockdown.guild_id, allow, deny);
+
+            http.update_channel_permission(lockdown.channel_id, &overwrite)
+                .await?
+                .exec()
+                .await?;
+        }
+        None => {
+            http.delete_channel_permission(lockdown.channel_id)
+                .await?
+                .role(lockdown.guild_id.cast())
+                .exec()
+                .await?;
+        }
+    }
+
+    state.mongodb().delete_lockdown(lockdown.channel_id).await?;
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! Unmute command.
+//!
+//! The command allows to revoke the mute (timeout) of a member. If the mute
+//! has been issued by the bot, the corresponding modlog is marked as revoked.
+
+use anyhow::Context;
+use raidprotect_model::mongodb::modlog::{ModlogType, SanctionKind};
+use time::OffsetDateTime;
+use twilight_http::request::AuditLogReason;
+use twilight_interactions::command::{CommandModel, CreateCommand, ResolvedUser};
+use twilight_model::{application::interaction::Interaction, guild::Permissions};
+
+use super::{revoke::revoke_sanction, Sanction};
+use crate::{
+    cluster::ClusterState,
+    desc_localizations, impl_command_handle,
+    interaction::{embed, response::InteractionResponse, util::InteractionExt},
+};
+
+/// Unmute command model.
+///
+/// See the [`module`][self] documentation for more information.
+#[derive(Debug, Clone, CommandModel, CreateCommand)]
+#[command(
+    name = "unmute",
+    desc = "Revokes the mute of a member",
+    desc_localizations = "unmute_description",
+    default_permissions = "UnmuteCommand::default_permissions",
+    dm_permission = false
+)]
+pub struct UnmuteCommand {
+    /// Member to unmute.
+    #[command(rename = "member")]
+    pub user: ResolvedUser,
+    /// Reason for unmute.
+    pub reason: Option<String>,
+}
+
+impl_command_handle!(UnmuteCommand);
+desc_localizations!(unmute_description);
+
+impl UnmuteCommand {
+    fn default_permissions() -> Permissions {
+        Permissions::MODERATE_MEMBERS
+    }
+
+    async fn exec(
+        self,
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let guild = interaction.guild()?;
+        let author_id = interaction.author_id().context("missing author_id")?;
+
+        let user = self.user.resolved;
+        let lang = interaction.locale()?;
+        let member = match self.user.member {
+            Some(member) => member,
+            None => return Ok(embed::sanction::not_member(user.name, lang)),
+        };
+
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let is_muted = member
+            .communication_disabled_until
+            .map(|until| until.as_secs() > now)
+            .unwrap_or(false);
+
+        if !is_muted {
+            return Ok(embed::sanction::not_muted(user.name, lang));
+        }
+
+        // The role hierarchy also applies when removing a timeout.
+        let sanction = Sanction::new(SanctionKind::Mute, user);
+        if let Some(response) = sanction
+            .check_permissions(&guild, author_id, Some(&member.roles), state, lang)
+            .await?
+        {
+            return Ok(response);
+        }
+
+        let user = sanction.user;
+        let mut request = state
+            .cache_http(guild.id)
+            .update_guild_member(user.id)
+            .await?
+            .communication_disabled_until(None)?;
+        if let Some(reason) = &self.reason {
+            request = request.reason(reason)?;
+        }
+
+        request.exec().await?;
+
+        revoke_sanction(ModlogType::Mute, &user, self.reason, &guild, state).await?;
+
+        Ok(embed::sanction::unmute_success(user.name, lang))
+    }
+}
@@ -0,0 +1,96 @@
+//! Warn command.
+//!
+//! The command allows to warn a member of the server. Warnings are lightweight
+//! sanctions that are only stored in the database, but the guild can configure
+//! escalation rules to automatically sanction members that receive too many
+//! warnings (see the [`escalation`] module). User can specify a reason directly
+//! in the command (as an optional parameter), or in the modal that is shown if
+//! it hasn't been set in the command.
+//!
+//! When a user is warned, the action is logged in the database and a message is
+//! sent in the guild's logs channel. The warned user receives a pm with the
+//! reason of the warning.
+//!
+//! [`escalation`]: super::escalation
+
+use anyhow::Context;
+use raidprotect_model::mongodb::modlog::SanctionKind;
+use twilight_interactions::command::{CommandModel, CreateCommand, ResolvedUser};
+use twilight_model::{application::interaction::Interaction, guild::Permissions};
+
+use super::Sanction;
+use crate::{
+    cluster::ClusterState,
+    desc_localizations, impl_command_handle,
+    interaction::{embed, response::InteractionResponse, util::InteractionExt},
+};
+
+/// Warn command model.
+///
+/// See the [`module`][self] documentation for more information.
+#[derive(Debug, Clone, CommandModel, CreateCommand)]
+#[command(
+    name = "warn",
+    desc = "Warns a member of the server",
+    desc_localizations = "warn_description",
+    default_permissions = "WarnCommand::default_permissions",
+    dm_permission = false
+)]
+pub struct WarnCommand {
+    /// Member to warn.
+    #[command(rename = "member")]
+    pub user: ResolvedUser,
+    /// Reason for warning.
+    #[command(max_length = 100)]
+    pub reason: Option<String>,
+}
+
+impl_command_handle!(WarnCommand);
+desc_localizations!(warn_description);
+
+impl WarnCommand {
+    fn default_permissions() -> Permissions {
+        Permissions::MODERATE_MEMBERS
+    }
+
+    async fn exec(
+        self,
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let guild = interaction.guild()?;
+        let author_id = interaction.author_id().context("missing author_id")?;
+
+        let user = self.user.resolved;
+        let lang = interaction.locale()?;
+        let member = match self.user.member {
+            Some(member) => member,
+            None => return Ok(embed::sanction::not_member(user.name, lang)),
+        };
+
+        let sanction = Sanction {
+            reason: self.reason,
+            ..Sanction::new(SanctionKind::Warn, user)
+        };
+
+        if let Some(response) = sanction
+            .check_permissions(&guild, author_id, Some(&member.roles), state, lang)
+            .await?
+        {
+            return Ok(response);
+        }
+
+        // Warn the member directly if a reason is provided, otherwise show the
+        // reason modal.
+        let config = state.mongodb().get_guild_or_create(guild.id).await?;
+
+        match sanction.reason {
+            Some(_) => sanction.apply(&guild, &config, state, lang).await,
+            None => {
+                let enforce_reason = config.moderation.enforce_reason;
+
+                sanction.reason_modal(enforce_reason, state, lang).await
+            }
+        }
+    }
+}
@@ -0,0 +1,383 @@
+//! Captcha verification components.
+//!
+//! Members joining a guild with the captcha enabled are given the unverified
+//! role, and must complete the verification from the message sent in the
+//! verification channel:
+//!
+//! - the "Verify" button of this message sends an ephemeral message with a
+//!   captcha image.
+//! - the "Enter the code" button of the ephemeral message opens a modal where
+//!   the member types the code shown in the image.
+//! - if the code is correct, the unverified role is replaced by the verified
+//!   roles. Otherwise, a new image is sent until the member reaches the
+//!   maximum number of attempts and is kicked.
+//!
+//! The verification message is shared by all members, so the components use
+//! static custom ids and the state of each member is stored in a
+//! [`PendingCaptcha`].
+
+use anyhow::Context;
+use raidprotect_captcha::{
+    code::random_human_code, generate_captcha_png_with, CaptchaOptions, Difficulty,
+};
+use raidprotect_model::{
+    cache::model::captcha::{PendingCaptcha, PendingCaptchaId},
+    mongodb::guild::{CaptchaDifficulty, Guild},
+};
+use twilight_http::{error::ErrorType, request::AuditLogReason};
+use twilight_model::{
+    application::{
+        component::{
+            button::ButtonStyle, text_input::TextInputStyle, ActionRow, Button, Component,
+            TextInput,
+        },
+        interaction::Interaction,
+    },
+    channel::{embed::Embed, message::MessageFlags, ReactionType},
+    http::{attachment::Attachment, interaction::InteractionResponseType},
+    id::{
+        marker::{ChannelMarker, GuildMarker, MessageMarker},
+        Id,
+    },
+};
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::{
+    cluster::ClusterState,
+    interaction::{
+        embed,
+        response::InteractionResponse,
+        util::{parse_modal_data, parse_modal_field, InteractionExt},
+    },
+    translations::Lang,
+};
+
+/// Custom id of the button of the verification message.
+pub const VERIFY_BUTTON_ID: &str = "captcha-verify";
+
+/// Custom id of the button that opens the code modal.
+pub const CODE_BUTTON_ID: &str = "captcha-code";
+
+/// Custom id of the code modal.
+pub const CODE_MODAL_ID: &str = "captcha-modal";
+
+/// Length of the captcha codes.
+const CODE_LENGTH: usize = 5;
+
+/// Maximum number of incorrect codes before the member is kicked.
+const MAX_ATTEMPTS: u8 = 3;
+
+/// File name of the captcha image.
+const IMAGE_NAME: &str = "captcha.png";
+
+/// Reason of a failed captcha verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptchaFailure {
+    /// The member entered too many incorrect codes.
+    Attempts,
+    /// The member did not complete the verification in time.
+    Timeout,
+}
+
+/// "Verify" button of the verification message.
+pub struct CaptchaVerify;
+
+impl CaptchaVerify {
+    /// Send the verification message in a channel.
+    ///
+    /// The `lang` argument should be the guild language. Returns the id of the
+    /// sent message.
+    pub async fn send_message(
+        guild_id: Id<GuildMarker>,
+        channel: Id<ChannelMarker>,
+        state: &ClusterState,
+        lang: Lang,
+    ) -> Result<Id<MessageMarker>, anyhow::Error> {
+        let embed = embed::captcha::verification_message(lang);
+        let components = [Component::ActionRow(ActionRow {
+            components: vec![Component::Button(Button {
+                custom_id: Some(VERIFY_BUTTON_ID.to_string()),
+                disabled: false,
+                emoji: Some(ReactionType::Unicode {
+                    name: "✅".to_string(),
+                }),
+                label: Some(lang.captcha_verify_button().to_string()),
+                style: ButtonStyle::Success,
+                url: None,
+            })],
+        })];
+
+        let message = state
+            .cache_http(guild_id)
+            .create_message(channel)
+            .await?
+            .embeds(&[embed])?
+            .components(&components)?
+            .exec()
+            .await?
+            .model()
+            .await?;
+
+        Ok(message.id)
+    }
+
+    /// Handle the button click.
+    ///
+    /// A new captcha image is sent to the member.
+    pub async fn handle(
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let lang = interaction.locale()?;
+        let pending = match pending_captcha(&interaction, state).await? {
+            Some(pending) => pending,
+            None => return Ok(embed::captcha::not_pending(lang)),
+        };
+
+        let config = state
+            .mongodb()
+            .get_guild_or_create(pending.guild_id)
+            .await?;
+
+        challenge(pending, None, &config, state, lang).await
+    }
+}
+
+/// "Enter the code" button and modal.
+pub struct CaptchaCode;
+
+impl CaptchaCode {
+    /// Handle the button click.
+    ///
+    /// A modal is opened for the member to type the captcha code.
+    pub async fn handle(
+        interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let lang = interaction.locale()?;
+
+        if pending_captcha(&interaction, state).await?.is_none() {
+            return Ok(embed::captcha::not_pending(lang));
+        }
+
+        let components = vec![Component::ActionRow(ActionRow {
+            components: vec![Component::TextInput(TextInput {
+                custom_id: "code".to_string(),
+                label: lang.captcha_modal_label().to_string(),
+                max_length: Some(CODE_LENGTH as u16),
+                min_length: Some(CODE_LENGTH as u16),
+                placeholder: None,
+                required: Some(true),
+                style: TextInputStyle::Short,
+                value: None,
+            })],
+        })];
+
+        Ok(InteractionResponse::Modal {
+            custom_id: CODE_MODAL_ID.to_string(),
+            title: lang.captcha_modal_title().to_string(),
+            components,
+        })
+    }
+
+    /// Handle the modal submit.
+    pub async fn handle_modal(
+        mut interaction: Interaction,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let data = parse_modal_data(&mut interaction)?;
+        let lang = interaction.locale()?;
+
+        let mut pending = match pending_captcha(&interaction, state).await? {
+            Some(pending) => pending,
+            None => return Ok(embed::captcha::not_pending(lang)),
+        };
+
+        let code = parse_modal_field(&data, "code").unwrap_or_default();
+        let config = state
+            .mongodb()
+            .get_guild_or_create(pending.guild_id)
+            .await?;
+
+        if pending
+            .code
+            .as_deref()
+            .is_some_and(|expected| expected.eq_ignore_ascii_case(code.trim()))
+        {
+            verify_member(pending.id(), &config, state).await?;
+
+            return Ok(embed::captcha::verified(lang));
+        }
+
+        pending.attempts += 1;
+
+        if pending.attempts >= MAX_ATTEMPTS {
+            kick_member(pending.id(), CaptchaFailure::Attempts, &config, state).await?;
+
+            return Ok(embed::captcha::failed(lang));
+        }
+
+        let remaining = MAX_ATTEMPTS - pending.attempts;
+        challenge(pending, Some(remaining), &config, state, lang).await
+    }
+}
+
+/// Kick a member that failed the captcha verification.
+///
+/// The pending verification is removed, and the kick is reported in the
+/// captcha logs channel. Members that already left the guild are ignored.
+pub async fn kick_member(
+    id: PendingCaptchaId,
+    failure: CaptchaFailure,
+    config: &Guild,
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    let guild_lang = Lang::from(&*config.lang);
+    let reason = match failure {
+        CaptchaFailure::Attempts => guild_lang.captcha_attempts_reason(),
+        CaptchaFailure::Timeout => guild_lang.captcha_timeout_reason(),
+    };
+
+    state.redis().remove_pending_captcha(id).await?;
+
+    let request = state
+        .cache_http(id.guild_id)
+        .remove_guild_member(id.user_id)
+        .await?
+        .reason(reason)?;
+
+    if let Err(error) = request.exec().await {
+        return match error.kind() {
+            ErrorType::Response { status, .. } if status.get() == 404 => Ok(()),
+            _ => Err(error.into()),
+        };
+    }
+
+    let logs_embed = embed::captcha::logs_message(id.user_id, Some(failure), guild_lang);
+    send_logs(id.guild_id, config, logs_embed, state).await
+}
+
+/// Replace the unverified role of a member by the verified roles.
+async fn verify_member(
+    id: PendingCaptchaId,
+    config: &Guild,
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    let guild_lang = Lang::from(&*config.lang);
+    let reason = guild_lang.captcha_verified_reason();
+    let http = state.cache_http(id.guild_id);
+
+    for role in &config.captcha.verified_roles {
+        http.add_guild_member_role(id.user_id, *role)
+            .await?
+            .reason(reason)?
+            .exec()
+            .await?;
+    }
+
+    if let Some(role) = config.captcha.role {
+        http.remove_guild_member_role(id.user_id, role)
+            .await?
+            .reason(reason)?
+            .exec()
+            .await?;
+    }
+
+    state.redis().remove_pending_captcha(id).await?;
+
+    let logs_embed = embed::captcha::logs_message(id.user_id, None, guild_lang);
+    send_logs(id.guild_id, config, logs_embed, state).await
+}
+
+/// Send a new captcha image to a member.
+///
+/// The previous code of the member is replaced with the new one. The image is
+/// generated with the difficulty configured for the guild.
+async fn challenge(
+    mut pending: PendingCaptcha,
+    remaining: Option<u8>,
+    config: &Guild,
+    state: &ClusterState,
+    lang: Lang,
+) -> Result<InteractionResponse, anyhow::Error> {
+    let code = random_human_code(CODE_LENGTH);
+    let options = captcha_options(config.captcha.difficulty);
+    let image = {
+        let code = code.clone();
+        tokio::task::spawn_blocking(move || {
+            generate_captcha_png_with(&code, &options, &mut rand::thread_rng())
+        })
+        .await??
+    };
+
+    pending.code = Some(code);
+    state.redis().set(&pending).await?;
+
+    let button = Component::Button(Button {
+        custom_id: Some(CODE_BUTTON_ID.to_string()),
+        disabled: false,
+        emoji: None,
+        label: Some(lang.captcha_code_button().to_string()),
+        style: ButtonStyle::Primary,
+        url: None,
+    });
+
+    let data = InteractionResponseDataBuilder::new()
+        .embeds([embed::captcha::challenge(IMAGE_NAME, remaining, lang)?])
+        .components([Component::ActionRow(ActionRow {
+            components: vec![button],
+        })])
+        .attachments([Attachment::from_bytes(IMAGE_NAME.to_string(), image, 0)])
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+
+    Ok(InteractionResponse::Raw {
+        kind: InteractionResponseType::ChannelMessageWithSource,
+        data: Some(data),
+    })
+}
+
+/// Get the captcha generation options of a difficulty.
+fn captcha_options(difficulty: CaptchaDifficulty) -> CaptchaOptions {
+    let difficulty = match difficulty {
+        CaptchaDifficulty::Easy => Difficulty::Easy,
+        CaptchaDifficulty::Medium => Difficulty::Medium,
+        CaptchaDifficulty::Hard => Difficulty::Hard,
+    };
+
+    CaptchaOptions::new(difficulty)
+}
+
+/// Get the pending verification of the interaction author.
+async fn pending_captcha(
+    interaction: &Interaction,
+    state: &ClusterState,
+) -> Result<Option<PendingCaptcha>, anyhow::Error> {
+    let guild = interaction.guild()?;
+    let id = PendingCaptchaId {
+        guild_id: guild.id,
+        user_id: interaction.author_id().context("missing author_id")?,
+    };
+
+    state.redis().get::<PendingCaptcha>(&id).await
+}
+
+/// Send a message in the captcha logs channel, if configured.
+async fn send_logs(
+    guild_id: Id<GuildMarker>,
+    config: &Guild,
+    embed: Embed,
+    state: &ClusterState,
+) -> Result<(), anyhow::Error> {
+    if let Some(logs_chan) = config.captcha.logs {
+        state
+            .cache_http(guild_id)
+            .create_message(logs_chan)
+            .await?
+            .embeds(&[embed])?
+            .exec()
+            .await?;
+    }
+
+    Ok(())
+}
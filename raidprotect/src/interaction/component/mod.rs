@@ -1,3 +1,7 @@
+pub mod captcha;
+mod modlogs_page;
 mod post_in_chat;
 
+pub use captcha::{CaptchaCode, CaptchaFailure, CaptchaVerify};
+pub use modlogs_page::ModlogsPage;
 pub use post_in_chat::PostInChat;
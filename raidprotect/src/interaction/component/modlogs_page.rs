@@ -0,0 +1,151 @@
+//! Modlogs pagination buttons.
+//!
+//! This module implement the previous and next buttons of the modlogs command,
+//! that allow users to navigate between pages of modlogs.
+
+use futures::TryStreamExt;
+use nanoid::nanoid;
+use raidprotect_model::{
+    cache::model::interaction::{ModlogsPageButton, PendingComponent},
+    mongodb::modlog::Modlog,
+};
+use twilight_model::{
+    application::{
+        component::{button::ButtonStyle, ActionRow, Button, Component},
+        interaction::Interaction,
+    },
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponseData, InteractionResponseType},
+    id::{marker::GuildMarker, Id},
+    user::User,
+};
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::{
+    cluster::ClusterState,
+    interaction::{embed, response::InteractionResponse, util::InteractionExt},
+    translations::Lang,
+};
+
+/// Number of modlogs shown per page.
+const PAGE_SIZE: u64 = 5;
+
+/// Paginated list of modlogs of a user.
+pub struct ModlogsPage;
+
+impl ModlogsPage {
+    /// Create the response with the first page of modlogs of a user.
+    pub async fn create(
+        guild_id: Id<GuildMarker>,
+        user: User,
+        state: &ClusterState,
+        lang: Lang,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let mut data = Self::page(guild_id, user, 0, state, lang).await?;
+        data.flags = Some(MessageFlags::EPHEMERAL);
+
+        Ok(InteractionResponse::Raw {
+            kind: InteractionResponseType::ChannelMessageWithSource,
+            data: Some(data),
+        })
+    }
+
+    /// Handle the button click.
+    pub async fn handle(
+        interaction: Interaction,
+        component: ModlogsPageButton,
+        state: &ClusterState,
+    ) -> Result<InteractionResponse, anyhow::Error> {
+        let lang = interaction.locale()?;
+        let data = Self::page(
+            component.guild_id,
+            component.user,
+            component.page,
+            state,
+            lang,
+        )
+        .await?;
+
+        Ok(InteractionResponse::Raw {
+            kind: InteractionResponseType::UpdateMessage,
+            data: Some(data),
+        })
+    }
+
+    /// Build the message of a given page.
+    ///
+    /// Pages start at zero. If the page does not exist, the last page is shown.
+    async fn page(
+        guild_id: Id<GuildMarker>,
+        user: User,
+        page: u64,
+        state: &ClusterState,
+        lang: Lang,
+    ) -> Result<InteractionResponseData, anyhow::Error> {
+        let total = state
+            .mongodb()
+            .count_modlogs(guild_id, Some(user.id))
+            .await?;
+        let total_pages = total.div_ceil(PAGE_SIZE).max(1);
+        let page = page.min(total_pages - 1);
+
+        let modlogs: Vec<Modlog> = state
+            .mongodb()
+            .find_modlogs(guild_id, Some(user.id), page * PAGE_SIZE, PAGE_SIZE as i64)
+            .await?
+            .try_collect()
+            .await?;
+
+        let embed = embed::modlogs::modlogs_page(&user, &modlogs, page, total_pages, lang);
+        let mut data = InteractionResponseDataBuilder::new().embeds([embed]);
+
+        // Add pagination buttons if there is more than one page.
+        if total_pages > 1 {
+            let previous = page.checked_sub(1);
+            let next = Some(page + 1).filter(|next| *next < total_pages);
+
+            let components = vec![
+                Self::button(guild_id, &user, previous, lang.modlogs_previous(), state).await?,
+                Self::button(guild_id, &user, next, lang.modlogs_next(), state).await?,
+            ];
+
+            data = data.components([Component::ActionRow(ActionRow { components })]);
+        }
+
+        Ok(data.build())
+    }
+
+    /// Create a pagination button.
+    ///
+    /// The button is disabled if the target page is [`None`].
+    async fn button(
+        guild_id: Id<GuildMarker>,
+        user: &User,
+        page: Option<u64>,
+        label: &str,
+        state: &ClusterState,
+    ) -> Result<Component, anyhow::Error> {
+        // Store button state in redis
+        let custom_id = nanoid!();
+
+        if let Some(page) = page {
+            let component = PendingComponent::ModlogsPage(ModlogsPageButton {
+                id: custom_id.clone(),
+                guild_id,
+                user: user.clone(),
+                page,
+            });
+
+            state.redis().set(&component).await?;
+        }
+
+        Ok(Component::Button(Button {
+            custom_id: Some(custom_id),
+            disabled: page.is_none(),
+            emoji: None,
+            label: Some(label.to_string()),
+            style: ButtonStyle::Secondary,
+            url: None,
+        }))
+    }
+}
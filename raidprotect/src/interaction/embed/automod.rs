@@ -0,0 +1,120 @@
+//! Embeds for the auto-moderation modules.
+
+use raidprotect_model::{cache::model::message::CachedMessage, mongodb::modlog::ModlogType};
+use time::OffsetDateTime;
+use twilight_mention::Mention;
+use twilight_model::{channel::embed::Embed, user::User, util::Timestamp};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder, EmbedFooterBuilder};
+
+use super::{modlogs::kind_name, COLOR_RED};
+use crate::{translations::Lang, util::TextProcessExt};
+
+/// Maximum number of messages shown in the logs message.
+const MAX_LOGGED_MESSAGES: usize = 5;
+
+/// Message sent in the guild's logs channel after a rule violation
+pub fn logs_message(
+    user: &User,
+    messages: &[CachedMessage],
+    reason: &str,
+    deleted: bool,
+    sanction: Option<ModlogType>,
+    lang: Lang,
+) -> Embed {
+    let member = format!(
+        "{} ({}#{:04})",
+        user.id.mention(),
+        user.name.remove_markdown(),
+        user.discriminator
+    );
+
+    let mut actions = Vec::new();
+    if deleted {
+        actions.push(lang.automod_action_delete());
+    }
+    if let Some(kind) = sanction {
+        actions.push(kind_name(kind, lang));
+    }
+    if actions.is_empty() {
+        actions.push(lang.automod_action_none());
+    }
+
+    let mut embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(lang.automod_logs_title())
+        .description(reason)
+        .field(EmbedFieldBuilder::new(lang.sanction_user(), member).inline())
+        .field(EmbedFieldBuilder::new(lang.automod_logs_action(), actions.join(", ")).inline())
+        .footer(EmbedFooterBuilder::new(format!("ID: {}", user.id)));
+
+    let content = messages
+        .iter()
+        .rev()
+        .take(MAX_LOGGED_MESSAGES)
+        .rev()
+        .map(|message| {
+            let content = message.content.remove_markdown().truncate(150);
+
+            format!("{} {}", message.channel_id.mention(), content)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if !content.is_empty() {
+        embed = embed.field(EmbedFieldBuilder::new(
+            lang.automod_logs_messages(messages.len()),
+            content,
+        ));
+    }
+
+    if let Ok(timestamp) = Timestamp::from_secs(OffsetDateTime::now_utc().unix_timestamp()) {
+        embed = embed.timestamp(timestamp);
+    }
+
+    embed.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use rosetta_i18n::Language;
+    use twilight_model::id::Id;
+
+    use super::*;
+    use crate::event::message::test_message;
+
+    #[test]
+    fn test_logs_message() {
+        let user = User {
+            accent_color: None,
+            avatar: None,
+            banner: None,
+            bot: false,
+            discriminator: 1,
+            email: None,
+            flags: None,
+            id: Id::new(1),
+            locale: None,
+            mfa_enabled: None,
+            name: "user".to_string(),
+            premium_type: None,
+            public_flags: None,
+            system: None,
+            verified: None,
+        };
+        let message = CachedMessage {
+            author_id: Id::new(1),
+            content: "spam".to_string(),
+            words: vec!["spam".to_string()],
+            ..test_message()
+        };
+
+        logs_message(
+            &user,
+            &[message],
+            "reason",
+            true,
+            Some(ModlogType::Mute),
+            Lang::fallback(),
+        );
+    }
+}
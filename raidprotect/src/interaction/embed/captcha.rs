@@ -0,0 +1,230 @@
+//! Embeds for the captcha verification.
+
+use time::OffsetDateTime;
+use twilight_mention::Mention;
+use twilight_model::{
+    channel::embed::Embed,
+    id::{
+        marker::{ChannelMarker, RoleMarker, UserMarker},
+        Id,
+    },
+    util::Timestamp,
+};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder, ImageSource};
+
+use super::{COLOR_RED, COLOR_TRANSPARENT};
+use crate::{
+    interaction::{component::CaptchaFailure, response::InteractionResponse},
+    translations::Lang,
+};
+
+/// User is not allowed to configure the captcha
+pub fn missing_permission(lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(lang.captcha_missing_permission())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Bot is not allowed to create the captcha role and channel
+pub fn bot_missing_permission(lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(lang.captcha_bot_missing_permission())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Reused unverified role is higher than the bot's highest role
+pub fn role_hierarchy(role: Id<RoleMarker>, lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(lang.captcha_role_hierarchy(role.mention()))
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Bot is missing permissions in the reused verification channel
+pub fn channel_missing_permission(channel: Id<ChannelMarker>, lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(lang.captcha_channel_missing_permission(channel.mention()))
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Captcha has been enabled
+///
+/// The `failed` parameter contains the channels whose permissions could not be
+/// updated.
+pub fn enabled(
+    channel: Id<ChannelMarker>,
+    role: Id<RoleMarker>,
+    failed: &[Id<ChannelMarker>],
+    lang: Lang,
+) -> InteractionResponse {
+    let mut embed = EmbedBuilder::new()
+        .color(COLOR_TRANSPARENT)
+        .description(lang.captcha_enabled(channel.mention(), role.mention()));
+
+    if !failed.is_empty() {
+        let channels = failed
+            .iter()
+            .map(|channel| channel.mention().to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        embed = embed.color(COLOR_RED).field(EmbedFieldBuilder::new(
+            lang.captcha_enabled_failed(failed.len()),
+            channels,
+        ));
+    }
+
+    InteractionResponse::EphemeralEmbed(embed.build())
+}
+
+/// Message sent in the verification channel
+pub fn verification_message(lang: Lang) -> Embed {
+    EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(lang.captcha_message_title())
+        .description(lang.captcha_message_description())
+        .build()
+}
+
+/// Captcha image sent to the member
+///
+/// The `remaining` parameter is the number of remaining attempts after an
+/// incorrect code.
+pub fn challenge(image: &str, remaining: Option<u8>, lang: Lang) -> Result<Embed, anyhow::Error> {
+    let mut description = String::new();
+
+    if let Some(remaining) = remaining {
+        description.push_str(&lang.captcha_challenge_retry(remaining));
+        description.push_str("\n\n");
+    }
+
+    description.push_str(lang.captcha_challenge_description());
+
+    Ok(EmbedBuilder::new()
+        .color(COLOR_TRANSPARENT)
+        .title(lang.captcha_challenge_title())
+        .description(description)
+        .image(ImageSource::attachment(image)?)
+        .build())
+}
+
+/// Member has no pending verification
+pub fn not_pending(lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(lang.captcha_not_pending())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Member completed the verification
+pub fn verified(lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_TRANSPARENT)
+        .description(lang.captcha_verified())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Member entered too many incorrect codes
+pub fn failed(lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(lang.captcha_failed())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Message sent in the captcha logs channel
+///
+/// The `failure` parameter is [`None`] if the member has been verified.
+pub fn logs_message(user_id: Id<UserMarker>, failure: Option<CaptchaFailure>, lang: Lang) -> Embed {
+    let (title, color) = match failure {
+        None => (lang.captcha_logs_verified_title(), COLOR_TRANSPARENT),
+        Some(CaptchaFailure::Attempts) => (lang.captcha_logs_attempts_title(), COLOR_RED),
+        Some(CaptchaFailure::Timeout) => (lang.captcha_logs_timeout_title(), COLOR_RED),
+    };
+
+    let mut embed = EmbedBuilder::new()
+        .color(color)
+        .title(title)
+        .description(format!("{} ({user_id})", user_id.mention()));
+
+    if let Ok(timestamp) = Timestamp::from_secs(OffsetDateTime::now_utc().unix_timestamp()) {
+        embed = embed.timestamp(timestamp);
+    }
+
+    embed.build()
+}
+
+/// Message sent in the guild's logs channel when the configuration is updated
+pub fn config_logs_message(description: &str, lang: Lang) -> Embed {
+    let mut embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(lang.captcha_config_logs_title())
+        .description(description);
+
+    if let Ok(timestamp) = Timestamp::from_secs(OffsetDateTime::now_utc().unix_timestamp()) {
+        embed = embed.timestamp(timestamp);
+    }
+
+    embed.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use rosetta_i18n::Language;
+
+    use super::*;
+
+    #[test]
+    fn test_enabled() {
+        enabled(Id::new(1), Id::new(2), &[], Lang::fallback());
+        enabled(Id::new(1), Id::new(2), &[Id::new(3)], Lang::fallback());
+    }
+
+    #[test]
+    fn test_setup_errors() {
+        role_hierarchy(Id::new(1), Lang::fallback());
+        channel_missing_permission(Id::new(1), Lang::fallback());
+    }
+
+    #[test]
+    fn test_challenge() {
+        challenge("captcha.png", None, Lang::fallback()).unwrap();
+
+        let embed = challenge("captcha.png", Some(2), Lang::En).unwrap();
+        assert!(embed
+            .description
+            .unwrap()
+            .starts_with("The code is incorrect"));
+    }
+
+    #[test]
+    fn test_logs_message() {
+        logs_message(Id::new(1), None, Lang::fallback());
+        logs_message(Id::new(1), Some(CaptchaFailure::Attempts), Lang::fallback());
+        logs_message(Id::new(1), Some(CaptchaFailure::Timeout), Lang::fallback());
+    }
+
+    #[test]
+    fn test_config_logs_message() {
+        let lang = Lang::fallback();
+
+        config_logs_message(lang.captcha_channel_deleted(), lang);
+    }
+}
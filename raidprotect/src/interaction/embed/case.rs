@@ -0,0 +1,43 @@
+//! Embeds for the case command.
+
+use twilight_util::builder::embed::EmbedBuilder;
+
+use super::{COLOR_RED, COLOR_TRANSPARENT};
+use crate::{interaction::response::InteractionResponse, translations::Lang};
+
+/// Case does not exist in the guild
+pub fn not_found(case: u64, lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(lang.case_not_found(case))
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Case has been successfully edited
+pub fn edit_success(case: u64, lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_TRANSPARENT)
+        .description(lang.case_edit_success(case))
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+#[cfg(test)]
+mod tests {
+    use rosetta_i18n::Language;
+
+    use super::*;
+
+    #[test]
+    fn test_not_found() {
+        not_found(1, Lang::fallback());
+    }
+
+    #[test]
+    fn test_edit_success() {
+        edit_success(1, Lang::fallback());
+    }
+}
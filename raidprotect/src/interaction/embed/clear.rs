@@ -0,0 +1,152 @@
+//! Embeds for the clear command.
+
+use time::OffsetDateTime;
+use twilight_mention::Mention;
+use twilight_model::{
+    channel::embed::Embed,
+    id::{
+        marker::{ChannelMarker, UserMarker},
+        Id,
+    },
+    util::Timestamp,
+};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder};
+
+use super::{COLOR_RED, COLOR_TRANSPARENT};
+use crate::{
+    interaction::{command::moderation::ClearFilter, response::InteractionResponse},
+    translations::Lang,
+};
+
+/// User is not allowed to delete messages in the channel
+pub fn missing_permission(lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(lang.clear_missing_permission())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Bot is not allowed to delete messages in the channel
+pub fn bot_missing_permission(lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(lang.clear_bot_missing_permission())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// No message matching the filters has been found
+pub fn no_messages(lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(lang.clear_no_messages())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Messages have been successfully deleted
+pub fn success(count: usize, lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_TRANSPARENT)
+        .description(lang.clear_success(count))
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Message sent in the guild's logs channel
+pub fn logs_message(
+    moderator: Id<UserMarker>,
+    channel: Id<ChannelMarker>,
+    count: usize,
+    filter: &ClearFilter,
+    lang: Lang,
+) -> Embed {
+    let mut filters = Vec::new();
+
+    if let Some(user) = filter.user {
+        filters.push(lang.clear_filter_user(user.mention()));
+    }
+    if filter.links {
+        filters.push(lang.clear_filter_links().to_string());
+    }
+    if filter.invites {
+        filters.push(lang.clear_filter_invites().to_string());
+    }
+    if filter.attachments {
+        filters.push(lang.clear_filter_attachments().to_string());
+    }
+    if filter.bots {
+        filters.push(lang.clear_filter_bots().to_string());
+    }
+
+    let mut embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(lang.clear_logs_title())
+        .field(
+            EmbedFieldBuilder::new(lang.clear_logs_channel(), channel.mention().to_string())
+                .inline(),
+        )
+        .field(
+            EmbedFieldBuilder::new(lang.sanction_moderator(), moderator.mention().to_string())
+                .inline(),
+        )
+        .field(EmbedFieldBuilder::new(lang.clear_logs_count(), count.to_string()).inline());
+
+    if !filters.is_empty() {
+        embed = embed.field(EmbedFieldBuilder::new(
+            lang.clear_logs_filters(),
+            filters.join("\n"),
+        ));
+    }
+
+    if let Ok(timestamp) = Timestamp::from_secs(OffsetDateTime::now_utc().unix_timestamp()) {
+        embed = embed.timestamp(timestamp);
+    }
+
+    embed.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use rosetta_i18n::Language;
+
+    use super::*;
+
+    #[test]
+    fn test_missing_permission() {
+        missing_permission(Lang::fallback());
+    }
+
+    #[test]
+    fn test_bot_missing_permission() {
+        bot_missing_permission(Lang::fallback());
+    }
+
+    #[test]
+    fn test_no_messages() {
+        no_messages(Lang::fallback());
+    }
+
+    #[test]
+    fn test_success() {
+        success(10, Lang::fallback());
+    }
+
+    #[test]
+    fn test_logs_message() {
+        let filter = ClearFilter {
+            user: Some(Id::new(3)),
+            links: true,
+            invites: true,
+            attachments: true,
+            bots: true,
+        };
+
+        logs_message(Id::new(1), Id::new(2), 10, &filter, Lang::fallback());
+    }
+}
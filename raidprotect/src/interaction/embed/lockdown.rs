@@ -0,0 +1,167 @@
+//! Embeds for the lockdown and unlock commands.
+
+use time::OffsetDateTime;
+use twilight_mention::Mention;
+use twilight_model::{
+    channel::embed::Embed,
+    id::{
+        marker::{ChannelMarker, UserMarker},
+        Id,
+    },
+    util::Timestamp,
+};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder};
+
+use super::{COLOR_RED, COLOR_TRANSPARENT};
+use crate::{
+    interaction::{command::moderation::LockdownReport, response::InteractionResponse},
+    translations::Lang,
+};
+
+/// Maximum number of channels listed in an embed field.
+const MAX_LISTED_CHANNELS: usize = 30;
+
+/// User is not allowed to manage the channels
+pub fn missing_permission(lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(lang.lockdown_missing_permission())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Result of the lockdown or unlock of channels
+///
+/// The `locked` parameter indicates whether the channels have been locked or
+/// unlocked.
+pub fn report(report: &LockdownReport, locked: bool, lang: Lang) -> InteractionResponse {
+    if report.updated.is_empty() && report.failed.is_empty() {
+        let description = match locked {
+            true => lang.lockdown_none(),
+            false => lang.unlock_none(),
+        };
+        let embed = EmbedBuilder::new()
+            .color(COLOR_RED)
+            .description(description)
+            .build();
+
+        return InteractionResponse::EphemeralEmbed(embed);
+    }
+
+    let description = match locked {
+        true => lang.lockdown_success(report.updated.len()),
+        false => lang.unlock_success(report.updated.len()),
+    };
+
+    let mut embed = EmbedBuilder::new()
+        .color(COLOR_TRANSPARENT)
+        .description(description);
+
+    if !report.failed.is_empty() {
+        embed = embed
+            .color(COLOR_RED)
+            .field(EmbedFieldBuilder::new(
+                lang.lockdown_failed(report.failed.len()),
+                channel_list(&report.failed, lang),
+            ))
+            .field(EmbedFieldBuilder::new(
+                lang.lockdown_failed_help_title(),
+                lang.lockdown_failed_help(),
+            ));
+    }
+
+    InteractionResponse::EphemeralEmbed(embed.build())
+}
+
+/// Message sent in the guild's logs channel
+pub fn logs_message(
+    moderator: Id<UserMarker>,
+    report: &LockdownReport,
+    locked: bool,
+    lang: Lang,
+) -> Embed {
+    let title = match locked {
+        true => lang.lockdown_logs_title(),
+        false => lang.unlock_logs_title(),
+    };
+
+    let mut embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(title)
+        .field(EmbedFieldBuilder::new(
+            lang.sanction_moderator(),
+            moderator.mention().to_string(),
+        ))
+        .field(EmbedFieldBuilder::new(
+            lang.lockdown_logs_channels(report.updated.len()),
+            channel_list(&report.updated, lang),
+        ));
+
+    if let Ok(timestamp) = Timestamp::from_secs(OffsetDateTime::now_utc().unix_timestamp()) {
+        embed = embed.timestamp(timestamp);
+    }
+
+    embed.build()
+}
+
+/// Format a list of channel mentions.
+///
+/// At most [`MAX_LISTED_CHANNELS`] are listed to fit in an embed field.
+fn channel_list(channels: &[Id<ChannelMarker>], lang: Lang) -> String {
+    let mut list = channels
+        .iter()
+        .take(MAX_LISTED_CHANNELS)
+        .map(|channel| channel.mention().to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if channels.len() > MAX_LISTED_CHANNELS {
+        list.push(' ');
+        list.push_str(&lang.lockdown_more(channels.len() - MAX_LISTED_CHANNELS));
+    }
+
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use rosetta_i18n::Language;
+
+    use super::*;
+
+    #[test]
+    fn test_missing_permission() {
+        missing_permission(Lang::fallback());
+    }
+
+    #[test]
+    fn test_report() {
+        let report = LockdownReport {
+            updated: vec![Id::new(1)],
+            skipped: 1,
+            failed: vec![Id::new(2)],
+        };
+
+        super::report(&report, true, Lang::fallback());
+        super::report(&LockdownReport::default(), false, Lang::fallback());
+    }
+
+    #[test]
+    fn test_channel_list() {
+        let channels = (1..=40).map(Id::new).collect::<Vec<_>>();
+
+        assert_eq!(channel_list(&channels[..2], Lang::En), "<#1> <#2>");
+        assert!(channel_list(&channels, Lang::En).ends_with("<#30> and 10 more"));
+    }
+
+    #[test]
+    fn test_logs_message() {
+        let report = LockdownReport {
+            updated: vec![Id::new(1), Id::new(2)],
+            ..Default::default()
+        };
+
+        logs_message(Id::new(3), &report, true, Lang::fallback());
+    }
+}
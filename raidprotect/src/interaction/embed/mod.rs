@@ -2,11 +2,33 @@
 //!
 //! This crate contains types used to generate embeds used as bot responses.
 
+pub mod automod;
+pub mod captcha;
+pub mod case;
+pub mod clear;
 pub mod error;
-pub mod kick;
+pub mod lockdown;
+pub mod modlogs;
+pub mod raid;
+pub mod sanction;
+
+use time::OffsetDateTime;
+use twilight_mention::{
+    timestamp::{Timestamp as TimestampMention, TimestampStyle},
+    Mention,
+};
 
 /// RaidProtect's red color.
 pub const COLOR_RED: u32 = 0xd35f5f;
 
 /// Transparent embed color (dark theme)
 pub const COLOR_TRANSPARENT: u32 = 0x2f3136;
+
+/// Format a date as a Discord timestamp with its relative time.
+fn format_date(date: OffsetDateTime) -> String {
+    let timestamp = date.unix_timestamp() as u64;
+    let long = TimestampMention::new(timestamp, Some(TimestampStyle::LongDateTime)).mention();
+    let relative = TimestampMention::new(timestamp, Some(TimestampStyle::RelativeTime)).mention();
+
+    format!("{long} ({relative})")
+}
@@ -0,0 +1,155 @@
+//! Embeds for the modlogs command.
+
+use raidprotect_model::mongodb::modlog::{Modlog, ModlogType};
+use twilight_mention::Mention;
+use twilight_model::{channel::embed::Embed, user::User};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder, EmbedFooterBuilder};
+
+use super::{format_date, COLOR_RED, COLOR_TRANSPARENT};
+use crate::{interaction::response::InteractionResponse, translations::Lang, util::TextProcessExt};
+
+/// User is not allowed to see the modlogs
+pub fn missing_permission(lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(lang.modlogs_missing_permission_title())
+        .description(lang.modlogs_missing_permission_description())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Page of the modlogs of a user
+///
+/// The `page` parameter starts at zero.
+pub fn modlogs_page(
+    user: &User,
+    modlogs: &[Modlog],
+    page: u64,
+    total_pages: u64,
+    lang: Lang,
+) -> Embed {
+    let username = user.name.remove_markdown().truncate(30);
+    let mut embed = EmbedBuilder::new()
+        .color(COLOR_TRANSPARENT)
+        .title(lang.modlogs_title(&username))
+        .footer(EmbedFooterBuilder::new(
+            lang.modlogs_page(page + 1, total_pages),
+        ));
+
+    if modlogs.is_empty() {
+        embed = embed.description(lang.modlogs_empty(username));
+    }
+
+    for modlog in modlogs {
+        let reason = modlog
+            .reason
+            .clone()
+            .unwrap_or_else(|| lang.sanction_no_reason().to_string());
+
+        let mut value = format!(
+            "{}\n**{}:** {}\n**{}:** {}",
+            format_date(modlog.date),
+            lang.sanction_reason(),
+            reason.truncate(200),
+            lang.sanction_moderator(),
+            modlog.moderator.id.mention()
+        );
+
+        if let Some(expires_at) = modlog.expires_at {
+            value.push_str(&format!(
+                "\n**{}:** {}",
+                lang.sanction_expires(),
+                format_date(expires_at)
+            ));
+        }
+
+        if let Some(revocation) = &modlog.revoked {
+            value.push_str(&format!(
+                "\n*{}*",
+                lang.modlogs_revoked(revocation.moderator.id.mention())
+            ));
+        }
+
+        let name = match modlog.case {
+            Some(case) => format!(
+                "{} • {}",
+                lang.sanction_case(case),
+                kind_name(modlog.kind, lang)
+            ),
+            None => kind_name(modlog.kind, lang).to_string(),
+        };
+
+        embed = embed.field(EmbedFieldBuilder::new(name, value));
+    }
+
+    embed.build()
+}
+
+/// Localized name of a modlog type.
+pub fn kind_name(kind: ModlogType, lang: Lang) -> &'static str {
+    match kind {
+        ModlogType::Kick => lang.modlogs_kind_kick(),
+        ModlogType::Ban => lang.modlogs_kind_ban(),
+        ModlogType::Mute => lang.modlogs_kind_mute(),
+        ModlogType::Warn => lang.modlogs_kind_warn(),
+        ModlogType::Flag => lang.modlogs_kind_flag(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use raidprotect_model::mongodb::modlog::ModlogUser;
+    use rosetta_i18n::Language;
+    use time::OffsetDateTime;
+    use twilight_model::id::Id;
+
+    use super::*;
+
+    #[test]
+    fn test_missing_permission() {
+        missing_permission(Lang::fallback());
+    }
+
+    #[test]
+    fn test_modlogs_page() {
+        let user = User {
+            accent_color: None,
+            avatar: None,
+            banner: None,
+            bot: false,
+            discriminator: 1,
+            email: None,
+            flags: None,
+            id: Id::new(1),
+            locale: None,
+            mfa_enabled: None,
+            name: "user".to_string(),
+            premium_type: None,
+            public_flags: None,
+            system: None,
+            verified: None,
+        };
+        let modlog = Modlog {
+            id: None,
+            case: None,
+            kind: ModlogType::Ban,
+            guild_id: Id::new(2),
+            user: ModlogUser::from(&user),
+            moderator: ModlogUser::from(&user),
+            date: OffsetDateTime::now_utc(),
+            reason: Some("reason".to_string()),
+            notes: None,
+            expires_at: Some(OffsetDateTime::now_utc()),
+            expired: false,
+            warns: Vec::new(),
+            revoked: None,
+            notified: None,
+            logs_message: None,
+            edits: Vec::new(),
+        };
+
+        modlogs_page(&user, &[modlog], 0, 1, Lang::fallback());
+        modlogs_page(&user, &[], 0, 1, Lang::fallback());
+    }
+}
@@ -0,0 +1,49 @@
+//! Embeds for the anti-raid module.
+
+use raidprotect_model::mongodb::guild::AntiRaid;
+use time::OffsetDateTime;
+use twilight_model::{channel::embed::Embed, util::Timestamp};
+use twilight_util::builder::embed::EmbedBuilder;
+
+use super::COLOR_RED;
+use crate::translations::Lang;
+
+/// Message sent in the guild's logs channel when a raid is detected
+pub fn logs_message(count: u64, config: &AntiRaid, lang: Lang) -> Embed {
+    let mut description = vec![
+        lang.raid_logs_description(count, config.window),
+        lang.raid_logs_cooldown(config.cooldown),
+    ];
+
+    if config.kick {
+        description.push(lang.raid_logs_kick().to_string());
+    }
+
+    let mut embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(lang.raid_logs_title())
+        .description(description.join("\n"));
+
+    if let Ok(timestamp) = Timestamp::from_secs(OffsetDateTime::now_utc().unix_timestamp()) {
+        embed = embed.timestamp(timestamp);
+    }
+
+    embed.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use rosetta_i18n::Language;
+
+    use super::*;
+
+    #[test]
+    fn test_logs_message() {
+        let config = AntiRaid {
+            kick: true,
+            ..Default::default()
+        };
+
+        logs_message(15, &config, Lang::fallback());
+    }
+}
@@ -0,0 +1,586 @@
+//! Embeds for the sanction commands.
+//!
+//! These embeds are shared between all moderation commands. The title of the
+//! embeds depends on the type of the sanction.
+
+use raidprotect_model::mongodb::modlog::{
+    Modlog, ModlogRevocation, ModlogType, ModlogUser, SanctionKind,
+};
+use time::OffsetDateTime;
+use twilight_mention::Mention;
+use twilight_model::{
+    channel::embed::Embed,
+    id::{marker::UserMarker, Id},
+    util::Timestamp,
+};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder, EmbedFooterBuilder};
+
+use super::{format_date, COLOR_RED, COLOR_TRANSPARENT};
+use crate::{interaction::response::InteractionResponse, translations::Lang, util::TextProcessExt};
+
+/// User is not a server member.
+pub fn not_member(user: String, lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(lang.sanction_not_member(user.remove_markdown().truncate(30)))
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// The provided duration is invalid
+pub fn invalid_duration(lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(lang.invalid_duration_title())
+        .description(lang.invalid_duration_description())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// The provided ban duration exceeds the maximum allowed
+pub fn ban_duration_too_long(lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(lang.ban_duration_too_long_title())
+        .description(lang.ban_duration_too_long_description())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// The provided mute duration exceeds the maximum allowed by Discord
+pub fn mute_duration_too_long(lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(lang.mute_duration_too_long_title())
+        .description(lang.mute_duration_too_long_description())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Members with the administrator permission cannot be muted
+pub fn mute_member_administrator(lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(lang.mute_missing_permission_title())
+        .description(lang.mute_member_administrator())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Bot is missing the permission required to apply the sanction
+pub fn bot_missing_permission(kind: SanctionKind, lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(bot_missing_permission_title(kind, lang))
+        .description(lang.bot_missing_permission())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// User cannot apply the sanction due to the role hierarchy
+pub fn user_hierarchy(kind: SanctionKind, lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(missing_permission_title(kind, lang))
+        .description(lang.hierarchy_user())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Bot cannot apply the sanction due to the role hierarchy
+pub fn bot_hierarchy(kind: SanctionKind, lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(bot_missing_permission_title(kind, lang))
+        .description(lang.hierarchy_bot())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Member is the guild owner, and thus cannot be sanctioned
+pub fn member_owner(kind: SanctionKind, lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(missing_permission_title(kind, lang))
+        .description(lang.hierarchy_owner())
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Sanction has been successfully applied
+pub fn success(kind: SanctionKind, user: String, lang: Lang) -> InteractionResponse {
+    let user = user.remove_markdown().truncate(30);
+    let description = match kind {
+        SanctionKind::Kick => lang.kick_success(user),
+        SanctionKind::Ban => lang.ban_success(user),
+        SanctionKind::Mute => lang.mute_success(user),
+        SanctionKind::Warn => lang.warn_success(user),
+    };
+
+    let embed = EmbedBuilder::new()
+        .color(COLOR_TRANSPARENT)
+        .description(description)
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Private message sent to the sanctioned user
+///
+/// The `moderator` is [`None`] if the guild has enabled anonymized sanctions.
+pub fn user_notification(
+    kind: SanctionKind,
+    guild: String,
+    reason: Option<String>,
+    expires_at: Option<OffsetDateTime>,
+    moderator: Option<&ModlogUser>,
+    appeal_url: Option<&str>,
+    lang: Lang,
+) -> Embed {
+    let guild = guild.remove_markdown();
+    let description = match kind {
+        SanctionKind::Kick => lang.kick_user_notification(guild),
+        SanctionKind::Ban => lang.ban_user_notification(guild),
+        SanctionKind::Mute => lang.mute_user_notification(guild),
+        SanctionKind::Warn => lang.warn_user_notification(guild),
+    };
+    let reason = reason.unwrap_or_else(|| lang.sanction_no_reason().to_string());
+
+    let mut embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(description)
+        .field(EmbedFieldBuilder::new(lang.sanction_reason(), reason));
+
+    if let Some(moderator) = moderator {
+        let moderator = format!(
+            "{}#{:04}",
+            moderator.name.remove_markdown(),
+            moderator.discriminator
+        );
+
+        embed = embed.field(EmbedFieldBuilder::new(lang.sanction_moderator(), moderator));
+    }
+
+    if let Some(expires_at) = expires_at {
+        embed = embed.field(EmbedFieldBuilder::new(
+            lang.sanction_expires(),
+            format_date(expires_at),
+        ));
+    }
+
+    if let Some(appeal_url) = appeal_url {
+        embed = embed.field(EmbedFieldBuilder::new(
+            lang.sanction_appeal(),
+            lang.sanction_appeal_link(appeal_url),
+        ));
+    }
+
+    embed.build()
+}
+
+/// Private message sent to the user when the sanction could not be applied
+/// after they have been notified
+pub fn user_notification_failed(guild: String, lang: Lang) -> Embed {
+    EmbedBuilder::new()
+        .color(COLOR_TRANSPARENT)
+        .description(lang.sanction_failed_notification(guild.remove_markdown()))
+        .build()
+}
+
+/// Message sent in the guild's logs channel
+pub fn logs_message(modlog: &Modlog, lang: Lang) -> Embed {
+    let title = match modlog.kind {
+        ModlogType::Kick => lang.kick_logs_title(),
+        ModlogType::Ban => lang.ban_logs_title(),
+        ModlogType::Mute => lang.mute_logs_title(),
+        ModlogType::Warn => lang.warn_logs_title(),
+        ModlogType::Flag => lang.flag_logs_title(),
+    };
+    let user = format!(
+        "{} ({}#{:04})",
+        modlog.user.id.mention(),
+        modlog.user.name.remove_markdown(),
+        modlog.user.discriminator
+    );
+    let reason = modlog
+        .reason
+        .clone()
+        .unwrap_or_else(|| lang.sanction_no_reason().to_string());
+
+    let mut embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .title(title)
+        .field(EmbedFieldBuilder::new(lang.sanction_user(), user).inline())
+        .field(
+            EmbedFieldBuilder::new(
+                lang.sanction_moderator(),
+                modlog.moderator.id.mention().to_string(),
+            )
+            .inline(),
+        )
+        .field(EmbedFieldBuilder::new(lang.sanction_reason(), reason))
+        .footer(footer(modlog.user.id, modlog.case, lang));
+
+    if let Some(expires_at) = modlog.expires_at {
+        embed = embed.field(EmbedFieldBuilder::new(
+            lang.sanction_expires(),
+            format_date(expires_at),
+        ));
+    }
+
+    if let Some(notes) = &modlog.notes {
+        embed = embed.field(EmbedFieldBuilder::new(lang.sanction_notes(), notes));
+    }
+
+    if modlog.notified == Some(false) {
+        embed = embed.field(EmbedFieldBuilder::new(
+            lang.sanction_notification(),
+            lang.sanction_not_notified(),
+        ));
+    }
+
+    if let Some(edit) = modlog.edits.last() {
+        embed = embed.field(EmbedFieldBuilder::new(
+            lang.sanction_edited(),
+            lang.sanction_edited_by(format_date(edit.date), edit.editor.id.mention()),
+        ));
+    }
+
+    if let Ok(timestamp) = Timestamp::from_secs(modlog.date.unix_timestamp()) {
+        embed = embed.timestamp(timestamp);
+    }
+
+    embed.build()
+}
+
+/// User is not banned from the guild
+pub fn not_banned(user: String, lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(lang.unban_not_banned(user.remove_markdown().truncate(30)))
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Member is not muted
+pub fn not_muted(user: String, lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_RED)
+        .description(lang.unmute_not_muted(user.remove_markdown().truncate(30)))
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// User has been successfully unbanned
+pub fn unban_success(user: String, lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_TRANSPARENT)
+        .description(lang.unban_success(user.remove_markdown().truncate(30)))
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Member has been successfully unmuted
+pub fn unmute_success(user: String, lang: Lang) -> InteractionResponse {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_TRANSPARENT)
+        .description(lang.unmute_success(user.remove_markdown().truncate(30)))
+        .build();
+
+    InteractionResponse::EphemeralEmbed(embed)
+}
+
+/// Message sent in the guild's logs channel when a sanction is revoked
+///
+/// The `case` is the case number of the revoked sanction, if any.
+pub fn revoke_logs_message(
+    kind: ModlogType,
+    user: &ModlogUser,
+    revocation: &ModlogRevocation,
+    case: Option<u64>,
+    lang: Lang,
+) -> Embed {
+    let title = match kind {
+        ModlogType::Ban => lang.unban_logs_title(),
+        ModlogType::Mute => lang.unmute_logs_title(),
+        ModlogType::Kick | ModlogType::Warn | ModlogType::Flag => {
+            lang.sanction_revoked_logs_title()
+        }
+    };
+    let user_field = format!(
+        "{} ({}#{:04})",
+        user.id.mention(),
+        user.name.remove_markdown(),
+        user.discriminator
+    );
+    let reason = revocation
+        .reason
+        .clone()
+        .unwrap_or_else(|| lang.sanction_no_reason().to_string());
+
+    let mut embed = EmbedBuilder::new()
+        .color(COLOR_TRANSPARENT)
+        .title(title)
+        .field(EmbedFieldBuilder::new(lang.sanction_user(), user_field).inline())
+        .field(
+            EmbedFieldBuilder::new(
+                lang.sanction_moderator(),
+                revocation.moderator.id.mention().to_string(),
+            )
+            .inline(),
+        )
+        .field(EmbedFieldBuilder::new(lang.sanction_reason(), reason))
+        .footer(footer(user.id, case, lang));
+
+    if let Ok(timestamp) = Timestamp::from_secs(revocation.date.unix_timestamp()) {
+        embed = embed.timestamp(timestamp);
+    }
+
+    embed.build()
+}
+
+/// Footer of logs messages, with the user id and the case number.
+fn footer(user_id: Id<UserMarker>, case: Option<u64>, lang: Lang) -> EmbedFooterBuilder {
+    match case {
+        Some(case) => {
+            EmbedFooterBuilder::new(format!("{} • ID: {}", lang.sanction_case(case), user_id))
+        }
+        None => EmbedFooterBuilder::new(format!("ID: {user_id}")),
+    }
+}
+
+/// Title of embeds when the user is missing permissions.
+fn missing_permission_title(kind: SanctionKind, lang: Lang) -> &'static str {
+    match kind {
+        SanctionKind::Kick => lang.kick_missing_permission_title(),
+        SanctionKind::Ban => lang.ban_missing_permission_title(),
+        SanctionKind::Mute => lang.mute_missing_permission_title(),
+        SanctionKind::Warn => lang.warn_missing_permission_title(),
+    }
+}
+
+/// Title of embeds when the bot is missing permissions.
+fn bot_missing_permission_title(kind: SanctionKind, lang: Lang) -> &'static str {
+    match kind {
+        SanctionKind::Kick => lang.kick_bot_missing_permission_title(),
+        SanctionKind::Ban => lang.ban_bot_missing_permission_title(),
+        SanctionKind::Mute => lang.mute_bot_missing_permission_title(),
+        SanctionKind::Warn => lang.warn_bot_missing_permission_title(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use raidprotect_model::mongodb::modlog::ModlogEdit;
+    use rosetta_i18n::Language;
+
+    use super::*;
+
+    #[test]
+    fn test_not_member() {
+        not_member("test".to_string(), Lang::fallback());
+    }
+
+    #[test]
+    fn test_invalid_duration() {
+        invalid_duration(Lang::fallback());
+    }
+
+    #[test]
+    fn test_ban_duration_too_long() {
+        ban_duration_too_long(Lang::fallback());
+    }
+
+    #[test]
+    fn test_mute_duration_too_long() {
+        mute_duration_too_long(Lang::fallback());
+    }
+
+    #[test]
+    fn test_mute_member_administrator() {
+        mute_member_administrator(Lang::fallback());
+    }
+
+    #[test]
+    fn test_bot_missing_permission() {
+        bot_missing_permission(SanctionKind::Kick, Lang::fallback());
+    }
+
+    #[test]
+    fn test_user_hierarchy() {
+        user_hierarchy(SanctionKind::Kick, Lang::fallback());
+    }
+
+    #[test]
+    fn test_bot_hierarchy() {
+        bot_hierarchy(SanctionKind::Kick, Lang::fallback());
+    }
+
+    #[test]
+    fn test_member_owner() {
+        member_owner(SanctionKind::Kick, Lang::fallback());
+    }
+
+    #[test]
+    fn test_success() {
+        success(SanctionKind::Ban, "test".to_string(), Lang::fallback());
+    }
+
+    #[test]
+    fn test_user_notification() {
+        user_notification(
+            SanctionKind::Ban,
+            "guild".to_string(),
+            None,
+            Some(OffsetDateTime::now_utc()),
+            None,
+            None,
+            Lang::fallback(),
+        );
+    }
+
+    #[test]
+    fn test_user_notification_failed() {
+        user_notification_failed("guild".to_string(), Lang::fallback());
+    }
+
+    #[test]
+    fn test_user_notification_moderator() {
+        let moderator = ModlogUser {
+            id: Id::new(1),
+            name: "moderator".to_string(),
+            discriminator: 1,
+            avatar: None,
+        };
+
+        user_notification(
+            SanctionKind::Kick,
+            "guild".to_string(),
+            Some("reason".to_string()),
+            None,
+            Some(&moderator),
+            Some("https://example.com/appeal"),
+            Lang::fallback(),
+        );
+    }
+
+    #[test]
+    fn test_logs_message() {
+        let user = ModlogUser {
+            id: Id::new(1),
+            name: "user".to_string(),
+            discriminator: 1,
+            avatar: None,
+        };
+        let modlog = Modlog {
+            id: None,
+            case: None,
+            kind: ModlogType::Kick,
+            guild_id: Id::new(2),
+            user: user.clone(),
+            moderator: user,
+            date: OffsetDateTime::now_utc(),
+            reason: Some("reason".to_string()),
+            notes: Some("notes".to_string()),
+            expires_at: None,
+            expired: false,
+            warns: Vec::new(),
+            revoked: None,
+            notified: None,
+            logs_message: None,
+            edits: Vec::new(),
+        };
+
+        logs_message(&modlog, Lang::fallback());
+    }
+
+    #[test]
+    fn test_logs_message_edited() {
+        let user = ModlogUser {
+            id: Id::new(1),
+            name: "user".to_string(),
+            discriminator: 1,
+            avatar: None,
+        };
+        let modlog = Modlog {
+            id: None,
+            case: Some(1),
+            kind: ModlogType::Warn,
+            guild_id: Id::new(2),
+            user: user.clone(),
+            moderator: user.clone(),
+            date: OffsetDateTime::now_utc(),
+            reason: Some("reason".to_string()),
+            notes: None,
+            expires_at: None,
+            expired: false,
+            warns: Vec::new(),
+            revoked: None,
+            notified: Some(false),
+            logs_message: None,
+            edits: vec![ModlogEdit {
+                editor: user,
+                date: OffsetDateTime::now_utc(),
+                reason: None,
+                notes: None,
+            }],
+        };
+
+        logs_message(&modlog, Lang::fallback());
+    }
+
+    #[test]
+    fn test_not_banned() {
+        not_banned("test".to_string(), Lang::fallback());
+    }
+
+    #[test]
+    fn test_not_muted() {
+        not_muted("test".to_string(), Lang::fallback());
+    }
+
+    #[test]
+    fn test_unban_success() {
+        unban_success("test".to_string(), Lang::fallback());
+    }
+
+    #[test]
+    fn test_unmute_success() {
+        unmute_success("test".to_string(), Lang::fallback());
+    }
+
+    #[test]
+    fn test_revoke_logs_message() {
+        let user = ModlogUser {
+            id: Id::new(1),
+            name: "user".to_string(),
+            discriminator: 1,
+            avatar: None,
+        };
+        let revocation = ModlogRevocation {
+            moderator: user.clone(),
+            date: OffsetDateTime::now_utc(),
+            reason: Some("reason".to_string()),
+        };
+
+        revoke_logs_message(
+            ModlogType::Ban,
+            &user,
+            &revocation,
+            Some(1),
+            Lang::fallback(),
+        );
+    }
+}
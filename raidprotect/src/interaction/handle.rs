@@ -14,21 +14,39 @@ use twilight_model::{
 };
 
 use super::{
-    command::{help::HelpCommand, moderation::KickCommand, profile::ProfileCommand},
-    component::PostInChat,
+    command::{
+        captcha::CaptchaCommand,
+        help::HelpCommand,
+        moderation::{
+            BanCommand, CaseCommand, ClearCommand, KickCommand, LockdownCommand, ModlogsCommand,
+            MuteCommand, Sanction, UnbanCommand, UnlockCommand, UnmuteCommand, WarnCommand,
+        },
+        profile::ProfileCommand,
+    },
+    component::{captcha, CaptchaCode, CaptchaVerify, ModlogsPage, PostInChat},
     embed,
     response::{InteractionResponder, InteractionResponse},
     util::InteractionExt,
 };
 use crate::{cluster::ClusterState, translations::Lang};
 
+/// Commands that may take longer than the interaction response deadline.
+///
+/// The response to these commands is deferred and sent as a follow-up
+/// message.
+const DEFERRED_COMMANDS: &[&str] = &["captcha", "clear", "lockdown", "unlock"];
+
 /// Handle incoming [`Interaction`].
 pub async fn handle_interaction(interaction: Interaction, state: Arc<ClusterState>) {
-    let responder = InteractionResponder::from_interaction(&interaction);
+    let mut responder = InteractionResponder::from_interaction(&interaction);
     debug!("received {} interaction", interaction.kind.kind());
 
     let lang = interaction.locale().unwrap_or_else(|_| Lang::fallback());
 
+    if is_deferred(&interaction) {
+        responder.defer(&state).await;
+    }
+
     let response = match interaction.kind {
         InteractionType::ApplicationCommand => handle_command(interaction, &state).await,
         InteractionType::MessageComponent => handle_component(interaction, &state).await,
@@ -52,6 +70,16 @@ pub async fn handle_interaction(interaction: Interaction, state: Arc<ClusterStat
     }
 }
 
+/// Whether the response to an interaction should be deferred.
+///
+/// See [`DEFERRED_COMMANDS`].
+fn is_deferred(interaction: &Interaction) -> bool {
+    match &interaction.data {
+        Some(InteractionData::ApplicationCommand(data)) => DEFERRED_COMMANDS.contains(&&*data.name),
+        _ => false,
+    }
+}
+
 /// Handle incoming command interaction.
 async fn handle_command(
     interaction: Interaction,
@@ -66,6 +94,17 @@ async fn handle_command(
     match name {
         "profile" => ProfileCommand::handle(interaction, state).await,
         "kick" => KickCommand::handle(interaction, state).await,
+        "ban" => BanCommand::handle(interaction, state).await,
+        "mute" => MuteCommand::handle(interaction, state).await,
+        "warn" => WarnCommand::handle(interaction, state).await,
+        "unban" => UnbanCommand::handle(interaction, state).await,
+        "unmute" => UnmuteCommand::handle(interaction, state).await,
+        "modlogs" => ModlogsCommand::handle(interaction, state).await,
+        "case" => CaseCommand::handle(interaction, state).await,
+        "clear" => ClearCommand::handle(interaction, state).await,
+        "lockdown" => LockdownCommand::handle(interaction, state).await,
+        "unlock" => UnlockCommand::handle(interaction, state).await,
+        "captcha" => CaptchaCommand::handle(interaction, state).await,
         "help" => HelpCommand::handle(interaction, state).await,
         name => {
             warn!(name = name, "received unknown command");
@@ -85,6 +124,13 @@ async fn handle_component(
         _ => bail!("expected message component data"),
     };
 
+    // Captcha components are shared by all members and have no pending state.
+    match custom_id {
+        captcha::VERIFY_BUTTON_ID => return CaptchaVerify::handle(interaction, state).await,
+        captcha::CODE_BUTTON_ID => return CaptchaCode::handle(interaction, state).await,
+        _ => {}
+    }
+
     let lang = interaction.locale()?;
     let component = match state
         .redis()
@@ -100,6 +146,9 @@ async fn handle_component(
         PendingComponent::PostInChat(component) => {
             PostInChat::handle(interaction, component, state).await
         }
+        PendingComponent::ModlogsPage(component) => {
+            ModlogsPage::handle(interaction, component, state).await
+        }
     }
 }
 
@@ -113,6 +162,10 @@ async fn handle_modal(
         _ => bail!("expected modal submit data"),
     };
 
+    if custom_id == captcha::CODE_MODAL_ID {
+        return CaptchaCode::handle_modal(interaction, state).await;
+    }
+
     let lang = interaction.locale()?;
     let modal = match state
         .redis()
@@ -125,7 +178,10 @@ async fn handle_modal(
     };
 
     match modal {
-        PendingModal::Sanction(_) => bail!("not implemented"),
+        PendingModal::Sanction(sanction) => {
+            Sanction::handle_modal(interaction, sanction, state).await
+        }
+        PendingModal::CaseEdit(case) => CaseCommand::handle_modal(interaction, case, state).await,
     }
 }
 
@@ -134,6 +190,17 @@ pub async fn register_commands(state: &ClusterState, application_id: Id<Applicat
     let commands: Vec<Command> = vec![
         ProfileCommand::create_command().into(),
         KickCommand::create_command().into(),
+        BanCommand::create_command().into(),
+        MuteCommand::create_command().into(),
+        WarnCommand::create_command().into(),
+        UnbanCommand::create_command().into(),
+        UnmuteCommand::create_command().into(),
+        ModlogsCommand::create_command().into(),
+        CaseCommand::create_command().into(),
+        ClearCommand::create_command().into(),
+        LockdownCommand::create_command().into(),
+        UnlockCommand::create_command().into(),
+        CaptchaCommand::create_command().into(),
         HelpCommand::create_command().into(),
     ];
 
@@ -28,6 +28,8 @@ pub struct InteractionResponder {
     pub application_id: Id<ApplicationMarker>,
     /// Token of the command.
     pub token: String,
+    /// Whether the response has been deferred.
+    deferred: bool,
 }
 
 impl InteractionResponder {
@@ -37,11 +39,33 @@ impl InteractionResponder {
             id: interaction.id,
             application_id: interaction.application_id,
             token: interaction.token.clone(),
+            deferred: false,
         }
     }
 
+    /// Defer the response to an interaction.
+    ///
+    /// An ephemeral loading message is shown to the user until the response
+    /// is sent with [`respond`], which sends it as a follow-up message. This
+    /// should be used for interactions that may take longer than the 3 seconds
+    /// allowed by Discord to respond.
+    ///
+    /// [`respond`]: Self::respond
+    pub async fn defer(&mut self, state: &ClusterState) {
+        self.respond(state, InteractionResponse::EphemeralDeferredMessage)
+            .await;
+        self.deferred = true;
+    }
+
     /// Send a response to an interaction.
+    ///
+    /// The response is sent as a follow-up message if the response has been
+    /// deferred.
     pub async fn respond(&self, state: &ClusterState, response: InteractionResponse) {
+        if self.deferred {
+            return self.followup(state, response).await;
+        }
+
         let client = state.http().interaction(self.application_id);
 
         if let Err(error) = client
@@ -52,6 +76,49 @@ impl InteractionResponder {
             error!(error = ?error, "failed to respond to interaction");
         }
     }
+
+    /// Send a follow-up message to an interaction.
+    ///
+    /// Modals cannot be sent as follow-up messages.
+    pub async fn followup(&self, state: &ClusterState, response: InteractionResponse) {
+        let data = match response.into_http().data {
+            Some(data) if data.custom_id.is_none() => data,
+            _ => {
+                error!("cannot send a follow-up message without message data");
+                return;
+            }
+        };
+
+        let client = state.http().interaction(self.application_id);
+        let embeds = data.embeds.unwrap_or_default();
+        let components = data.components.unwrap_or_default();
+        let attachments = data.attachments.unwrap_or_default();
+
+        let result = async {
+            let mut request = client
+                .create_followup(&self.token)
+                .embeds(&embeds)?
+                .components(&components)?
+                .attachments(&attachments)?;
+
+            if let Some(content) = &data.content {
+                request = request.content(content)?;
+            }
+
+            if let Some(flags) = data.flags {
+                request = request.flags(flags);
+            }
+
+            request.exec().await?;
+
+            Ok::<_, anyhow::Error>(())
+        }
+        .await;
+
+        if let Err(error) = result {
+            error!(error = ?error, "failed to send interaction follow-up");
+        }
+    }
 }
 
 /// Response to an interaction.
@@ -4,7 +4,7 @@ use std::mem;
 use anyhow::{bail, Context};
 use twilight_interactions::command::CommandModel;
 use twilight_model::{
-    application::interaction::{Interaction, InteractionData},
+    application::interaction::{modal::ModalInteractionData, Interaction, InteractionData},
     guild::PartialMember,
     id::{marker::GuildMarker, Id},
 };
@@ -70,6 +70,33 @@ where
     T::from_interaction(data.into()).context("failed to parse command data")
 }
 
+/// Parse incoming [`ModalSubmit`] interaction data.
+///
+/// This takes a mutable [`Interaction`] since the inner [`ModalInteractionData`]
+/// is replaced with [`None`] to avoid useless clones.
+///
+/// [`ModalSubmit`]: twilight_model::application::interaction::InteractionType::ModalSubmit
+pub fn parse_modal_data(
+    interaction: &mut Interaction,
+) -> Result<ModalInteractionData, anyhow::Error> {
+    match mem::take(&mut interaction.data) {
+        Some(InteractionData::ModalSubmit(data)) => Ok(data),
+        _ => bail!("unable to parse modal data, received unknown data type"),
+    }
+}
+
+/// Get the value of a modal text input from its custom id.
+///
+/// Empty values are returned as [`None`].
+pub fn parse_modal_field<'a>(data: &'a ModalInteractionData, custom_id: &str) -> Option<&'a str> {
+    data.components
+        .iter()
+        .flat_map(|row| &row.components)
+        .find(|component| component.custom_id == custom_id)
+        .and_then(|component| component.value.as_deref())
+        .filter(|value| !value.is_empty())
+}
+
 /// Implement `handle` method for a command type.
 ///
 /// The generated method will parse the command from an interaction and execute
@@ -8,11 +8,13 @@
 //! - `event`: Discord event handlers
 //! - `interaction`: interaction handlers
 //! - `model`: models shared between crates
+//! - `task`: background tasks such as lifting expired sanctions
 //! - `util`: contain utilities such as logging and shutdown
 
 mod cluster;
 mod event;
 mod interaction;
+mod task;
 mod util;
 
 use anyhow::{Context, Result};
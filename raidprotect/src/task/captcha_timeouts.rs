@@ -0,0 +1,67 @@
+//! Kick members that did not complete the captcha in time.
+//!
+//! The deadline of each pending captcha verification is stored in the cache.
+//! This task periodically queries verifications whose deadline has passed and
+//! kicks the members. Verifications of guilds where the captcha has been
+//! disabled in the meantime are removed without kicking the member.
+
+use std::{sync::Arc, time::Duration};
+
+use raidprotect_model::cache::model::captcha::{PendingCaptcha, PendingCaptchaId};
+use time::OffsetDateTime;
+use tracing::{error, info_span, Instrument};
+
+use crate::{
+    cluster::ClusterState,
+    interaction::component::{captcha::kick_member, CaptchaFailure},
+};
+
+/// Interval between two checks of expired verifications.
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Periodically kick members that did not complete the captcha in time.
+///
+/// This function never returns and is intended to be run alongside the
+/// cluster events handler.
+pub async fn captcha_timeouts(state: Arc<ClusterState>) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let span = info_span!("captcha_timeouts");
+        if let Err(error) = kick_expired(&state).instrument(span).await {
+            error!(error = ?error, "failed to kick unverified members");
+        }
+    }
+}
+
+/// Kick all members whose verification has expired.
+async fn kick_expired(state: &ClusterState) -> Result<(), anyhow::Error> {
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+
+    for id in state.redis().expired_captchas(now).await? {
+        // Expired verifications are not retried since they have been removed
+        // from the deadlines.
+        if let Err(error) = kick_unverified(id, state).await {
+            error!(error = ?error, guild = %id.guild_id, user = %id.user_id, "failed to kick unverified member");
+        }
+    }
+
+    Ok(())
+}
+
+/// Kick a single member whose verification has expired.
+async fn kick_unverified(id: PendingCaptchaId, state: &ClusterState) -> Result<(), anyhow::Error> {
+    if state.redis().get::<PendingCaptcha>(&id).await?.is_none() {
+        return Ok(());
+    }
+
+    let config = state.mongodb().get_guild_or_create(id.guild_id).await?;
+
+    if !config.captcha.enabled {
+        return state.redis().remove_pending_captcha(id).await;
+    }
+
+    kick_member(id, CaptchaFailure::Timeout, &config, state).await
+}
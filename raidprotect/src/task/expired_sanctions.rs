@@ -0,0 +1,96 @@
+//! Lift expired temporary sanctions.
+//!
+//! Temporary sanctions are stored in the database with their expiry date. This
+//! task periodically queries sanctions that have expired and lift them. Since
+//! the state is persisted in the database, sanctions that expired while the
+//! bot was offline are lifted on the next run. Sanctions that have been
+//! manually revoked are ignored.
+
+use std::{sync::Arc, time::Duration};
+
+use futures::TryStreamExt;
+use raidprotect_model::mongodb::modlog::{Modlog, ModlogType};
+use time::OffsetDateTime;
+use tracing::{error, info_span, Instrument};
+use twilight_http::{error::ErrorType, request::AuditLogReason};
+
+use crate::cluster::ClusterState;
+
+/// Interval between two checks of expired sanctions.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Periodically lift expired temporary sanctions.
+///
+/// This function never returns and is intended to be run alongside the
+/// cluster events handler.
+pub async fn expired_sanctions(state: Arc<ClusterState>) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let span = info_span!("expired_sanctions");
+        if let Err(error) = lift_expired(&state).instrument(span).await {
+            error!(error = ?error, "failed to lift expired sanctions");
+        }
+    }
+}
+
+/// Lift all sanctions that have expired.
+async fn lift_expired(state: &ClusterState) -> Result<(), anyhow::Error> {
+    let mut modlogs = state
+        .mongodb()
+        .find_expired_modlogs(OffsetDateTime::now_utc())
+        .await?;
+
+    while let Some(modlog) = modlogs.try_next().await? {
+        let id = match modlog.id {
+            Some(id) => id,
+            None => continue,
+        };
+
+        // Errors are logged and the sanction is retried on the next run.
+        if let Err(error) = lift_sanction(&modlog, state).await {
+            error!(error = ?error, modlog = %id, "failed to lift expired sanction");
+            continue;
+        }
+
+        state.mongodb().expire_modlog(id).await?;
+    }
+
+    Ok(())
+}
+
+/// Lift a single expired sanction.
+async fn lift_sanction(modlog: &Modlog, state: &ClusterState) -> Result<(), anyhow::Error> {
+    match modlog.kind {
+        ModlogType::Ban => {
+            // The user may have been banned again since, in which case the
+            // newer ban must not be lifted.
+            let active = state
+                .mongodb()
+                .find_active_sanction(modlog.guild_id, modlog.user.id, ModlogType::Ban)
+                .await?;
+
+            if active.and_then(|active| active.id) != modlog.id {
+                return Ok(());
+            }
+
+            let request = state
+                .http()
+                .delete_ban(modlog.guild_id, modlog.user.id)
+                .reason("Temporary ban expired")?;
+
+            match request.exec().await {
+                Ok(_) => Ok(()),
+                Err(error) => match error.kind() {
+                    // The ban has already been lifted manually.
+                    ErrorType::Response { status, .. } if status.get() == 404 => Ok(()),
+                    _ => Err(error.into()),
+                },
+            }
+        }
+        // Timeouts are automatically lifted by Discord.
+        ModlogType::Kick | ModlogType::Mute | ModlogType::Warn | ModlogType::Flag => Ok(()),
+    }
+}
@@ -0,0 +1,12 @@
+//! Background tasks.
+//!
+//! This module contains tasks that run periodically in the background while
+//! the bot is running, such as lifting expired temporary sanctions.
+
+mod captcha_timeouts;
+mod expired_sanctions;
+mod phishing_domains;
+
+pub use captcha_timeouts::captcha_timeouts;
+pub use expired_sanctions::expired_sanctions;
+pub use phishing_domains::phishing_domains;
@@ -0,0 +1,37 @@
+//! Refresh the list of phishing domains.
+//!
+//! The list of known phishing domains is loaded when the bot starts and is
+//! periodically reloaded, so that it can be updated without restarting the
+//! bot. If the list fails to load, the previously loaded domains are kept.
+
+use std::{sync::Arc, time::Duration};
+
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{cluster::ClusterState, event::message::DomainSource};
+
+/// Interval between two refreshes of the phishing domains.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Periodically refresh the list of phishing domains.
+///
+/// This function never returns and is intended to be run alongside the
+/// cluster events handler.
+pub async fn phishing_domains(state: Arc<ClusterState>, source: impl DomainSource + Sync) {
+    let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let span = info_span!("phishing_domains");
+        match state
+            .phishing_domains()
+            .refresh(&source)
+            .instrument(span)
+            .await
+        {
+            Ok(count) => info!("loaded {} phishing domains", count),
+            Err(error) => error!(error = ?error, "failed to refresh phishing domains"),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Human-readable duration parsing.
+
+use time::Duration;
+
+/// Parse a human-readable duration.
+///
+/// The duration is composed of one or more number followed by a unit, such as
+/// `10m`, `2h` or `1d12h`. The following units are supported: `s` (seconds),
+/// `m` (minutes), `h` (hours), `d` (days) and `w` (weeks).
+///
+/// [`None`] is returned if the duration is invalid or equal to zero.
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim().to_lowercase();
+
+    if value.is_empty() {
+        return None;
+    }
+
+    let mut total = Duration::ZERO;
+    let mut number = String::new();
+
+    for c in value.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            ' ' => continue,
+            unit => {
+                let amount: i64 = number.parse().ok()?;
+                let seconds = match unit {
+                    's' => 1,
+                    'm' => 60,
+                    'h' => 60 * 60,
+                    'd' => 24 * 60 * 60,
+                    'w' => 7 * 24 * 60 * 60,
+                    _ => return None,
+                };
+
+                total = total.checked_add(Duration::seconds(amount.checked_mul(seconds)?))?;
+                number.clear();
+            }
+        }
+    }
+
+    // Trailing number without unit
+    if !number.is_empty() || total.is_zero() {
+        return None;
+    }
+
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30s"), Some(Duration::seconds(30)));
+        assert_eq!(parse_duration("10m"), Some(Duration::minutes(10)));
+        assert_eq!(parse_duration("2h"), Some(Duration::hours(2)));
+        assert_eq!(parse_duration("7d"), Some(Duration::days(7)));
+        assert_eq!(parse_duration("2w"), Some(Duration::weeks(2)));
+    }
+
+    #[test]
+    fn test_parse_duration_combined() {
+        assert_eq!(
+            parse_duration("1d12h"),
+            Some(Duration::days(1) + Duration::hours(12))
+        );
+        assert_eq!(
+            parse_duration(" 1H 30M "),
+            Some(Duration::hours(1) + Duration::minutes(30))
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_invalid() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("10"), None);
+        assert_eq!(parse_duration("m"), None);
+        assert_eq!(parse_duration("10x"), None);
+        assert_eq!(parse_duration("0m"), None);
+        assert_eq!(parse_duration("99999999999999999999d"), None);
+    }
+}
@@ -2,10 +2,12 @@
 //!
 //! This module provides various utilities that doesn't fit in other modules.
 
+mod duration;
 mod logs_channel;
 pub mod resource;
 pub mod shutdown;
 mod text;
 
+pub use duration::parse_duration;
 pub use logs_channel::guild_logs_channel;
 pub use text::TextProcessExt;